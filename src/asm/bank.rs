@@ -1,6 +1,7 @@
 use crate::*;
 
 
+#[derive(Clone, Debug)]
 pub struct Bank
 {
     pub name: String,
@@ -20,6 +21,11 @@ pub struct BankData
     pub bank_ref: asm::BankRef,
     pub cur_bit_offset: usize,
     pub invocations: Vec<asm::Invocation>,
+
+    /// The alignment in bits, from `#align` and `#labelalign`,
+    /// that the contents rely on relative to the bank's start.
+    /// The linker places an object's section at a multiple of it.
+    pub alignment: usize,
 }
 
 
@@ -38,6 +44,61 @@ impl Bank
             decl_span: None,
         }
     }
+
+
+    /// Whether both banks are written to the output,
+    /// and their output regions share any position.
+    pub fn output_overlaps(&self, other: &Bank) -> bool
+    {
+        let (outp1, outp2) = match (self.output_offset, other.output_offset)
+        {
+            (Some(outp1), Some(outp2)) => (outp1, outp2),
+            _ => return false,
+        };
+
+        // FIXME: multiplication by wordsize can overflow
+        let size1 = self.addr_size.map(|s| s * self.wordsize);
+        let size2 = other.addr_size.map(|s| s * other.wordsize);
+
+        match (size1, size2)
+        {
+            (None, None) => true,
+            (Some(size1), None) => outp1 + size1 > outp2,
+            (None, Some(size2)) => outp2 + size2 > outp1,
+            (Some(size1), Some(size2)) => outp1 + size1 > outp2 && outp2 + size2 > outp1,
+        }
+    }
+
+
+    /// Returns the position in the output, in bytes,
+    /// where the given address of this bank is written.
+    pub fn get_output_byte_offset(&self, addr: &util::BigInt) -> Option<usize>
+    {
+        let output_offset = self.output_offset?;
+        let addr = addr.checked_to_usize()?;
+        let addr_start = self.addr_start.checked_to_usize()?;
+        let words = addr.checked_sub(addr_start)?;
+
+        Some((output_offset + words * self.wordsize) / 8)
+    }
+
+
+    /// Returns where this bank is in the output, counted in units
+    /// of its own size, which is how debuggers number switchable
+    /// banks laid out one after another. Banks that aren't written
+    /// to the output or have no fixed size are numbered zero.
+    pub fn get_bank_number(&self) -> usize
+    {
+        let bank_bits = self.addr_size
+            .and_then(|size| size.checked_mul(self.wordsize))
+            .filter(|bits| *bits > 0);
+
+        match (self.output_offset, bank_bits)
+        {
+            (Some(output_offset), Some(bank_bits)) => output_offset / bank_bits,
+            _ => 0,
+        }
+    }
 }
 
 
@@ -87,6 +148,23 @@ impl BankData
     }
 
 
+    /// Records that the contents rely on being placed at a
+    /// multiple of the given number of bits.
+    pub fn require_alignment(&mut self, bits: usize)
+    {
+        let mut a = self.alignment;
+        let mut b = bits;
+        while b != 0
+        {
+            let rem = a % b;
+            a = b;
+            b = rem;
+        }
+
+        self.alignment = self.alignment / a * bits;
+    }
+
+
     pub fn bits_until_aligned(&self, state: &asm::State, wordsize: usize) -> usize
     {
 		let _bank = &state.banks[self.bank_ref.index];
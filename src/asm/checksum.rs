@@ -0,0 +1,275 @@
+use crate::*;
+
+
+/// A value computed over the finished output and written
+/// over the placeholder that `#checksum` left in its bank.
+#[derive(Debug)]
+pub struct Checksum
+{
+    pub decl_span: diagn::Span,
+    pub algorithm: ChecksumAlgorithm,
+    pub little_endian: bool,
+    pub ctx: asm::Context,
+    pub range_bank: asm::BankRef,
+    pub range: Option<(expr::Expr, expr::Expr)>,
+}
+
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ChecksumAlgorithm
+{
+    Sum8,
+    Sum16,
+    Xor,
+    Crc16Ccitt,
+    Crc32,
+    Adler32,
+}
+
+
+impl ChecksumAlgorithm
+{
+    pub fn from_name(name: &str) -> Option<ChecksumAlgorithm>
+    {
+        match name
+        {
+            "sum8" => Some(ChecksumAlgorithm::Sum8),
+            "sum16" => Some(ChecksumAlgorithm::Sum16),
+            "xor" => Some(ChecksumAlgorithm::Xor),
+            "crc16-ccitt" => Some(ChecksumAlgorithm::Crc16Ccitt),
+            "crc32" => Some(ChecksumAlgorithm::Crc32),
+            "adler32" => Some(ChecksumAlgorithm::Adler32),
+            _ => None,
+        }
+    }
+
+
+    /// The size of the result, in bits.
+    pub fn size(&self) -> usize
+    {
+        match self
+        {
+            ChecksumAlgorithm::Sum8 => 8,
+            ChecksumAlgorithm::Sum16 => 16,
+            ChecksumAlgorithm::Xor => 8,
+            ChecksumAlgorithm::Crc16Ccitt => 16,
+            ChecksumAlgorithm::Crc32 => 32,
+            ChecksumAlgorithm::Adler32 => 32,
+        }
+    }
+
+
+    pub fn compute(&self, bytes: &[u8]) -> u32
+    {
+        match self
+        {
+            ChecksumAlgorithm::Sum8 =>
+            {
+                bytes.iter().fold(0u32, |sum, b| sum.wrapping_add(*b as u32)) & 0xff
+            }
+
+            ChecksumAlgorithm::Sum16 =>
+            {
+                bytes.iter().fold(0u32, |sum, b| sum.wrapping_add(*b as u32)) & 0xffff
+            }
+
+            ChecksumAlgorithm::Xor =>
+            {
+                bytes.iter().fold(0u32, |x, b| x ^ *b as u32)
+            }
+
+            // CRC-16/CCITT-FALSE: polynomial 0x1021, initial value 0xffff
+            ChecksumAlgorithm::Crc16Ccitt =>
+            {
+                let mut crc = 0xffffu32;
+                for b in bytes
+                {
+                    crc ^= (*b as u32) << 8;
+                    for _ in 0..8
+                    {
+                        crc = if crc & 0x8000 != 0
+                            { (crc << 1) ^ 0x1021 }
+                        else
+                            { crc << 1 };
+                    }
+                }
+
+                crc & 0xffff
+            }
+
+            // The CRC-32 used by zip and PNG
+            ChecksumAlgorithm::Crc32 =>
+            {
+                let mut crc = 0xffffffffu32;
+                for b in bytes
+                {
+                    crc ^= *b as u32;
+                    for _ in 0..8
+                    {
+                        crc = if crc & 1 != 0
+                            { (crc >> 1) ^ 0xedb88320 }
+                        else
+                            { crc >> 1 };
+                    }
+                }
+
+                !crc
+            }
+
+            ChecksumAlgorithm::Adler32 =>
+            {
+                let mut a = 1u32;
+                let mut b = 0u32;
+                for byte in bytes
+                {
+                    a = (a + *byte as u32) % 65521;
+                    b = (b + a) % 65521;
+                }
+
+                (b << 16) | a
+            }
+        }
+    }
+}
+
+
+impl asm::State
+{
+    /// Computes every checksum over the output, in the order they
+    /// were declared, so that a checksum can cover an earlier one.
+    pub fn write_checksums(
+        &self,
+        report: diagn::RcReport,
+        fileserver: &dyn util::FileServer,
+        output: &mut util::BitVec)
+        -> Result<(), ()>
+    {
+        for checksum in &self.checksums
+        {
+            let _guard = report.push_parent("failed to compute checksum", &checksum.decl_span);
+
+            let (start, end) = self.get_checksum_range(report.clone(), fileserver, checksum)?;
+
+            let bytes: Vec<u8> = (start..end)
+                .step_by(8)
+                .map(|index| (0..8).fold(0u8, |byte, i| (byte << 1) | output.read(index + i) as u8))
+                .collect();
+
+            let size = checksum.algorithm.size();
+            let mut value = util::BigInt::new(checksum.algorithm.compute(&bytes), Some(size));
+            if checksum.little_endian
+            {
+                value = value.convert_le();
+            }
+
+            let bank = &self.banks[checksum.ctx.bank_ref.index];
+            output.write_bigint(bank.output_offset.unwrap() + checksum.ctx.bit_offset, value);
+        }
+
+        Ok(())
+    }
+
+
+    /// Returns the output bits covered by a checksum.
+    fn get_checksum_range(
+        &self,
+        report: diagn::RcReport,
+        fileserver: &dyn util::FileServer,
+        checksum: &asm::Checksum)
+        -> Result<(usize, usize), ()>
+    {
+        let bank = &self.banks[checksum.range_bank.index];
+
+        let output_offset = match bank.output_offset
+        {
+            Some(offset) => offset,
+            None =>
+            {
+                report.error_span("bank is non-writable (`#outp` is not set)", &checksum.decl_span);
+                return Err(());
+            }
+        };
+
+        let (start, end) = match checksum.range
+        {
+            None =>
+            {
+                let size = match bank.addr_size
+                {
+                    Some(size) => size * bank.wordsize,
+                    None => self.bankdata[checksum.range_bank.index].cur_bit_offset,
+                };
+
+                (0, size)
+            }
+
+            Some((ref start_expr, ref end_expr)) =>
+            {
+                let start = self.get_checksum_range_bound(report.clone(), fileserver, checksum, start_expr)?;
+                let end = self.get_checksum_range_bound(report.clone(), fileserver, checksum, end_expr)?;
+
+                if end < start
+                {
+                    report.error_span("checksum range ends before it starts", &end_expr.span());
+                    return Err(());
+                }
+
+                (start, end)
+            }
+        };
+
+        if start % 8 != 0 || end % 8 != 0 || output_offset % 8 != 0
+        {
+            report.error_span("checksum range is not byte-aligned", &checksum.decl_span);
+            return Err(());
+        }
+
+        Ok((output_offset + start, output_offset + end))
+    }
+
+
+    /// Evaluates an address in the range of a checksum,
+    /// and returns its bit offset from the start of the bank.
+    fn get_checksum_range_bound(
+        &self,
+        report: diagn::RcReport,
+        fileserver: &dyn util::FileServer,
+        checksum: &asm::Checksum,
+        expr: &expr::Expr)
+        -> Result<usize, ()>
+    {
+        let bank = &self.banks[checksum.range_bank.index];
+
+        let value = self.eval_expr(
+            report.clone(),
+            expr,
+            &checksum.ctx,
+            &mut expr::EvalContext::new(),
+            fileserver,
+            true)?;
+
+        let addr = match value.get_bigint()
+        {
+            Some(addr) => addr,
+            None =>
+            {
+                report.error_span("expected integer value", &expr.span());
+                return Err(());
+            }
+        };
+
+        let words = (&addr - &bank.addr_start)
+            .checked_to_usize()
+            .filter(|words| bank.addr_size.map_or(true, |size| *words <= size));
+
+        match words
+        {
+            Some(words) => Ok(words * bank.wordsize),
+            None =>
+            {
+                report.error_span("address is outside of the bank", &expr.span());
+                Err(())
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,10 @@
+use crate::*;
+
+
+#[derive(Debug)]
+pub struct Condition
+{
+    pub ctx: asm::Context,
+    pub expr: expr::Expr,
+    pub value: bool,
+}
\ No newline at end of file
@@ -0,0 +1,413 @@
+use crate::*;
+
+
+/// A rule whose production is a plain concatenation of
+/// constants and parameter slices, which can be matched
+/// directly against the bits of a binary.
+struct Decoder<'a>
+{
+    rule: &'a asm::Rule,
+    pattern_text: String,
+    fields: Vec<DecoderField>,
+    size: usize,
+    constant_bits: usize,
+}
+
+
+enum DecoderField
+{
+    Constant
+    {
+        size: usize,
+        value: util::BigInt,
+    },
+    Parameter
+    {
+        size: usize,
+        param_index: usize,
+        lsb: usize,
+    },
+}
+
+
+impl asm::State
+{
+    pub fn disassemble(
+        &self,
+        report: diagn::RcReport,
+        fileserver: &dyn util::FileServer,
+        binary: &util::BitVec,
+        bank_name: Option<&str>)
+        -> Result<String, ()>
+    {
+        let bank = self.choose_disassembly_bank(report, bank_name)?;
+        let wordsize = bank.wordsize;
+
+        let mut decoders = Vec::new();
+        for ruleset_ref in &self.active_rulesets
+        {
+            for rule in &self.rulesets[ruleset_ref.index].rules
+            {
+                if let Some(decoder) = Decoder::new(rule, fileserver)
+                {
+                    if decoder.size > 0 && decoder.size % wordsize == 0
+                    {
+                        decoders.push(decoder);
+                    }
+                }
+            }
+        }
+
+        // Try the most specific encodings first, so that
+        // e.g. `inc a` wins over a generic `inc {reg}`.
+        decoders.sort_by(|a, b| b.constant_bits.cmp(&a.constant_bits));
+
+        let mut lines = Vec::new();
+
+        let mut offset = 0;
+        while offset + wordsize <= binary.len()
+        {
+            let mut decoded = None;
+            for decoder in &decoders
+            {
+                if offset + decoder.size > binary.len()
+                {
+                    continue;
+                }
+
+                if let Some(text) = decoder.decode(binary, offset)
+                {
+                    decoded = Some((text, decoder.size));
+                    break;
+                }
+            }
+
+            let (text, size) = decoded.unwrap_or_else(||
+            {
+                let word = read_bits(binary, offset, wordsize);
+                (format!("#d{} 0x{:0>2$x}", wordsize, word, (wordsize + 3) / 4), wordsize)
+            });
+
+            let mut words = Vec::new();
+            for i in 0..(size / wordsize)
+            {
+                let word = read_bits(binary, offset + i * wordsize, wordsize);
+                words.push(format!("{:0>1$x}", word, (wordsize + 3) / 4));
+            }
+
+            let addr = &bank.addr_start + &util::BigInt::from(offset / wordsize);
+            lines.push((text, format!("0x{:x}: {}", addr, words.join(" "))));
+
+            offset += size;
+        }
+
+        // Bytes left over after the last whole word are kept as data
+        if offset < binary.len()
+        {
+            let size = binary.len() - offset;
+            let value = read_bits(binary, offset, size);
+            let addr = &bank.addr_start + &util::BigInt::from(offset / wordsize);
+
+            lines.push((
+                format!("#d{} 0x{:0>2$x}", size, value, (size + 3) / 4),
+                format!("0x{:x}: {:0>2$x} (partial word)", addr, value, (size + 3) / 4)));
+        }
+
+        let text_width = lines.iter().map(|l| l.0.len()).max().unwrap_or(0);
+
+        let mut result = String::new();
+        for (text, comment) in lines
+        {
+            result.push_str(&format!("{:<1$} ; {2}\n", text, text_width, comment));
+        }
+
+        Ok(result)
+    }
+
+
+    /// Picks the bank given by name, or else the only bank
+    /// in use, which is the default bank when no `#bankdef`
+    /// was declared.
+    fn choose_disassembly_bank(
+        &self,
+        report: diagn::RcReport,
+        bank_name: Option<&str>)
+        -> Result<&asm::Bank, ()>
+    {
+        if let Some(bank_name) = bank_name
+        {
+            return match self.banks.iter().find(|b| b.name == bank_name)
+            {
+                Some(bank) => Ok(bank),
+                None =>
+                {
+                    report.error(format!("unknown bank `{}`", bank_name));
+                    Err(())
+                }
+            };
+        }
+
+        match self.banks.len()
+        {
+            1 => Ok(&self.banks[0]),
+            2 => Ok(&self.banks[1]),
+            _ =>
+            {
+                report.error("more than one bank was declared; select one with `--bank`");
+                Err(())
+            }
+        }
+    }
+}
+
+
+impl<'a> Decoder<'a>
+{
+    fn new(rule: &'a asm::Rule, fileserver: &dyn util::FileServer) -> Option<Decoder<'a>>
+    {
+        let mut decoder = Decoder
+        {
+            rule,
+            pattern_text: fileserver.get_excerpt(&rule.span),
+            fields: Vec::new(),
+            size: 0,
+            constant_bits: 0,
+        };
+
+        decoder.add_fields(&rule.production)?;
+
+        // Every parameter needs to appear in the
+        // production, or its value can't be recovered.
+        for param_index in 0..rule.parameters.len()
+        {
+            if let asm::PatternParameterType::Ruleset(_) = rule.parameters[param_index].typ
+            {
+                return None;
+            }
+
+            let is_used = decoder.fields.iter().any(|f| match f
+            {
+                DecoderField::Parameter { param_index: index, .. } => *index == param_index,
+                _ => false,
+            });
+
+            if !is_used
+            {
+                return None;
+            }
+        }
+
+        Some(decoder)
+    }
+
+
+    fn add_fields(&mut self, expr: &expr::Expr) -> Option<()>
+    {
+        match expr
+        {
+            expr::Expr::Block(_, exprs) if exprs.len() == 1 =>
+            {
+                self.add_fields(&exprs[0])
+            }
+
+            expr::Expr::BinaryOp(_, _, expr::BinaryOp::Concat, lhs, rhs) =>
+            {
+                self.add_fields(lhs)?;
+                self.add_fields(rhs)
+            }
+
+            expr::Expr::Literal(_, expr::Value::Integer(bigint)) =>
+            {
+                let size = bigint.size?;
+                self.add_constant(size, bigint.slice(size, 0));
+                Some(())
+            }
+
+            expr::Expr::BitSlice(_, _, left, right, inner) =>
+            {
+                match inner.as_ref()
+                {
+                    expr::Expr::Literal(_, expr::Value::Integer(bigint)) =>
+                    {
+                        self.add_constant(left - right, bigint.slice(*left, *right));
+                        Some(())
+                    }
+
+                    expr::Expr::Variable(_, 0, hierarchy) if hierarchy.len() == 1 =>
+                    {
+                        let param_index = self.find_parameter(&hierarchy[0])?;
+                        self.add_parameter(left - right, param_index, *right);
+                        Some(())
+                    }
+
+                    _ => None,
+                }
+            }
+
+            expr::Expr::Variable(_, 0, hierarchy) if hierarchy.len() == 1 =>
+            {
+                let param_index = self.find_parameter(&hierarchy[0])?;
+                let size = match self.rule.parameters[param_index].typ
+                {
+                    asm::PatternParameterType::Unsigned(size) |
+                    asm::PatternParameterType::Signed(size) |
+                    asm::PatternParameterType::Integer(size) => size,
+                    _ => return None,
+                };
+
+                self.add_parameter(size, param_index, 0);
+                Some(())
+            }
+
+            _ => None,
+        }
+    }
+
+
+    fn find_parameter(&self, name: &str) -> Option<usize>
+    {
+        self.rule.parameters.iter().position(|p| p.name == name)
+    }
+
+
+    fn add_constant(&mut self, size: usize, value: util::BigInt)
+    {
+        self.fields.push(DecoderField::Constant { size, value });
+        self.size += size;
+        self.constant_bits += size;
+    }
+
+
+    fn add_parameter(&mut self, size: usize, param_index: usize, lsb: usize)
+    {
+        self.fields.push(DecoderField::Parameter { size, param_index, lsb });
+        self.size += size;
+    }
+
+
+    fn decode(&self, binary: &util::BitVec, offset: usize) -> Option<String>
+    {
+        let mut values = vec![util::BigInt::from(0); self.rule.parameters.len()];
+        let mut known_bits = vec![Vec::<(usize, bool)>::new(); self.rule.parameters.len()];
+
+        let mut bit_offset = offset;
+        for field in &self.fields
+        {
+            match field
+            {
+                DecoderField::Constant { size, value } =>
+                {
+                    for i in 0..*size
+                    {
+                        if binary.read(bit_offset + i) != value.get_bit(size - 1 - i)
+                        {
+                            return None;
+                        }
+                    }
+
+                    bit_offset += size;
+                }
+
+                DecoderField::Parameter { size, param_index, lsb } =>
+                {
+                    for i in 0..*size
+                    {
+                        let bit_index = lsb + size - 1 - i;
+                        let bit = binary.read(bit_offset + i);
+
+                        // The same parameter bit may appear in more than
+                        // one place, and every copy must agree.
+                        if let Some(known) = known_bits[*param_index].iter().find(|b| b.0 == bit_index)
+                        {
+                            if known.1 != bit
+                            {
+                                return None;
+                            }
+                        }
+
+                        known_bits[*param_index].push((bit_index, bit));
+
+                        if bit
+                        {
+                            values[*param_index] = values[*param_index].set_bit(bit_index, true);
+                        }
+                    }
+
+                    bit_offset += size;
+                }
+            }
+        }
+
+        let mut result = String::new();
+        let mut param_index = 0;
+        let mut in_param = false;
+
+        for c in self.pattern_text.chars()
+        {
+            if in_param
+            {
+                if c == '}'
+                {
+                    in_param = false;
+                }
+            }
+            else if c == '{'
+            {
+                // Glued to a name, as in `r{index}`, the value is
+                // most likely a register number, so skip the prefix.
+                let is_suffix = result.chars().last().map(|c| c.is_alphanumeric()).unwrap_or(false);
+
+                in_param = true;
+                result.push_str(&self.format_parameter(param_index, &values[param_index], is_suffix));
+                param_index += 1;
+            }
+            else
+            {
+                result.push(c);
+            }
+        }
+
+        Some(result)
+    }
+
+
+    fn format_parameter(&self, param_index: usize, value: &util::BigInt, is_suffix: bool) -> String
+    {
+        let prefix = if is_suffix { "" } else { "0x" };
+
+        if let asm::PatternParameterType::Signed(size) = self.rule.parameters[param_index].typ
+        {
+            if size > 0 && value.get_bit(size - 1)
+            {
+                let magnitude = &util::BigInt::from(1).shl(size) - value;
+                return format!("-{}{:x}", prefix, magnitude);
+            }
+        }
+
+        if is_suffix
+        {
+            if let Some(value) = value.checked_to_usize()
+            {
+                return format!("{}", value);
+            }
+        }
+
+        format!("0x{:x}", value)
+    }
+}
+
+
+fn read_bits(binary: &util::BitVec, offset: usize, size: usize) -> util::BigInt
+{
+    let mut value = util::BigInt::from(0);
+
+    for i in 0..size
+    {
+        if binary.read(offset + i)
+        {
+            value = value.set_bit(size - 1 - i, true);
+        }
+    }
+
+    value
+}
\ No newline at end of file
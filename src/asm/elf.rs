@@ -0,0 +1,57 @@
+use crate::*;
+
+
+impl asm::State
+{
+    /// Creates an ELF file with a section for every bank that
+    /// holds data, placed at the bank's address, and a symbol
+    /// table with the labels bound to those sections.
+    pub fn format_elf(&self, binary: &util::BitVec) -> Vec<u8>
+    {
+        let mut elf = util::ElfWriter::new();
+        let mut bank_sections = vec![None; self.banks.len()];
+
+        for output in self.get_bank_outputs(binary)
+        {
+            if output.addr > u32::MAX as usize
+            {
+                continue;
+            }
+
+            let bank = &self.banks[output.bank_ref.index];
+
+            let name = if bank.name.len() == 0
+                { ".text".to_string() }
+            else
+                { bank.name.clone() };
+
+            let (flags, kind) = match output.data
+            {
+                Some(data) =>
+                (
+                    util::SHF_ALLOC | util::SHF_EXECINSTR,
+                    util::ElfSectionKind::ProgBits(data),
+                ),
+
+                None =>
+                (
+                    util::SHF_ALLOC | util::SHF_WRITE,
+                    util::ElfSectionKind::NoBits(output.size as u32),
+                ),
+            };
+
+            bank_sections[output.bank_ref.index] = Some(elf.sections.len());
+            elf.sections.push(util::ElfSection
+            {
+                name,
+                addr: output.addr as u32,
+                flags,
+                kind,
+            });
+        }
+
+        self.symbols.add_elf_symbols(self, &mut elf, &bank_sections);
+
+        elf.build()
+    }
+}
\ No newline at end of file
@@ -79,6 +79,14 @@ impl Invocation
 
         panic!();
     }
+
+
+    /// Identifies the invocation across passes. The symbol
+    /// context tells apart expansions of the same macro line.
+    pub fn size_guess_key(&self) -> (diagn::Span, Vec<String>)
+    {
+        (self.span.clone(), self.ctx.symbol_ctx.hierarchy().to_vec())
+    }
 }
 
 
@@ -0,0 +1,392 @@
+use crate::*;
+use std::collections::HashMap;
+
+
+#[derive(Debug)]
+pub enum ListingEntry
+{
+    FileBegin
+    {
+        filename: String,
+        include_span: Option<diagn::Span>,
+    },
+    FileEnd,
+    Line
+    {
+        span: diagn::Span,
+        ctx: asm::Context,
+        invocations_start: usize,
+        invocations_end: usize,
+        bit_offset_end: usize,
+        nested: bool,
+    },
+    Skipped(diagn::Span),
+}
+
+
+struct ListingRow
+{
+    location: String,
+    bank: String,
+    addr: String,
+    data: String,
+    source: String,
+}
+
+
+struct ListingFile
+{
+    filename: String,
+    chars: Vec<char>,
+    next_line: usize,
+}
+
+
+static WORDS_PER_ROW: usize = 8;
+
+
+impl asm::State
+{
+    pub fn format_listing(
+        &self,
+        binary: &util::BitVec,
+        fileserver: &dyn util::FileServer)
+        -> String
+    {
+        let mut declared_symbols = HashMap::<String, Vec<(usize, util::BigInt)>>::new();
+        let mut symbol_table = Vec::new();
+
+        self.symbols.format(&mut |_, symbol: &asm::Symbol, name: &str, bigint: &util::BigInt|
+        {
+            if let Some((start, _)) = symbol.decl_span.location
+            {
+                if let asm::SymbolKind::Constant = symbol.kind
+                {
+                    declared_symbols
+                        .entry(symbol.decl_span.file.as_ref().clone())
+                        .or_default()
+                        .push((start, bigint.clone()));
+                }
+            }
+
+            let kind = match symbol.kind
+            {
+                asm::SymbolKind::Label => "label",
+                asm::SymbolKind::Constant => "constant",
+            };
+
+            symbol_table.push((name.to_string(), kind, bigint.clone()));
+        });
+
+        let mut rows = Vec::new();
+        let mut file_stack: Vec<ListingFile> = Vec::new();
+
+        for entry in &self.listing
+        {
+            match entry
+            {
+                ListingEntry::FileBegin { filename, include_span } =>
+                {
+                    if include_span.is_some()
+                    {
+                        rows.push(ListingRow::new_marker(format!("; begin include `{}`", filename)));
+                    }
+
+                    let chars = fileserver
+                        .get_chars(diagn::RcReport::new(), filename, None)
+                        .unwrap_or(Vec::new());
+
+                    file_stack.push(ListingFile
+                    {
+                        filename: filename.clone(),
+                        chars,
+                        next_line: 0,
+                    });
+                }
+
+                ListingEntry::FileEnd =>
+                {
+                    let mut file = file_stack.pop().unwrap();
+                    let line_count = util::CharCounter::new(&file.chars).get_line_count();
+                    file.push_plain_rows_until(&mut rows, line_count);
+
+                    if file_stack.len() > 0
+                    {
+                        rows.push(ListingRow::new_marker(format!("; end include `{}`", file.filename)));
+                    }
+                }
+
+                ListingEntry::Skipped(span) =>
+                {
+                    let file = file_stack.last_mut().unwrap();
+                    if let Some(mut row) = file.take_row(&mut rows, span)
+                    {
+                        row.data = "(skipped)".to_string();
+                        rows.push(row);
+                    }
+                }
+
+                ListingEntry::Line { span, ctx, invocations_start, invocations_end, bit_offset_end, nested } =>
+                {
+                    let file = file_stack.last_mut().unwrap();
+                    let line_range = file.get_line_range(span);
+
+                    let mut row = match file.take_row(&mut rows, span)
+                    {
+                        Some(row) => row,
+                        None => continue,
+                    };
+
+                    let bank = &self.banks[ctx.bank_ref.index];
+                    let invocations = &self.bankdata[ctx.bank_ref.index].invocations[*invocations_start..*invocations_end];
+
+                    let mut first_addr = None;
+                    let mut used_bits = 0;
+                    let mut words = Vec::new();
+                    let mut word_addrs = Vec::new();
+
+                    for invoc in invocations
+                    {
+                        let invoc_start = invoc.span.location.map(|l| l.0).unwrap_or(0);
+                        if *invoc.span.file != file.filename || invoc_start < line_range.0 || invoc_start >= line_range.1
+                        {
+                            continue;
+                        }
+
+                        let addr = self.get_addr_aprox(&invoc.ctx);
+                        if first_addr.is_none()
+                        {
+                            first_addr = Some(addr.clone());
+                        }
+
+                        used_bits += invoc.size_guess;
+
+                        if let Some(output_offset) = bank.output_offset
+                        {
+                            let mut index = 0;
+                            while index < invoc.size_guess
+                            {
+                                let word_size = std::cmp::min(ctx.cur_wordsize, invoc.size_guess - index);
+
+                                let mut word = String::new();
+                                for digit in format_digits(binary, output_offset + invoc.ctx.bit_offset + index, word_size)
+                                {
+                                    word.push(digit);
+                                }
+
+                                words.push(word);
+                                word_addrs.push(&addr + &util::BigInt::from(index / ctx.cur_wordsize));
+                                index += word_size;
+                            }
+                        }
+                    }
+
+                    if let Some(value) = declared_symbols.get(&file.filename).and_then(|s|
+                        s.iter().find(|s| s.0 >= line_range.0 && s.0 < line_range.1))
+                    {
+                        row.data = format!("= 0x{:x}", value.1);
+                    }
+
+                    let reserved_bits = if *nested
+                        { 0 }
+                    else
+                        { bit_offset_end.saturating_sub(ctx.bit_offset + used_bits) };
+
+                    if reserved_bits > 0
+                    {
+                        let reserved_words = reserved_bits / ctx.cur_wordsize;
+                        row.data = format!(
+                            "({} word{} reserved)",
+                            reserved_words,
+                            if reserved_words == 1 { "" } else { "s" });
+                    }
+
+                    if first_addr.is_some() || reserved_bits > 0
+                    {
+                        let addr = first_addr.unwrap_or(self.get_addr_aprox(ctx));
+                        row.bank = bank.name.clone();
+                        row.addr = format!("{:x}", addr);
+                    }
+
+                    if words.len() > 0
+                    {
+                        row.data = words[0..std::cmp::min(WORDS_PER_ROW, words.len())].join(" ");
+                    }
+
+                    rows.push(row);
+
+                    let mut index = WORDS_PER_ROW;
+                    while index < words.len()
+                    {
+                        rows.push(ListingRow
+                        {
+                            location: "".to_string(),
+                            bank: bank.name.clone(),
+                            addr: format!("{:x}", word_addrs[index]),
+                            data: words[index..std::cmp::min(index + WORDS_PER_ROW, words.len())].join(" "),
+                            source: "".to_string(),
+                        });
+
+                        index += WORDS_PER_ROW;
+                    }
+                }
+            }
+        }
+
+        let mut result = String::new();
+
+        let location_width = rows.iter().map(|r| r.location.len()).max().unwrap_or(0).max(4);
+        let bank_width = rows.iter().map(|r| r.bank.len()).max().unwrap_or(0).max(4);
+        let addr_width = rows.iter().map(|r| r.addr.len()).max().unwrap_or(0).max(4);
+        let data_width = rows.iter().map(|r| r.data.len()).max().unwrap_or(0).max(4);
+
+        result.push_str(&format!(
+            " {:<4$} | {:<5$} | {:>6$} | {:<7$} | source\n",
+            "line", "bank", "addr", "data",
+            location_width, bank_width, addr_width, data_width));
+        result.push_str("\n");
+
+        for row in &rows
+        {
+            if row.location.len() == 0 && row.addr.len() == 0
+            {
+                result.push_str(&row.source);
+                result.push_str("\n");
+                continue;
+            }
+
+            let line = format!(
+                " {:<5$} | {:<6$} | {:>7$} | {:<8$} | {}",
+                row.location, row.bank, row.addr, row.data, row.source,
+                location_width, bank_width, addr_width, data_width);
+
+            result.push_str(line.trim_end());
+            result.push_str("\n");
+        }
+
+        symbol_table.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let value_width = symbol_table.iter().map(|s| format!("{:x}", s.2).len()).max().unwrap_or(0);
+
+        result.push_str("\n");
+        result.push_str("; symbol table\n");
+        result.push_str("\n");
+
+        for (name, kind, value) in &symbol_table
+        {
+            result.push_str(&format!(
+                " {:>3$} | {:<8} | {}\n",
+                format!("{:x}", value), kind, name,
+                value_width));
+        }
+
+        result
+    }
+}
+
+
+impl ListingRow
+{
+    fn new_marker(text: String) -> ListingRow
+    {
+        ListingRow
+        {
+            location: "".to_string(),
+            bank: "".to_string(),
+            addr: "".to_string(),
+            data: "".to_string(),
+            source: text,
+        }
+    }
+}
+
+
+impl ListingFile
+{
+    fn get_line_index(&self, span: &diagn::Span) -> usize
+    {
+        let counter = util::CharCounter::new(&self.chars);
+        counter.get_line_column_at_index(span.location.map(|l| l.0).unwrap_or(0)).0
+    }
+
+
+    fn get_line_range(&self, span: &diagn::Span) -> (usize, usize)
+    {
+        let counter = util::CharCounter::new(&self.chars);
+        counter.get_index_range_of_line(self.get_line_index(span))
+    }
+
+
+    fn make_row(&self, line: usize) -> ListingRow
+    {
+        let counter = util::CharCounter::new(&self.chars);
+        let range = counter.get_index_range_of_line(line);
+        let source: String = counter.get_excerpt(range.0, range.1).iter().collect();
+
+        ListingRow
+        {
+            location: format!("{}:{}", self.filename, line + 1),
+            bank: "".to_string(),
+            addr: "".to_string(),
+            data: "".to_string(),
+            source: source.trim_end().to_string(),
+        }
+    }
+
+
+    fn push_plain_rows_until(&mut self, rows: &mut Vec<ListingRow>, line: usize)
+    {
+        while self.next_line < line
+        {
+            rows.push(self.make_row(self.next_line));
+            self.next_line += 1;
+        }
+    }
+
+
+    fn take_row(&mut self, rows: &mut Vec<ListingRow>, span: &diagn::Span) -> Option<ListingRow>
+    {
+        // Lines expanded from a macro declared
+        // in another file are listed at the call site.
+        if *span.file != self.filename
+        {
+            return None;
+        }
+
+        let line = self.get_line_index(span);
+        if line < self.next_line
+        {
+            return None;
+        }
+
+        self.push_plain_rows_until(rows, line);
+        self.next_line = line + 1;
+        Some(self.make_row(line))
+    }
+}
+
+
+fn format_digits(binary: &util::BitVec, offset: usize, size: usize) -> Vec<char>
+{
+    let mut digits = Vec::new();
+
+    let first_digit_bits = if size % 4 == 0 { 4 } else { size % 4 };
+
+    let mut index = 0;
+    while index < size
+    {
+        let digit_bits = if index == 0 { first_digit_bits } else { 4 };
+
+        let mut digit = 0;
+        for _ in 0..digit_bits
+        {
+            digit <<= 1;
+            digit |= if binary.read(offset + index) { 1 } else { 0 };
+            index += 1;
+        }
+
+        digits.push(std::char::from_digit(digit, 16).unwrap());
+    }
+
+    digits
+}
\ No newline at end of file
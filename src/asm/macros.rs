@@ -0,0 +1,11 @@
+use crate::*;
+
+
+#[derive(Debug)]
+pub struct Macro
+{
+    pub decl_span: diagn::Span,
+    pub name: String,
+    pub params: Vec<String>,
+    pub body: Vec<syntax::Token>,
+}
\ No newline at end of file
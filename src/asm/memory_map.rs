@@ -0,0 +1,206 @@
+use crate::*;
+
+
+static MAX_LABELLED_REGIONS: usize = 5;
+
+
+impl asm::State
+{
+    /// Summarizes the usage of every bank: the words used and
+    /// free, the gaps left between data by directives like `#res`
+    /// and `#align`, and the largest regions that start at a
+    /// global label.
+    pub fn format_memory_map(&self) -> String
+    {
+        let mut labels = Vec::new();
+        self.symbols.format(&mut |_: &mut String, symbol: &asm::Symbol, name: &str, bigint: &util::BigInt|
+        {
+            if let asm::SymbolKind::Label = symbol.kind
+            {
+                // Skip local labels
+                if name.contains('.')
+                {
+                    return;
+                }
+
+                if let Some(addr) = bigint.checked_to_usize()
+                {
+                    labels.push((symbol.bankref, addr, name.to_string()));
+                }
+            }
+        });
+
+        let mut result = String::new();
+
+        for (index, bank) in self.banks.iter().enumerate()
+        {
+            let bankdata = &self.bankdata[index];
+
+            // The default bank is usually left empty
+            // when other banks are declared
+            if index == 0 && self.banks.len() > 1 && bankdata.cur_bit_offset == 0
+            {
+                continue;
+            }
+
+            let bank_ref = asm::BankRef { index };
+            let wordsize = bank.wordsize;
+            let used_words = (bankdata.cur_bit_offset + wordsize - 1) / wordsize;
+
+            let addr_start = match bank.addr_start.checked_to_usize()
+            {
+                Some(addr) => addr,
+                None => continue,
+            };
+
+            if result.len() > 0
+            {
+                result.push('\n');
+            }
+
+            let name = if bank.name.len() == 0
+                { "(default)".to_string() }
+            else
+                { format!("`{}`", bank.name) };
+
+            result.push_str(&format!(
+                "bank {}: starts at 0x{:x}, {}-bit words\n",
+                name,
+                addr_start,
+                wordsize));
+
+            match bank.addr_size
+            {
+                Some(addr_size) =>
+                {
+                    let percent = if addr_size == 0
+                        { 100.0 }
+                    else
+                        { used_words as f64 * 100.0 / addr_size as f64 };
+
+                    result.push_str(&format!(
+                        "  used: {} of {} words ({:.1}%)\n",
+                        used_words,
+                        addr_size,
+                        percent));
+
+                    result.push_str(&format!(
+                        "  free: {} words\n",
+                        addr_size.saturating_sub(used_words)));
+                }
+
+                None =>
+                {
+                    result.push_str(&format!("  used: {} words (size not set)\n", used_words));
+                }
+            }
+
+            // Banks without `#outp` are reserved wholesale,
+            // so gaps only make sense where data is written
+            if bank.output_offset.is_some()
+            {
+                let gaps = self.get_bank_gaps(bank_ref);
+                let gap_words: usize = gaps.iter().map(|(start, end)| end - start).sum();
+
+                result.push_str(&format!("  gaps: {} words\n", gap_words));
+                for (start, end) in gaps
+                {
+                    result.push_str(&format!(
+                        "    0x{:x} - 0x{:x} ({} word{})\n",
+                        addr_start + start,
+                        addr_start + end - 1,
+                        end - start,
+                        if end - start == 1 { "" } else { "s" }));
+                }
+            }
+
+            let mut bank_labels: Vec<&(asm::BankRef, usize, String)> = labels
+                .iter()
+                .filter(|l| l.0 == bank_ref && l.1 >= addr_start && l.1 <= addr_start + used_words)
+                .collect();
+
+            bank_labels.sort_by_key(|l| l.1);
+
+            let mut regions = Vec::new();
+            for (i, label) in bank_labels.iter().enumerate()
+            {
+                let end = match bank_labels.get(i + 1)
+                {
+                    Some(next) => next.1,
+                    None => addr_start + used_words,
+                };
+
+                if end > label.1
+                {
+                    regions.push((end - label.1, label.1, &label.2));
+                }
+            }
+
+            // Largest first, then in address order
+            regions.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+            if regions.len() > 0
+            {
+                result.push_str("  largest labelled regions:\n");
+                for (size, addr, name) in regions.iter().take(MAX_LABELLED_REGIONS)
+                {
+                    result.push_str(&format!(
+                        "    {}: 0x{:x} ({} word{})\n",
+                        name,
+                        addr,
+                        size,
+                        if *size == 1 { "" } else { "s" }));
+                }
+            }
+        }
+
+        result
+    }
+
+
+    /// Returns the ranges of words, relative to the start
+    /// of the bank, that are skipped over without data.
+    fn get_bank_gaps(&self, bank_ref: asm::BankRef) -> Vec<(usize, usize)>
+    {
+        let bankdata = &self.bankdata[bank_ref.index];
+        let wordsize = self.banks[bank_ref.index].wordsize;
+
+        let mut ranges: Vec<(usize, usize)> = bankdata.invocations
+            .iter()
+            .filter(|invoc| invoc.size_guess > 0)
+            .map(|invoc| (invoc.ctx.bit_offset, invoc.ctx.bit_offset + invoc.size_guess))
+            .collect();
+
+        ranges.sort();
+
+        let mut gaps = Vec::new();
+        let mut cur_bit = 0;
+
+        let mut push_gap = |start_bit: usize, end_bit: usize|
+        {
+            let start = (start_bit + wordsize - 1) / wordsize;
+            let end = end_bit / wordsize;
+            if end > start
+            {
+                gaps.push((start, end));
+            }
+        };
+
+        for (start, end) in ranges
+        {
+            if start > cur_bit
+            {
+                push_gap(cur_bit, start);
+            }
+
+            cur_bit = std::cmp::max(cur_bit, end);
+        }
+
+        if bankdata.cur_bit_offset > cur_bit
+        {
+            push_gap(cur_bit, bankdata.cur_bit_offset);
+        }
+
+        gaps
+    }
+}
\ No newline at end of file
@@ -5,6 +5,17 @@ mod invocation;
 mod bank;
 mod symbol;
 mod function;
+mod macros;
+mod condition;
+mod repeat;
+mod structure;
+mod checksum;
+mod listing;
+mod disassembler;
+mod object;
+mod elf;
+mod output;
+mod memory_map;
 
 
 pub mod parser;
@@ -16,6 +27,8 @@ pub use self::state::Context;
 pub use self::state::BankRef;
 pub use self::state::RulesetRef;
 pub use self::state::RuleRef;
+pub use self::state::AssemblyOutput;
+pub use self::output::BankOutput;
 pub use self::rule::Rule;
 pub use self::rule::PatternPart;
 pub use self::rule::PatternParameter;
@@ -34,4 +47,22 @@ pub use self::symbol::SymbolManager;
 pub use self::symbol::Symbol;
 pub use self::symbol::SymbolKind;
 pub use self::symbol::SymbolContext;
-pub use self::function::Function;
\ No newline at end of file
+pub use self::symbol::ConstantDecl;
+pub use self::function::Function;
+pub use self::macros::Macro;
+pub use self::condition::Condition;
+pub use self::repeat::Iteration;
+pub use self::structure::Struct;
+pub use self::structure::StructField;
+pub use self::checksum::Checksum;
+pub use self::checksum::ChecksumAlgorithm;
+pub use self::listing::ListingEntry;
+pub use self::object::Object;
+pub use self::object::ObjectBank;
+pub use self::object::ObjectItem;
+pub use self::object::ObjectItemKind;
+pub use self::object::ObjectRule;
+pub use self::object::ObjectRuleArgument;
+pub use self::object::ObjectSymbol;
+pub use self::object::ObjectSymbolKind;
+pub use self::object::link;
\ No newline at end of file
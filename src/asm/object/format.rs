@@ -0,0 +1,1039 @@
+use crate::*;
+use super::sexpr::SExpr;
+use super::objfile::*;
+use std::rc::Rc;
+
+
+static OBJECT_VERSION: usize = 1;
+
+
+struct Writer
+{
+    files: Vec<Rc<String>>,
+}
+
+
+struct Reader
+{
+    files: Vec<Rc<String>>,
+}
+
+
+impl Object
+{
+    pub fn serialize(&self) -> String
+    {
+        let mut writer = Writer
+        {
+            files: Vec::new(),
+        };
+
+        let mut body = Vec::new();
+
+        let mut imports = SExpr::list("imports");
+        for (name, span) in &self.imports
+        {
+            imports.push(SExpr::list("import")
+                .with(SExpr::str(name.clone()))
+                .with(writer.span(span)));
+        }
+        body.push(imports);
+
+        for bank in &self.banks
+        {
+            body.push(writer.bank(bank));
+        }
+
+        for symbol in &self.symbols
+        {
+            body.push(writer.symbol(symbol));
+        }
+
+        for function in &self.functions
+        {
+            body.push(writer.function(function));
+        }
+
+        for condition in &self.conditions
+        {
+            body.push(SExpr::list("condition")
+                .with(writer.ctx(&condition.ctx))
+                .with(SExpr::atom(if condition.value { "1" } else { "0" }))
+                .with(writer.expr(&condition.expr)));
+        }
+
+        // The file table is only complete after
+        // everything else has been written.
+        let mut files = SExpr::list("files");
+        for file in &writer.files
+        {
+            files.push(SExpr::str(file.as_ref().clone()));
+        }
+
+        let mut result = SExpr::list("customasm-object")
+            .with(SExpr::atom(format!("{}", OBJECT_VERSION)))
+            .with(files);
+
+        for elem in body
+        {
+            result.push(elem);
+        }
+
+        format!("{}\n", result)
+    }
+
+
+    pub fn deserialize(
+        report: diagn::RcReport,
+        filename: &str,
+        src: &str)
+        -> Result<Object, ()>
+    {
+        match Object::deserialize_inner(src)
+        {
+            Ok(object) => Ok(object),
+            Err(()) =>
+            {
+                report.error(format!("invalid object file `{}`", filename));
+                Err(())
+            }
+        }
+    }
+
+
+    fn deserialize_inner(src: &str) -> Result<Object, ()>
+    {
+        let root = SExpr::parse(src)?;
+        let elems = root.as_tagged("customasm-object")?;
+
+        if elems.len() < 2 || elems[0].as_usize()? != OBJECT_VERSION
+        {
+            return Err(());
+        }
+
+        let mut reader = Reader
+        {
+            files: Vec::new(),
+        };
+
+        for file in elems[1].as_tagged("files")?
+        {
+            reader.files.push(Rc::new(file.as_str()?.to_string()));
+        }
+
+        let mut object = Object
+        {
+            banks: Vec::new(),
+            symbols: Vec::new(),
+            functions: Vec::new(),
+            conditions: Vec::new(),
+            imports: Vec::new(),
+        };
+
+        for elem in &elems[2..]
+        {
+            match elem.tag()?
+            {
+                "imports" =>
+                {
+                    for import in elem.as_tagged("imports")?
+                    {
+                        let args = expect_args(import, "import", 2)?;
+                        object.imports.push((
+                            args[0].as_str()?.to_string(),
+                            reader.span(&args[1])?));
+                    }
+                }
+
+                "bank" => object.banks.push(reader.bank(elem)?),
+                "label" | "constant" => object.symbols.push(reader.symbol(elem)?),
+                "function" => object.functions.push(reader.function(elem)?),
+
+                "condition" =>
+                {
+                    let args = expect_args(elem, "condition", 3)?;
+                    object.conditions.push(asm::Condition
+                    {
+                        ctx: reader.ctx(&args[0])?,
+                        value: args[1].as_usize()? != 0,
+                        expr: reader.expr(&args[2])?,
+                    });
+                }
+
+                _ => return Err(()),
+            }
+        }
+
+        Ok(object)
+    }
+}
+
+
+/// Returns an expression's text in the object format,
+/// which can be used to compare two expressions.
+pub fn expr_to_string(expr: &expr::Expr) -> String
+{
+    let mut writer = Writer
+    {
+        files: Vec::new(),
+    };
+
+    format!("{}", writer.expr(expr))
+}
+
+
+impl Writer
+{
+    fn file(&mut self, file: &Rc<String>) -> usize
+    {
+        match self.files.iter().position(|f| f == file)
+        {
+            Some(index) => index,
+            None =>
+            {
+                self.files.push(file.clone());
+                self.files.len() - 1
+            }
+        }
+    }
+
+
+    fn span(&mut self, span: &diagn::Span) -> SExpr
+    {
+        match span.location
+        {
+            None => SExpr::atom("_"),
+            Some((start, end)) =>
+            {
+                let file = self.file(&span.file);
+                SExpr::atom(format!("{}:{}:{}", file, start, end))
+            }
+        }
+    }
+
+
+    fn ctx(&mut self, ctx: &asm::Context) -> SExpr
+    {
+        let mut hierarchy = SExpr::List(Vec::new());
+        for name in ctx.symbol_ctx.hierarchy()
+        {
+            hierarchy.push(SExpr::str(name.clone()));
+        }
+
+        let mut locals = SExpr::List(Vec::new());
+        for (name, value) in ctx.locals.iter()
+        {
+            locals.push(SExpr::List(vec![
+                SExpr::str(name.clone()),
+                write_bigint(value)]));
+        }
+
+        SExpr::list("ctx")
+            .with(SExpr::atom(format!("{}", ctx.bank_ref.index)))
+            .with(SExpr::atom(format!("{}", ctx.bit_offset)))
+            .with(SExpr::atom(format!("{}", ctx.cur_wordsize)))
+            .with(SExpr::atom(format!("{}", self.file(&ctx.cur_filename))))
+            .with(hierarchy)
+            .with(locals)
+    }
+
+
+    fn bank(&mut self, object_bank: &ObjectBank) -> SExpr
+    {
+        let bank = &object_bank.bank;
+
+        let mut result = SExpr::list("bank")
+            .with(SExpr::str(bank.name.clone()))
+            .with(SExpr::atom(format!("{}", bank.wordsize)))
+            .with(SExpr::atom(format!("{}", bank.labelalign)))
+            .with(write_bigint(&bank.addr_start))
+            .with(write_optional(bank.addr_size))
+            .with(write_optional(bank.output_offset))
+            .with(SExpr::atom(if bank.fill { "1" } else { "0" }))
+            .with(SExpr::atom(format!("{}", object_bank.size)))
+            .with(SExpr::atom(format!("{}", object_bank.alignment)));
+
+        for item in &object_bank.items
+        {
+            let elem = match item.kind
+            {
+                ObjectItemKind::Data(ref data_invoc) => SExpr::list("data")
+                    .with(self.ctx(&item.ctx))
+                    .with(self.span(&item.span))
+                    .with(SExpr::atom(format!("{}", item.size)))
+                    .with(write_optional(data_invoc.elem_size))
+                    .with(self.expr(&data_invoc.expr)),
+
+                ObjectItemKind::Rule(ref rule) => SExpr::list("rule")
+                    .with(self.ctx(&item.ctx))
+                    .with(self.span(&item.span))
+                    .with(SExpr::atom(format!("{}", item.size)))
+                    .with(self.rule(rule)),
+            };
+
+            result.push(elem);
+        }
+
+        result
+    }
+
+
+    fn rule(&mut self, rule: &ObjectRule) -> SExpr
+    {
+        let mut params = SExpr::List(Vec::new());
+        for (param, arg) in rule.parameters.iter().zip(rule.args.iter())
+        {
+            let typ = match param.typ
+            {
+                asm::PatternParameterType::Unspecified => "_".to_string(),
+                asm::PatternParameterType::Ruleset(_) => "ruleset".to_string(),
+                asm::PatternParameterType::Unsigned(size) => format!("u{}", size),
+                asm::PatternParameterType::Signed(size) => format!("s{}", size),
+                asm::PatternParameterType::Integer(size) => format!("i{}", size),
+            };
+
+            let arg = match arg
+            {
+                ObjectRuleArgument::Expression(expr) => self.expr(expr),
+                ObjectRuleArgument::NestedRule(nested) => self.rule(nested),
+            };
+
+            params.push(SExpr::List(vec![
+                SExpr::str(param.name.clone()),
+                SExpr::atom(typ),
+                arg]));
+        }
+
+        SExpr::list("candidate")
+            .with(self.span(&rule.span))
+            .with(params)
+            .with(self.expr(&rule.production))
+    }
+
+
+    fn symbol(&mut self, symbol: &ObjectSymbol) -> SExpr
+    {
+        let mut hierarchy = SExpr::List(Vec::new());
+        for name in &symbol.hierarchy
+        {
+            hierarchy.push(SExpr::str(name.clone()));
+        }
+
+        match symbol.kind
+        {
+            ObjectSymbolKind::Label { bank_ref, ref offset } => SExpr::list("label")
+                .with(self.span(&symbol.decl_span))
+                .with(hierarchy)
+                .with(SExpr::atom(format!("{}", bank_ref.index)))
+                .with(write_bigint(offset)),
+
+            ObjectSymbolKind::Constant { ref ctx, ref expr } => SExpr::list("constant")
+                .with(self.span(&symbol.decl_span))
+                .with(hierarchy)
+                .with(self.ctx(ctx))
+                .with(self.expr(expr)),
+        }
+    }
+
+
+    fn function(&mut self, function: &asm::Function) -> SExpr
+    {
+        let mut params = SExpr::List(Vec::new());
+        for param in &function.params
+        {
+            params.push(SExpr::str(param.clone()));
+        }
+
+        SExpr::list("function")
+            .with(self.span(&function.decl_span))
+            .with(SExpr::str(function.name.clone()))
+            .with(params)
+            .with(self.expr(&function.body))
+    }
+
+
+    fn value(&mut self, value: &expr::Value) -> SExpr
+    {
+        match value
+        {
+            expr::Value::Integer(bigint) => SExpr::list("int")
+                .with(write_bigint(bigint))
+                .with(write_optional(bigint.size)),
+
+            expr::Value::String(s) => SExpr::list("str")
+                .with(SExpr::str(s.utf8_contents.clone()))
+                .with(SExpr::str(s.encoding.clone())),
+
+            expr::Value::Bool(b) => SExpr::list("bool")
+                .with(SExpr::atom(if *b { "1" } else { "0" })),
+
+            expr::Value::BuiltInFunction(name) => SExpr::list("builtin")
+                .with(SExpr::str(name.clone())),
+
+            expr::Value::Void => SExpr::list("void"),
+
+            // User functions are always referred to by name
+            // in the source, so their indices never show up here.
+            expr::Value::Unknown |
+            expr::Value::Function(_) => SExpr::list("unknown"),
+        }
+    }
+
+
+    fn expr(&mut self, expr: &expr::Expr) -> SExpr
+    {
+        match expr
+        {
+            expr::Expr::Literal(span, value) => SExpr::list("lit")
+                .with(self.span(span))
+                .with(self.value(value)),
+
+            expr::Expr::Variable(span, hierarchy_level, hierarchy) =>
+            {
+                let mut names = SExpr::List(Vec::new());
+                for name in hierarchy
+                {
+                    names.push(SExpr::str(name.clone()));
+                }
+
+                SExpr::list("var")
+                    .with(self.span(span))
+                    .with(SExpr::atom(format!("{}", hierarchy_level)))
+                    .with(names)
+            }
+
+            expr::Expr::UnaryOp(span, op_span, op, inner) => SExpr::list("unary")
+                .with(self.span(span))
+                .with(self.span(op_span))
+                .with(SExpr::atom(unary_op_name(*op)))
+                .with(self.expr(inner)),
+
+            expr::Expr::BinaryOp(span, op_span, op, lhs, rhs) => SExpr::list("binary")
+                .with(self.span(span))
+                .with(self.span(op_span))
+                .with(SExpr::atom(binary_op_name(*op)))
+                .with(self.expr(lhs))
+                .with(self.expr(rhs)),
+
+            expr::Expr::TernaryOp(span, cond, true_branch, false_branch) => SExpr::list("ternary")
+                .with(self.span(span))
+                .with(self.expr(cond))
+                .with(self.expr(true_branch))
+                .with(self.expr(false_branch)),
+
+            expr::Expr::BitSlice(span, slice_span, left, right, inner) => SExpr::list("slice")
+                .with(self.span(span))
+                .with(self.span(slice_span))
+                .with(SExpr::atom(format!("{}", left)))
+                .with(SExpr::atom(format!("{}", right)))
+                .with(self.expr(inner)),
+
+            expr::Expr::SoftSlice(span, slice_span, left, right, inner) => SExpr::list("softslice")
+                .with(self.span(span))
+                .with(self.span(slice_span))
+                .with(SExpr::atom(format!("{}", left)))
+                .with(SExpr::atom(format!("{}", right)))
+                .with(self.expr(inner)),
+
+            expr::Expr::Block(span, exprs) =>
+            {
+                let mut result = SExpr::list("block").with(self.span(span));
+                for expr in exprs
+                {
+                    result.push(self.expr(expr));
+                }
+
+                result
+            }
+
+            expr::Expr::While(span, cond, body) => SExpr::list("while")
+                .with(self.span(span))
+                .with(self.expr(cond))
+                .with(self.expr(body)),
+
+            expr::Expr::For(span, var, start, end, body) => SExpr::list("for")
+                .with(self.span(span))
+                .with(SExpr::str(var.clone()))
+                .with(self.expr(start))
+                .with(self.expr(end))
+                .with(self.expr(body)),
+
+            expr::Expr::Let(span, name, value) => SExpr::list("let")
+                .with(self.span(span))
+                .with(SExpr::str(name.clone()))
+                .with(self.expr(value)),
+
+            expr::Expr::Call(span, func, args) =>
+            {
+                let mut result = SExpr::list("call")
+                    .with(self.span(span))
+                    .with(self.expr(func));
+
+                for arg in args
+                {
+                    result.push(self.expr(arg));
+                }
+
+                result
+            }
+
+            expr::Expr::Asm(span, _) => SExpr::list("asm")
+                .with(self.span(span)),
+        }
+    }
+}
+
+
+impl Reader
+{
+    fn span(&self, elem: &SExpr) -> Result<diagn::Span, ()>
+    {
+        let text = elem.as_atom()?;
+        if text == "_"
+        {
+            return Ok(diagn::Span::new_dummy());
+        }
+
+        let parts: Vec<&str> = text.split(':').collect();
+        if parts.len() != 3
+        {
+            return Err(());
+        }
+
+        let file = parse_usize(parts[0])?;
+        let start = parse_usize(parts[1])?;
+        let end = parse_usize(parts[2])?;
+
+        Ok(diagn::Span::new(self.file(file)?, start, end))
+    }
+
+
+    fn file(&self, index: usize) -> Result<Rc<String>, ()>
+    {
+        self.files.get(index).cloned().ok_or(())
+    }
+
+
+    fn ctx(&self, elem: &SExpr) -> Result<asm::Context, ()>
+    {
+        let args = expect_args(elem, "ctx", 6)?;
+
+        let mut locals = Vec::new();
+        for local in args[5].as_list()?
+        {
+            let pair = local.as_list()?;
+            if pair.len() != 2
+            {
+                return Err(());
+            }
+
+            locals.push((pair[0].as_str()?.to_string(), read_bigint(&pair[1])?));
+        }
+
+        Ok(asm::Context
+        {
+            bank_ref: asm::BankRef { index: args[0].as_usize()? },
+            bit_offset: args[1].as_usize()?,
+            cur_wordsize: args[2].as_usize()?,
+            cur_filename: self.file(args[3].as_usize()?)?,
+            symbol_ctx: asm::SymbolContext::new(read_names(&args[4])?),
+            locals: Rc::new(locals),
+        })
+    }
+
+
+    fn bank(&self, elem: &SExpr) -> Result<ObjectBank, ()>
+    {
+        let args = elem.as_tagged("bank")?;
+        if args.len() < 9
+        {
+            return Err(());
+        }
+
+        let bank = asm::Bank
+        {
+            name: args[0].as_str()?.to_string(),
+            wordsize: args[1].as_usize()?,
+            labelalign: args[2].as_usize()?,
+            addr_start: read_bigint(&args[3])?,
+            addr_size: read_optional(&args[4])?,
+            output_offset: read_optional(&args[5])?,
+            fill: args[6].as_usize()? != 0,
+            decl_span: None,
+        };
+
+        let alignment = args[8].as_usize()?;
+
+        if bank.wordsize == 0 || alignment == 0
+        {
+            return Err(());
+        }
+
+        let mut items = Vec::new();
+        for item in &args[9..]
+        {
+            let kind = match item.tag()?
+            {
+                "data" =>
+                {
+                    let args = expect_args(item, "data", 5)?;
+                    ObjectItemKind::Data(asm::DataInvocation
+                    {
+                        elem_size: read_optional(&args[3])?,
+                        expr: self.expr(&args[4])?,
+                    })
+                }
+
+                "rule" =>
+                {
+                    let args = expect_args(item, "rule", 4)?;
+                    ObjectItemKind::Rule(self.rule(&args[3])?)
+                }
+
+                _ => return Err(()),
+            };
+
+            let args = &item.as_list()?[1..];
+            items.push(ObjectItem
+            {
+                ctx: self.ctx(&args[0])?,
+                span: self.span(&args[1])?,
+                size: args[2].as_usize()?,
+                kind,
+            });
+        }
+
+        Ok(ObjectBank
+        {
+            bank,
+            size: args[7].as_usize()?,
+            alignment,
+            items,
+        })
+    }
+
+
+    fn rule(&self, elem: &SExpr) -> Result<ObjectRule, ()>
+    {
+        let args = expect_args(elem, "candidate", 3)?;
+
+        let mut parameters = Vec::new();
+        let mut rule_args = Vec::new();
+
+        for param in args[1].as_list()?
+        {
+            let param = param.as_list()?;
+            if param.len() != 3
+            {
+                return Err(());
+            }
+
+            let typ_name = param[1].as_atom()?;
+            let typ = match typ_name
+            {
+                "_" => asm::PatternParameterType::Unspecified,
+                "ruleset" => asm::PatternParameterType::Ruleset(asm::RulesetRef { index: 0 }),
+                _ if typ_name.len() > 1 =>
+                {
+                    let size = parse_usize(&typ_name[1..])?;
+                    match &typ_name[0..1]
+                    {
+                        "u" => asm::PatternParameterType::Unsigned(size),
+                        "s" => asm::PatternParameterType::Signed(size),
+                        "i" => asm::PatternParameterType::Integer(size),
+                        _ => return Err(()),
+                    }
+                }
+                _ => return Err(()),
+            };
+
+            let arg = match typ
+            {
+                asm::PatternParameterType::Ruleset(_) =>
+                    ObjectRuleArgument::NestedRule(self.rule(&param[2])?),
+                _ =>
+                    ObjectRuleArgument::Expression(self.expr(&param[2])?),
+            };
+
+            parameters.push(asm::PatternParameter
+            {
+                name: param[0].as_str()?.to_string(),
+                typ,
+            });
+
+            rule_args.push(arg);
+        }
+
+        Ok(ObjectRule
+        {
+            span: self.span(&args[0])?,
+            parameters,
+            args: rule_args,
+            production: self.expr(&args[2])?,
+        })
+    }
+
+
+    fn symbol(&self, elem: &SExpr) -> Result<ObjectSymbol, ()>
+    {
+        let kind = match elem.tag()?
+        {
+            "label" =>
+            {
+                let args = expect_args(elem, "label", 4)?;
+                ObjectSymbolKind::Label
+                {
+                    bank_ref: asm::BankRef { index: args[2].as_usize()? },
+                    offset: read_bigint(&args[3])?,
+                }
+            }
+
+            _ =>
+            {
+                let args = expect_args(elem, "constant", 4)?;
+                ObjectSymbolKind::Constant
+                {
+                    ctx: self.ctx(&args[2])?,
+                    expr: self.expr(&args[3])?,
+                }
+            }
+        };
+
+        let args = &elem.as_list()?[1..];
+        let hierarchy = read_names(&args[1])?;
+        if hierarchy.len() == 0
+        {
+            return Err(());
+        }
+
+        Ok(ObjectSymbol
+        {
+            hierarchy,
+            decl_span: self.span(&args[0])?,
+            kind,
+        })
+    }
+
+
+    fn function(&self, elem: &SExpr) -> Result<asm::Function, ()>
+    {
+        let args = expect_args(elem, "function", 4)?;
+
+        Ok(asm::Function
+        {
+            decl_span: self.span(&args[0])?,
+            name: args[1].as_str()?.to_string(),
+            params: read_names(&args[2])?,
+            body: self.expr(&args[3])?,
+        })
+    }
+
+
+    fn value(&self, elem: &SExpr) -> Result<expr::Value, ()>
+    {
+        match elem.tag()?
+        {
+            "int" =>
+            {
+                let args = expect_args(elem, "int", 2)?;
+                let mut bigint = read_bigint(&args[0])?;
+                bigint.size = read_optional(&args[1])?;
+                Ok(expr::Value::make_integer(bigint))
+            }
+
+            "str" =>
+            {
+                let args = expect_args(elem, "str", 2)?;
+                Ok(expr::Value::make_string(args[0].as_str()?, args[1].as_str()?))
+            }
+
+            "bool" =>
+            {
+                let args = expect_args(elem, "bool", 1)?;
+                Ok(expr::Value::Bool(args[0].as_usize()? != 0))
+            }
+
+            "builtin" =>
+            {
+                let args = expect_args(elem, "builtin", 1)?;
+                Ok(expr::Value::BuiltInFunction(args[0].as_str()?.to_string()))
+            }
+
+            "void" => Ok(expr::Value::Void),
+            "unknown" => Ok(expr::Value::Unknown),
+
+            _ => Err(()),
+        }
+    }
+
+
+    fn expr(&self, elem: &SExpr) -> Result<expr::Expr, ()>
+    {
+        let tag = elem.tag()?;
+        let args = &elem.as_list()?[1..];
+
+        let expected_args = match tag
+        {
+            "lit" => 2,
+            "var" => 3,
+            "unary" => 4,
+            "binary" => 5,
+            "ternary" => 4,
+            "slice" | "softslice" => 5,
+            "block" => 1,
+            "while" => 3,
+            "for" => 5,
+            "let" => 3,
+            "call" => 2,
+            _ => return Err(()),
+        };
+
+        let is_variadic = tag == "block" || tag == "call";
+        if args.len() < expected_args || (!is_variadic && args.len() != expected_args)
+        {
+            return Err(());
+        }
+
+        let span = self.span(&args[0])?;
+
+        match tag
+        {
+            "lit" => Ok(expr::Expr::Literal(span, self.value(&args[1])?)),
+
+            "var" => Ok(expr::Expr::Variable(
+                span,
+                args[1].as_usize()?,
+                read_names(&args[2])?)),
+
+            "unary" => Ok(expr::Expr::UnaryOp(
+                span,
+                self.span(&args[1])?,
+                unary_op_from_name(args[2].as_atom()?)?,
+                Box::new(self.expr(&args[3])?))),
+
+            "binary" => Ok(expr::Expr::BinaryOp(
+                span,
+                self.span(&args[1])?,
+                binary_op_from_name(args[2].as_atom()?)?,
+                Box::new(self.expr(&args[3])?),
+                Box::new(self.expr(&args[4])?))),
+
+            "ternary" => Ok(expr::Expr::TernaryOp(
+                span,
+                Box::new(self.expr(&args[1])?),
+                Box::new(self.expr(&args[2])?),
+                Box::new(self.expr(&args[3])?))),
+
+            "slice" => Ok(expr::Expr::BitSlice(
+                span,
+                self.span(&args[1])?,
+                args[2].as_usize()?,
+                args[3].as_usize()?,
+                Box::new(self.expr(&args[4])?))),
+
+            "softslice" => Ok(expr::Expr::SoftSlice(
+                span,
+                self.span(&args[1])?,
+                args[2].as_usize()?,
+                args[3].as_usize()?,
+                Box::new(self.expr(&args[4])?))),
+
+            "block" =>
+            {
+                let mut exprs = Vec::new();
+                for arg in &args[1..]
+                {
+                    exprs.push(self.expr(arg)?);
+                }
+
+                Ok(expr::Expr::Block(span, exprs))
+            }
+
+            "while" => Ok(expr::Expr::While(
+                span,
+                Box::new(self.expr(&args[1])?),
+                Box::new(self.expr(&args[2])?))),
+
+            "for" => Ok(expr::Expr::For(
+                span,
+                args[1].as_str()?.to_string(),
+                Box::new(self.expr(&args[2])?),
+                Box::new(self.expr(&args[3])?),
+                Box::new(self.expr(&args[4])?))),
+
+            "let" => Ok(expr::Expr::Let(
+                span,
+                args[1].as_str()?.to_string(),
+                Box::new(self.expr(&args[2])?))),
+
+            _ =>
+            {
+                let mut call_args = Vec::new();
+                for arg in &args[2..]
+                {
+                    call_args.push(self.expr(arg)?);
+                }
+
+                Ok(expr::Expr::Call(
+                    span,
+                    Box::new(self.expr(&args[1])?),
+                    call_args))
+            }
+        }
+    }
+}
+
+
+fn expect_args<'a>(elem: &'a SExpr, tag: &str, count: usize) -> Result<&'a [SExpr], ()>
+{
+    let args = elem.as_tagged(tag)?;
+    if args.len() != count
+    {
+        return Err(());
+    }
+
+    Ok(args)
+}
+
+
+fn parse_usize(text: &str) -> Result<usize, ()>
+{
+    text.parse::<usize>().map_err(|_| ())
+}
+
+
+fn read_names(elem: &SExpr) -> Result<Vec<String>, ()>
+{
+    let mut result = Vec::new();
+    for name in elem.as_list()?
+    {
+        result.push(name.as_str()?.to_string());
+    }
+
+    Ok(result)
+}
+
+
+fn write_optional(value: Option<usize>) -> SExpr
+{
+    match value
+    {
+        Some(value) => SExpr::atom(format!("{}", value)),
+        None => SExpr::atom("_"),
+    }
+}
+
+
+fn read_optional(elem: &SExpr) -> Result<Option<usize>, ()>
+{
+    if elem.as_atom()? == "_"
+    {
+        Ok(None)
+    }
+    else
+    {
+        Ok(Some(elem.as_usize()?))
+    }
+}
+
+
+fn write_bigint(bigint: &util::BigInt) -> SExpr
+{
+    if bigint.sign() < 0
+    {
+        SExpr::atom(format!("-0x{:x}", &-bigint))
+    }
+    else
+    {
+        SExpr::atom(format!("0x{:x}", bigint))
+    }
+}
+
+
+fn read_bigint(elem: &SExpr) -> Result<util::BigInt, ()>
+{
+    let text = elem.as_atom()?;
+    let (negative, digits) = match text.strip_prefix('-')
+    {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+
+    if !digits.starts_with("0x") || digits.len() < 3
+    {
+        return Err(());
+    }
+
+    let mut magnitude = syntax::excerpt_as_bigint(None, digits, &diagn::Span::new_dummy())?;
+    magnitude.size = None;
+
+    if negative
+    {
+        Ok(-&magnitude)
+    }
+    else
+    {
+        Ok(magnitude)
+    }
+}
+
+
+fn unary_op_name(op: expr::UnaryOp) -> &'static str
+{
+    match op
+    {
+        expr::UnaryOp::Neg => "neg",
+        expr::UnaryOp::Not => "not",
+    }
+}
+
+
+fn unary_op_from_name(name: &str) -> Result<expr::UnaryOp, ()>
+{
+    match name
+    {
+        "neg" => Ok(expr::UnaryOp::Neg),
+        "not" => Ok(expr::UnaryOp::Not),
+        _ => Err(()),
+    }
+}
+
+
+static BINARY_OPS: [(expr::BinaryOp, &str); 20] = [
+    (expr::BinaryOp::Assign, "assign"),
+    (expr::BinaryOp::Add, "add"),
+    (expr::BinaryOp::Sub, "sub"),
+    (expr::BinaryOp::Mul, "mul"),
+    (expr::BinaryOp::Div, "div"),
+    (expr::BinaryOp::Mod, "mod"),
+    (expr::BinaryOp::Shl, "shl"),
+    (expr::BinaryOp::Shr, "shr"),
+    (expr::BinaryOp::And, "and"),
+    (expr::BinaryOp::Or, "or"),
+    (expr::BinaryOp::Xor, "xor"),
+    (expr::BinaryOp::Eq, "eq"),
+    (expr::BinaryOp::Ne, "ne"),
+    (expr::BinaryOp::Lt, "lt"),
+    (expr::BinaryOp::Le, "le"),
+    (expr::BinaryOp::Gt, "gt"),
+    (expr::BinaryOp::Ge, "ge"),
+    (expr::BinaryOp::LazyAnd, "land"),
+    (expr::BinaryOp::LazyOr, "lor"),
+    (expr::BinaryOp::Concat, "concat"),
+];
+
+
+fn binary_op_name(op: expr::BinaryOp) -> &'static str
+{
+    BINARY_OPS.iter().find(|o| o.0 == op).unwrap().1
+}
+
+
+fn binary_op_from_name(name: &str) -> Result<expr::BinaryOp, ()>
+{
+    match BINARY_OPS.iter().find(|o| o.1 == name)
+    {
+        Some(op) => Ok(op.0),
+        None => Err(()),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,524 @@
+use crate::*;
+use super::objfile::*;
+use std::collections::HashMap;
+
+
+struct LinkSymbol
+{
+    hierarchy: Vec<String>,
+    decl_span: diagn::Span,
+    kind: LinkSymbolKind,
+}
+
+
+enum LinkSymbolKind
+{
+    Label
+    {
+        bank_ref: asm::BankRef,
+        value: util::BigInt,
+    },
+    Constant
+    {
+        ctx: asm::Context,
+        expr: expr::Expr,
+    },
+}
+
+
+/// Merges the given objects into a single assembly state,
+/// placing each object's section of a bank right after the
+/// previous object's, and then resolves every deferred
+/// expression against the combined symbol table.
+pub fn link(
+    report: diagn::RcReport,
+    objects: Vec<(String, Object)>,
+    fileserver: &dyn util::FileServer)
+    -> Result<asm::AssemblyOutput, ()>
+{
+    let mut state = asm::State::new();
+    state.banks.clear();
+    state.bankdata.clear();
+
+    state.rulesets.push(asm::Ruleset
+    {
+        name: "".to_string(),
+        rules: Vec::new(),
+        decl_span: diagn::Span::new_dummy(),
+    });
+
+    let mut bank_filenames = Vec::<String>::new();
+    let mut symbols = Vec::<LinkSymbol>::new();
+    let mut symbol_indices = HashMap::<Vec<String>, usize>::new();
+    let mut imports = Vec::<(String, diagn::Span, String)>::new();
+
+    for (filename, object) in objects
+    {
+        // Map the object's banks to the merged ones, and
+        // record where the object's section starts.
+        let mut placements = Vec::new();
+        for object_bank in &object.banks
+        {
+            let bank_index = match state.banks.iter().position(|b| b.name == object_bank.bank.name)
+            {
+                Some(index) =>
+                {
+                    if !is_same_bank(&state.banks[index], &object_bank.bank)
+                    {
+                        report.error(format!(
+                            "conflicting definitions of bank `{}` in `{}`",
+                            object_bank.bank.name,
+                            filename));
+
+                        return Err(());
+                    }
+
+                    index
+                }
+
+                None =>
+                {
+                    bank_filenames.push(filename.clone());
+                    state.banks.push(object_bank.bank.clone());
+                    state.bankdata.push(asm::BankData
+                    {
+                        bank_ref: asm::BankRef { index: state.banks.len() - 1 },
+                        cur_bit_offset: 0,
+                        invocations: Vec::new(),
+                        alignment: 1,
+                    });
+
+                    state.banks.len() - 1
+                }
+            };
+
+            // Keep the section's `#align`s true once placed
+            let bankdata = &mut state.bankdata[bank_index];
+            let excess_bits = bankdata.cur_bit_offset % object_bank.alignment;
+            if excess_bits != 0
+            {
+                bankdata.reserve(object_bank.alignment - excess_bits);
+            }
+
+            bankdata.require_alignment(object_bank.alignment);
+            placements.push((bank_index, bankdata.cur_bit_offset));
+            bankdata.cur_bit_offset += object_bank.size;
+        }
+
+        for object_bank in object.banks
+        {
+            for item in object_bank.items
+            {
+                let ctx = relocate(&placements, &item.ctx);
+
+                let kind = match item.kind
+                {
+                    ObjectItemKind::Data(data_invoc) =>
+                        asm::InvocationKind::Data(data_invoc),
+
+                    ObjectItemKind::Rule(rule) =>
+                        asm::InvocationKind::Rule(asm::RuleInvocation
+                        {
+                            candidates: vec![add_rule(&mut state, rule)],
+                        }),
+                };
+
+                state.bankdata[ctx.bank_ref.index].invocations.push(asm::Invocation
+                {
+                    ctx,
+                    size_guess: item.size,
+                    span: item.span,
+                    kind,
+                });
+            }
+        }
+
+        for symbol in object.symbols
+        {
+            let kind = match symbol.kind
+            {
+                ObjectSymbolKind::Label { bank_ref, offset } =>
+                {
+                    let (bank_index, base) = placements[bank_ref.index];
+                    let bank = &state.banks[bank_index];
+
+                    let base = util::BigInt::from(base / bank.wordsize);
+                    let value = &(&bank.addr_start + &base) + &offset;
+
+                    LinkSymbolKind::Label
+                    {
+                        bank_ref: asm::BankRef { index: bank_index },
+                        value,
+                    }
+                }
+
+                ObjectSymbolKind::Constant { ctx, expr } =>
+                {
+                    LinkSymbolKind::Constant
+                    {
+                        ctx: relocate(&placements, &ctx),
+                        expr,
+                    }
+                }
+            };
+
+            if let Some(&index) = symbol_indices.get(&symbol.hierarchy)
+            {
+                // Constants declared in a file included by more
+                // than one object are the same declaration.
+                if is_same_constant(&symbols[index].kind, &kind)
+                {
+                    continue;
+                }
+
+                let _guard = report.push_parent(
+                    format!("duplicate symbol `{}`", symbol.hierarchy.join(".")),
+                    &symbol.decl_span);
+
+                report.note_span("first declared here", &symbols[index].decl_span);
+                return Err(());
+            }
+
+            symbol_indices.insert(symbol.hierarchy.clone(), symbols.len());
+            symbols.push(LinkSymbol
+            {
+                hierarchy: symbol.hierarchy,
+                decl_span: symbol.decl_span,
+                kind,
+            });
+        }
+
+        for function in object.functions
+        {
+            match state.functions.iter().find(|f| f.name == function.name)
+            {
+                None => state.functions.push(function),
+
+                // Functions declared in a file included by more
+                // than one object are the same declaration.
+                Some(other) if is_same_function(other, &function) => {}
+
+                Some(other) =>
+                {
+                    let _guard = report.push_parent(
+                        format!("conflicting definitions of function `{}`", function.name),
+                        &function.decl_span);
+
+                    report.note_span("first declared here", &other.decl_span);
+                    return Err(());
+                }
+            }
+        }
+
+        for condition in object.conditions
+        {
+            state.conditions.push(asm::Condition
+            {
+                ctx: relocate(&placements, &condition.ctx),
+                expr: condition.expr,
+                value: condition.value,
+            });
+        }
+
+        for (name, span) in object.imports
+        {
+            imports.push((name, span, filename.clone()));
+        }
+    }
+
+    check_banks(report.clone(), &state, &bank_filenames)?;
+
+    for (name, span, filename) in &imports
+    {
+        let is_defined =
+            symbol_indices.contains_key(&vec![name.clone()]) ||
+            state.functions.iter().any(|f| f.name == *name);
+
+        if !is_defined
+        {
+            report.error_span(
+                format!(
+                    "unresolved symbol `{}` imported by `{}`",
+                    name,
+                    filename),
+                span);
+        }
+    }
+
+    if report.has_errors()
+    {
+        return Err(());
+    }
+
+    create_symbols(report.clone(), &mut state, symbols, fileserver)?;
+
+    let binary = state.resolve_output(
+        report.clone(),
+        fileserver,
+        &mut HashMap::new())?;
+
+    Ok(asm::AssemblyOutput
+    {
+        binary,
+        state,
+        iterations: 1,
+    })
+}
+
+
+/// Runs the checks that `#bankdef` makes when assembling
+/// a single file on the banks merged from every object.
+fn check_banks(
+    report: diagn::RcReport,
+    state: &asm::State,
+    bank_filenames: &[String])
+    -> Result<(), ()>
+{
+    let default_bank_used = state.banks
+        .iter()
+        .zip(state.bankdata.iter())
+        .any(|(bank, bankdata)| bank.name == "" && bankdata.cur_bit_offset != 0);
+
+    for i in 0..state.banks.len()
+    {
+        let bank = &state.banks[i];
+        if bank.name == ""
+            { continue; }
+
+        if default_bank_used
+        {
+            report.error(format!(
+                "cannot create new bank `{}` in `{}` if the default bank has already been used",
+                bank.name,
+                bank_filenames[i]));
+
+            return Err(());
+        }
+
+        for j in 0..i
+        {
+            let other_bank = &state.banks[j];
+            if other_bank.name == ""
+                { continue; }
+
+            if bank.output_overlaps(other_bank)
+            {
+                report.error(format!(
+                    "output region of bank `{}` in `{}` overlaps with bank `{}`",
+                    bank.name,
+                    bank_filenames[i],
+                    other_bank.name));
+
+                return Err(());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+
+fn is_same_bank(a: &asm::Bank, b: &asm::Bank) -> bool
+{
+    a.wordsize == b.wordsize &&
+        a.labelalign == b.labelalign &&
+        a.addr_start == b.addr_start &&
+        a.addr_size == b.addr_size &&
+        a.output_offset == b.output_offset &&
+        a.fill == b.fill
+}
+
+
+fn is_same_constant(a: &LinkSymbolKind, b: &LinkSymbolKind) -> bool
+{
+    match (a, b)
+    {
+        (LinkSymbolKind::Constant { expr: expr_a, .. },
+            LinkSymbolKind::Constant { expr: expr_b, .. }) =>
+        {
+            super::format::expr_to_string(expr_a) ==
+                super::format::expr_to_string(expr_b)
+        }
+
+        _ => false,
+    }
+}
+
+
+fn is_same_function(a: &asm::Function, b: &asm::Function) -> bool
+{
+    a.params == b.params &&
+        super::format::expr_to_string(&a.body) ==
+            super::format::expr_to_string(&b.body)
+}
+
+
+fn relocate(placements: &[(usize, usize)], ctx: &asm::Context) -> asm::Context
+{
+    let (bank_index, base) = placements[ctx.bank_ref.index];
+
+    asm::Context
+    {
+        bit_offset: ctx.bit_offset + base,
+        bank_ref: asm::BankRef { index: bank_index },
+        ..ctx.clone()
+    }
+}
+
+
+fn add_rule(state: &mut asm::State, rule: ObjectRule) -> asm::RuleInvocationCandidate
+{
+    let mut args = Vec::new();
+    for arg in rule.args
+    {
+        args.push(match arg
+        {
+            ObjectRuleArgument::Expression(expr) =>
+                asm::RuleInvocationArgument::Expression(expr),
+
+            ObjectRuleArgument::NestedRule(nested) =>
+                asm::RuleInvocationArgument::NestedRuleset(add_rule(state, nested)),
+        });
+    }
+
+    let ruleset = &mut state.rulesets[0];
+    let rule_ref = asm::RuleRef
+    {
+        ruleset_ref: asm::RulesetRef { index: 0 },
+        index: ruleset.rules.len(),
+    };
+
+    ruleset.rules.push(asm::Rule
+    {
+        span: rule.span,
+        pattern: Vec::new(),
+        parameters: rule.parameters,
+        production: rule.production,
+    });
+
+    asm::RuleInvocationCandidate
+    {
+        rule_ref,
+        specificity: 0,
+        token_args: vec![None; args.len()],
+        args,
+    }
+}
+
+
+/// Creates every symbol in the merged symbol table,
+/// evaluating constants once the symbols they refer
+/// to are available.
+fn create_symbols(
+    report: diagn::RcReport,
+    state: &mut asm::State,
+    mut pending: Vec<LinkSymbol>,
+    fileserver: &dyn util::FileServer)
+    -> Result<(), ()>
+{
+    pending.sort_by_key(|s| s.hierarchy.len());
+
+    while pending.len() > 0
+    {
+        let mut remaining = Vec::new();
+        let mut any_created = false;
+
+        for symbol in pending
+        {
+            let parent = &symbol.hierarchy[0..(symbol.hierarchy.len() - 1)];
+            let root_ctx = asm::SymbolContext::new(Vec::new());
+
+            if parent.len() > 0 && state.symbols.get(&root_ctx, 0, parent).is_none()
+            {
+                remaining.push(symbol);
+                continue;
+            }
+
+            let (value, bank_ref) = match symbol.kind
+            {
+                LinkSymbolKind::Label { bank_ref, ref value } =>
+                {
+                    (expr::Value::make_integer(value.clone()), bank_ref)
+                }
+
+                LinkSymbolKind::Constant { ref ctx, ref expr } =>
+                {
+                    let result = state.eval_expr(
+                        diagn::RcReport::new(),
+                        expr,
+                        ctx,
+                        &mut expr::EvalContext::new(),
+                        fileserver,
+                        true);
+
+                    match result
+                    {
+                        Ok(value) => (value, ctx.bank_ref),
+                        Err(()) =>
+                        {
+                            remaining.push(symbol);
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            let kind = match symbol.kind
+            {
+                LinkSymbolKind::Label { .. } => asm::SymbolKind::Label,
+                LinkSymbolKind::Constant { .. } => asm::SymbolKind::Constant,
+            };
+
+            state.symbols.create(
+                &asm::SymbolContext::new(parent.to_vec()),
+                symbol.hierarchy.last().unwrap().clone(),
+                parent.len(),
+                kind,
+                value,
+                bank_ref,
+                report.clone(),
+                &symbol.decl_span)?;
+
+            any_created = true;
+        }
+
+        if !any_created
+        {
+            // Evaluate again to report why
+            // the remaining symbols failed.
+            for symbol in &remaining
+            {
+                match symbol.kind
+                {
+                    LinkSymbolKind::Constant { ref ctx, ref expr } =>
+                    {
+                        let _guard = report.push_parent(
+                            "failed to resolve constant",
+                            &symbol.decl_span);
+
+                        let _ = state.eval_expr(
+                            report.clone(),
+                            expr,
+                            ctx,
+                            &mut expr::EvalContext::new(),
+                            fileserver,
+                            true);
+                    }
+
+                    LinkSymbolKind::Label { .. } =>
+                    {
+                        report.error_span(
+                            "parent of local label is not defined",
+                            &symbol.decl_span);
+                    }
+                }
+            }
+
+            return Err(());
+        }
+
+        pending = remaining;
+    }
+
+    Ok(())
+}
\ No newline at end of file
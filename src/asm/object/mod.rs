@@ -0,0 +1,15 @@
+mod objfile;
+mod format;
+mod link;
+mod sexpr;
+
+
+pub use self::objfile::Object;
+pub use self::objfile::ObjectBank;
+pub use self::objfile::ObjectItem;
+pub use self::objfile::ObjectItemKind;
+pub use self::objfile::ObjectRule;
+pub use self::objfile::ObjectRuleArgument;
+pub use self::objfile::ObjectSymbol;
+pub use self::objfile::ObjectSymbolKind;
+pub use self::link::link;
\ No newline at end of file
@@ -0,0 +1,452 @@
+use crate::*;
+
+
+/// The contents of a relocatable object file: every bank's
+/// invocations with their expressions left unevaluated, so
+/// that symbols from other objects can be resolved at link time.
+#[derive(Debug)]
+pub struct Object
+{
+    pub banks: Vec<ObjectBank>,
+    pub symbols: Vec<ObjectSymbol>,
+    pub functions: Vec<asm::Function>,
+    pub conditions: Vec<asm::Condition>,
+    pub imports: Vec<(String, diagn::Span)>,
+}
+
+
+#[derive(Debug)]
+pub struct ObjectBank
+{
+    pub bank: asm::Bank,
+    pub size: usize,
+    pub alignment: usize,
+    pub items: Vec<ObjectItem>,
+}
+
+
+#[derive(Debug)]
+pub struct ObjectItem
+{
+    pub ctx: asm::Context,
+    pub span: diagn::Span,
+    pub size: usize,
+    pub kind: ObjectItemKind,
+}
+
+
+#[derive(Debug)]
+pub enum ObjectItemKind
+{
+    Data(asm::DataInvocation),
+    Rule(ObjectRule),
+}
+
+
+/// The rule candidate chosen for an instruction,
+/// together with its unevaluated arguments.
+#[derive(Debug)]
+pub struct ObjectRule
+{
+    pub span: diagn::Span,
+    pub parameters: Vec<asm::PatternParameter>,
+    pub args: Vec<ObjectRuleArgument>,
+    pub production: expr::Expr,
+}
+
+
+#[derive(Debug)]
+pub enum ObjectRuleArgument
+{
+    Expression(expr::Expr),
+    NestedRule(ObjectRule),
+}
+
+
+#[derive(Debug)]
+pub struct ObjectSymbol
+{
+    pub hierarchy: Vec<String>,
+    pub decl_span: diagn::Span,
+    pub kind: ObjectSymbolKind,
+}
+
+
+#[derive(Debug)]
+pub enum ObjectSymbolKind
+{
+    Label
+    {
+        bank_ref: asm::BankRef,
+        offset: util::BigInt,
+    },
+    Constant
+    {
+        ctx: asm::Context,
+        expr: expr::Expr,
+    },
+}
+
+
+impl asm::State
+{
+    pub fn make_object(
+        &self,
+        report: diagn::RcReport,
+        fileserver: &dyn util::FileServer)
+        -> Result<Object, ()>
+    {
+        let mut object = Object
+        {
+            banks: Vec::new(),
+            symbols: Vec::new(),
+            functions: Vec::new(),
+            conditions: Vec::new(),
+            imports: Vec::new(),
+        };
+
+        for (bank, bankdata) in self.banks.iter().zip(self.bankdata.iter())
+        {
+            let mut size = bankdata.cur_bit_offset;
+            let mut items = Vec::new();
+
+            for invoc in &bankdata.invocations
+            {
+                size = std::cmp::max(size, invoc.ctx.bit_offset + invoc.size_guess);
+
+                let kind = match invoc.kind
+                {
+                    asm::InvocationKind::Label(_) => continue,
+
+                    asm::InvocationKind::Data(ref data_invoc) =>
+                    {
+                        check_deferrable(report.clone(), &data_invoc.expr, &invoc.span)?;
+
+                        ObjectItemKind::Data(asm::DataInvocation
+                        {
+                            expr: data_invoc.expr.clone(),
+                            elem_size: data_invoc.elem_size,
+                        })
+                    }
+
+                    asm::InvocationKind::Rule(_) =>
+                    {
+                        let candidate = self.choose_object_candidate(invoc, fileserver);
+
+                        ObjectItemKind::Rule(self.make_object_rule(
+                            report.clone(),
+                            invoc,
+                            candidate)?)
+                    }
+                };
+
+                items.push(ObjectItem
+                {
+                    ctx: invoc.ctx.clone(),
+                    span: invoc.span.clone(),
+                    size: invoc.size_guess,
+                    kind,
+                });
+            }
+
+            // Sections are concatenated at link time, so
+            // each one must end at an address boundary.
+            if size % bank.wordsize != 0
+            {
+                size += bank.wordsize - size % bank.wordsize;
+            }
+
+            object.banks.push(ObjectBank
+            {
+                bank: bank.clone(),
+                size,
+                alignment: bankdata.alignment,
+                items,
+            });
+        }
+
+        self.symbols.visit(&mut |hierarchy: &[String], symbol: &asm::Symbol|
+        {
+            let kind = match symbol.kind
+            {
+                asm::SymbolKind::Label =>
+                {
+                    let bank = &self.banks[symbol.bankref.index];
+                    let offset = match symbol.value
+                    {
+                        expr::Value::Integer(ref addr) => addr - &bank.addr_start,
+                        _ => return,
+                    };
+
+                    ObjectSymbolKind::Label
+                    {
+                        bank_ref: symbol.bankref,
+                        offset,
+                    }
+                }
+
+                asm::SymbolKind::Constant =>
+                {
+                    let decl = self.constant_decls
+                        .iter()
+                        .find(|d| d.decl_span == symbol.decl_span)
+                        .filter(|d| check_deferrable(diagn::RcReport::new(), &d.expr, &d.decl_span).is_ok());
+
+                    // Constants without a recorded declaration, such as
+                    // ones given on the command line, keep their value.
+                    match decl
+                    {
+                        Some(decl) => ObjectSymbolKind::Constant
+                        {
+                            ctx: decl.ctx.clone(),
+                            expr: decl.expr.clone(),
+                        },
+
+                        None => ObjectSymbolKind::Constant
+                        {
+                            ctx: asm::Context
+                            {
+                                bit_offset: 0,
+                                cur_wordsize: self.banks[symbol.bankref.index].wordsize,
+                                bank_ref: symbol.bankref,
+                                symbol_ctx: asm::SymbolContext::new(Vec::new()),
+                                cur_filename: std::rc::Rc::new("".to_string()),
+                                locals: std::rc::Rc::new(Vec::new()),
+                            },
+                            expr: symbol.value.make_literal(),
+                        },
+                    }
+                }
+            };
+
+            object.symbols.push(ObjectSymbol
+            {
+                hierarchy: hierarchy.to_vec(),
+                decl_span: symbol.decl_span.clone(),
+                kind,
+            });
+        });
+
+        for function in &self.functions
+        {
+            // Functions built on `asm` blocks can only be
+            // used by instructions resolved at this point.
+            if check_deferrable(diagn::RcReport::new(), &function.body, &function.decl_span).is_err()
+            {
+                continue;
+            }
+
+            object.functions.push(asm::Function
+            {
+                decl_span: function.decl_span.clone(),
+                name: function.name.clone(),
+                params: function.params.clone(),
+                body: function.body.clone(),
+            });
+        }
+
+        for condition in &self.conditions
+        {
+            check_deferrable(report.clone(), &condition.expr, &condition.expr.span())?;
+
+            object.conditions.push(asm::Condition
+            {
+                ctx: condition.ctx.clone(),
+                expr: condition.expr.clone(),
+                value: condition.value,
+            });
+        }
+
+        for (name, span) in self.imports.borrow().iter()
+        {
+            let is_exported = object.symbols
+                .iter()
+                .any(|s| s.hierarchy.len() == 1 && s.hierarchy[0] == *name);
+
+            if !is_exported
+            {
+                object.imports.push((name.clone(), span.clone()));
+            }
+        }
+
+        Ok(object)
+    }
+
+
+    /// Picks the same candidate the final pass used,
+    /// which is the one that produced the invocation's size.
+    fn choose_object_candidate<'a>(
+        &self,
+        invoc: &'a asm::Invocation,
+        fileserver: &dyn util::FileServer)
+        -> &'a asm::RuleInvocationCandidate
+    {
+        let candidates = &invoc.get_rule_invoc().candidates;
+
+        for candidate in candidates
+        {
+            let resolved = self.resolve_rule_invocation_candidate(
+                diagn::RcReport::new(),
+                invoc,
+                candidate,
+                fileserver,
+                true,
+                &mut expr::EvalContext::new());
+
+            if let Ok(expr::Value::Integer(bigint)) = resolved
+            {
+                if bigint.size == Some(invoc.size_guess)
+                {
+                    return candidate;
+                }
+            }
+        }
+
+        &candidates[0]
+    }
+
+
+    fn make_object_rule(
+        &self,
+        report: diagn::RcReport,
+        invoc: &asm::Invocation,
+        candidate: &asm::RuleInvocationCandidate)
+        -> Result<ObjectRule, ()>
+    {
+        let rule = self.get_rule(candidate.rule_ref).unwrap();
+
+        check_deferrable(report.clone(), &rule.production, &invoc.span)?;
+
+        let mut args = Vec::new();
+        for arg in &candidate.args
+        {
+            match arg
+            {
+                asm::RuleInvocationArgument::Expression(expr) =>
+                {
+                    check_deferrable(report.clone(), expr, &invoc.span)?;
+                    args.push(ObjectRuleArgument::Expression(expr.clone()));
+                }
+
+                asm::RuleInvocationArgument::NestedRuleset(inner_candidate) =>
+                {
+                    args.push(ObjectRuleArgument::NestedRule(self.make_object_rule(
+                        report.clone(),
+                        invoc,
+                        inner_candidate)?));
+                }
+            }
+        }
+
+        let parameters = rule.parameters
+            .iter()
+            .map(|p| asm::PatternParameter
+            {
+                name: p.name.clone(),
+                typ: p.typ,
+            })
+            .collect();
+
+        Ok(ObjectRule
+        {
+            span: rule.span.clone(),
+            parameters,
+            args,
+            production: rule.production.clone(),
+        })
+    }
+}
+
+
+fn check_deferrable(
+    report: diagn::RcReport,
+    expr: &expr::Expr,
+    span: &diagn::Span)
+    -> Result<(), ()>
+{
+    let mut asm_span = None;
+    find_asm_block(expr, &mut asm_span);
+
+    match asm_span
+    {
+        None => Ok(()),
+        Some(asm_span) =>
+        {
+            let _guard = report.push_parent(
+                "cannot defer evaluation to the link step",
+                span);
+
+            report.note_span("`asm` blocks must be resolved when assembling", &asm_span);
+            Err(())
+        }
+    }
+}
+
+
+fn find_asm_block(expr: &expr::Expr, result: &mut Option<diagn::Span>)
+{
+    match expr
+    {
+        expr::Expr::Asm(span, _) =>
+        {
+            if result.is_none()
+            {
+                *result = Some(span.clone());
+            }
+        }
+
+        expr::Expr::Literal(..) |
+        expr::Expr::Variable(..) => {}
+
+        expr::Expr::UnaryOp(_, _, _, inner) |
+        expr::Expr::BitSlice(_, _, _, _, inner) |
+        expr::Expr::SoftSlice(_, _, _, _, inner) |
+        expr::Expr::Let(_, _, inner) =>
+        {
+            find_asm_block(inner, result);
+        }
+
+        expr::Expr::BinaryOp(_, _, _, lhs, rhs) =>
+        {
+            find_asm_block(lhs, result);
+            find_asm_block(rhs, result);
+        }
+
+        expr::Expr::TernaryOp(_, cond, true_branch, false_branch) =>
+        {
+            find_asm_block(cond, result);
+            find_asm_block(true_branch, result);
+            find_asm_block(false_branch, result);
+        }
+
+        expr::Expr::While(_, cond, body) =>
+        {
+            find_asm_block(cond, result);
+            find_asm_block(body, result);
+        }
+
+        expr::Expr::For(_, _, start, end, body) =>
+        {
+            find_asm_block(start, result);
+            find_asm_block(end, result);
+            find_asm_block(body, result);
+        }
+
+        expr::Expr::Block(_, exprs) =>
+        {
+            for expr in exprs
+            {
+                find_asm_block(expr, result);
+            }
+        }
+
+        expr::Expr::Call(_, func, args) =>
+        {
+            find_asm_block(func, result);
+            for arg in args
+            {
+                find_asm_block(arg, result);
+            }
+        }
+    }
+}
\ No newline at end of file
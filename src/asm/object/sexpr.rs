@@ -0,0 +1,339 @@
+/// A minimal S-expression tree, used as the
+/// textual encoding of object files.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SExpr
+{
+    Atom(String),
+    Str(String),
+    List(Vec<SExpr>),
+}
+
+
+impl SExpr
+{
+    pub fn atom<S: Into<String>>(s: S) -> SExpr
+    {
+        SExpr::Atom(s.into())
+    }
+
+
+    pub fn str<S: Into<String>>(s: S) -> SExpr
+    {
+        SExpr::Str(s.into())
+    }
+
+
+    pub fn list(tag: &str) -> SExpr
+    {
+        SExpr::List(vec![SExpr::atom(tag)])
+    }
+
+
+    pub fn with(mut self, elem: SExpr) -> SExpr
+    {
+        if let SExpr::List(ref mut elems) = self
+        {
+            elems.push(elem);
+        }
+
+        self
+    }
+
+
+    pub fn push(&mut self, elem: SExpr)
+    {
+        if let SExpr::List(ref mut elems) = self
+        {
+            elems.push(elem);
+        }
+    }
+
+
+    pub fn as_atom(&self) -> Result<&str, ()>
+    {
+        match self
+        {
+            SExpr::Atom(s) => Ok(s),
+            _ => Err(()),
+        }
+    }
+
+
+    pub fn as_str(&self) -> Result<&str, ()>
+    {
+        match self
+        {
+            SExpr::Str(s) => Ok(s),
+            _ => Err(()),
+        }
+    }
+
+
+    pub fn as_usize(&self) -> Result<usize, ()>
+    {
+        self.as_atom()?.parse::<usize>().map_err(|_| ())
+    }
+
+
+    pub fn as_list(&self) -> Result<&[SExpr], ()>
+    {
+        match self
+        {
+            SExpr::List(elems) => Ok(elems),
+            _ => Err(()),
+        }
+    }
+
+
+    /// Returns the elements following the leading tag
+    /// atom of a list, checking that the tag matches.
+    pub fn as_tagged(&self, tag: &str) -> Result<&[SExpr], ()>
+    {
+        let elems = self.as_list()?;
+        if elems.len() == 0 || elems[0].as_atom()? != tag
+        {
+            return Err(());
+        }
+
+        Ok(&elems[1..])
+    }
+
+
+    pub fn tag(&self) -> Result<&str, ()>
+    {
+        match self.as_list()?.first()
+        {
+            Some(elem) => elem.as_atom(),
+            None => Err(()),
+        }
+    }
+
+
+    pub fn parse(src: &str) -> Result<SExpr, ()>
+    {
+        let chars: Vec<char> = src.chars().collect();
+        let mut index = 0;
+
+        let result = parse_elem(&chars, &mut index)?;
+
+        skip_whitespace(&chars, &mut index);
+        if index != chars.len()
+        {
+            return Err(());
+        }
+
+        Ok(result)
+    }
+
+
+    fn write(&self, result: &mut String, indent: usize)
+    {
+        match self
+        {
+            SExpr::Atom(s) => result.push_str(s),
+            SExpr::Str(s) => write_string(result, s),
+            SExpr::List(elems) =>
+            {
+                // Lists of lists are broken into lines,
+                // so that the output stays diffable.
+                let multiline = elems.iter().skip(1).any(|e| match e
+                {
+                    SExpr::List(inner) => inner.iter().any(|i| matches!(i, SExpr::List(_))),
+                    _ => false,
+                });
+
+                result.push('(');
+                for (i, elem) in elems.iter().enumerate()
+                {
+                    if i > 0
+                    {
+                        if multiline && matches!(elem, SExpr::List(_))
+                        {
+                            result.push('\n');
+                            for _ in 0..(indent + 1)
+                            {
+                                result.push_str("  ");
+                            }
+                        }
+                        else
+                        {
+                            result.push(' ');
+                        }
+                    }
+
+                    elem.write(result, indent + 1);
+                }
+                result.push(')');
+            }
+        }
+    }
+}
+
+
+impl std::fmt::Display for SExpr
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error>
+    {
+        let mut result = String::new();
+        self.write(&mut result, 0);
+        write!(f, "{}", result)
+    }
+}
+
+
+fn write_string(result: &mut String, s: &str)
+{
+    result.push('"');
+
+    for c in s.chars()
+    {
+        match c
+        {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+}
+
+
+fn skip_whitespace(chars: &[char], index: &mut usize)
+{
+    while *index < chars.len() && chars[*index].is_whitespace()
+    {
+        *index += 1;
+    }
+}
+
+
+fn parse_elem(chars: &[char], index: &mut usize) -> Result<SExpr, ()>
+{
+    skip_whitespace(chars, index);
+
+    if *index >= chars.len()
+    {
+        return Err(());
+    }
+
+    match chars[*index]
+    {
+        '(' =>
+        {
+            *index += 1;
+
+            let mut elems = Vec::new();
+            loop
+            {
+                skip_whitespace(chars, index);
+
+                if *index >= chars.len()
+                {
+                    return Err(());
+                }
+
+                if chars[*index] == ')'
+                {
+                    *index += 1;
+                    return Ok(SExpr::List(elems));
+                }
+
+                elems.push(parse_elem(chars, index)?);
+            }
+        }
+
+        '"' => parse_string(chars, index).map(SExpr::Str),
+
+        ')' => Err(()),
+
+        _ =>
+        {
+            let start = *index;
+            while *index < chars.len() &&
+                !chars[*index].is_whitespace() &&
+                chars[*index] != '(' &&
+                chars[*index] != ')' &&
+                chars[*index] != '"'
+            {
+                *index += 1;
+            }
+
+            Ok(SExpr::Atom(chars[start..*index].iter().collect()))
+        }
+    }
+}
+
+
+fn parse_string(chars: &[char], index: &mut usize) -> Result<String, ()>
+{
+    *index += 1;
+
+    let mut result = String::new();
+    loop
+    {
+        if *index >= chars.len()
+        {
+            return Err(());
+        }
+
+        let c = chars[*index];
+        *index += 1;
+
+        match c
+        {
+            '"' => return Ok(result),
+
+            '\\' =>
+            {
+                if *index >= chars.len()
+                {
+                    return Err(());
+                }
+
+                let escape = chars[*index];
+                *index += 1;
+
+                match escape
+                {
+                    '"' => result.push('"'),
+                    '\\' => result.push('\\'),
+                    'n' => result.push('\n'),
+                    'r' => result.push('\r'),
+                    't' => result.push('\t'),
+                    'u' =>
+                    {
+                        if *index >= chars.len() || chars[*index] != '{'
+                        {
+                            return Err(());
+                        }
+
+                        let start = *index + 1;
+                        while *index < chars.len() && chars[*index] != '}'
+                        {
+                            *index += 1;
+                        }
+
+                        if *index >= chars.len()
+                        {
+                            return Err(());
+                        }
+
+                        let hex: String = chars[start..*index].iter().collect();
+                        *index += 1;
+
+                        let code = u32::from_str_radix(&hex, 16).map_err(|_| ())?;
+                        result.push(std::char::from_u32(code).ok_or(())?);
+                    }
+                    _ => return Err(()),
+                }
+            }
+
+            c => result.push(c),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,228 @@
+use crate::*;
+
+
+/// The part of the output that belongs to a bank,
+/// placed at the byte address of its `addr_start`.
+pub struct BankOutput
+{
+    pub bank_ref: asm::BankRef,
+    pub addr: usize,
+    pub size: usize,
+
+    /// The bytes written by the bank, or `None`
+    /// for a bank that has no `#outp`.
+    pub data: Option<Vec<u8>>,
+}
+
+
+impl asm::State
+{
+    /// Returns the output of every bank that holds data,
+    /// in declaration order.
+    pub fn get_bank_outputs(&self, binary: &util::BitVec) -> Vec<BankOutput>
+    {
+        let mut result = Vec::new();
+
+        for (index, bank) in self.banks.iter().enumerate()
+        {
+            let size_bits = self.get_bank_size_bits(index);
+            if size_bits == 0
+            {
+                continue;
+            }
+
+            let addr = match bank.addr_start
+                .checked_to_usize()
+                .and_then(|addr| addr.checked_mul(bank.wordsize))
+            {
+                Some(bits) => bits / 8,
+                None => continue,
+            };
+
+            let size = (size_bits + 7) / 8;
+
+            let data = bank.output_offset.map(|output_offset|
+            {
+                let mut data = Vec::with_capacity(size);
+                for byte_index in 0..size
+                {
+                    let mut byte: u8 = 0;
+                    for bit_index in 0..8
+                    {
+                        let index = byte_index * 8 + bit_index;
+
+                        byte <<= 1;
+                        if index < size_bits && binary.read(output_offset + index)
+                        {
+                            byte |= 1;
+                        }
+                    }
+
+                    data.push(byte);
+                }
+
+                data
+            });
+
+            result.push(BankOutput
+            {
+                bank_ref: asm::BankRef { index },
+                addr,
+                size,
+                data,
+            });
+        }
+
+        result
+    }
+
+
+    /// Returns the bits written by a bank, or `None`
+    /// if the bank has no `#outp`.
+    pub fn get_bank_bitvec(&self, bank_ref: asm::BankRef, binary: &util::BitVec) -> Option<util::BitVec>
+    {
+        let output_offset = self.banks[bank_ref.index].output_offset?;
+        let size_bits = self.get_bank_size_bits(bank_ref.index);
+
+        Some(binary.get_slice(output_offset, size_bits))
+    }
+
+
+    /// Returns the wordsize shared by every bank that
+    /// holds data, or 8 if they differ.
+    pub fn get_output_wordsize(&self) -> usize
+    {
+        let mut wordsizes = self.banks
+            .iter()
+            .enumerate()
+            .filter(|(index, bank)| bank.output_offset.is_some() && self.get_bank_size_bits(*index) > 0)
+            .map(|(_, bank)| bank.wordsize);
+
+        match wordsizes.next()
+        {
+            Some(wordsize) if wordsizes.all(|w| w == wordsize) => wordsize,
+            _ => 8,
+        }
+    }
+
+
+    fn get_bank_size_bits(&self, index: usize) -> usize
+    {
+        let bank = &self.banks[index];
+
+        match (bank.fill, bank.addr_size)
+        {
+            (true, Some(addr_size)) => addr_size * bank.wordsize,
+            _ => self.bankdata[index].cur_bit_offset,
+        }
+    }
+
+
+    /// Formats the output as Motorola S-records, using the
+    /// shortest address field that fits every bank.
+    /// The output can be restricted to a single bank.
+    pub fn format_srec(&self, binary: &util::BitVec, bank_ref: Option<asm::BankRef>) -> String
+    {
+        let outputs: Vec<BankOutput> = self.get_bank_outputs(binary)
+            .into_iter()
+            .filter(|o| bank_ref.map_or(true, |b| b == o.bank_ref))
+            .collect();
+
+        let max_addr = outputs
+            .iter()
+            .filter(|o| o.data.is_some())
+            .map(|o| o.addr + o.size)
+            .max()
+            .unwrap_or(0);
+
+        let (data_kind, end_kind, addr_bytes) = if max_addr <= 0x1_0000
+            { (1, 9, 2) }
+        else if max_addr <= 0x100_0000
+            { (2, 8, 3) }
+        else
+            { (3, 7, 4) };
+
+        let mut result = String::new();
+        push_srec(&mut result, 0, 2, 0, &[]);
+
+        let mut record_count = 0;
+        for output in &outputs
+        {
+            if let Some(ref data) = output.data
+            {
+                for (i, chunk) in data.chunks(32).enumerate()
+                {
+                    push_srec(&mut result, data_kind, addr_bytes, output.addr + i * 32, chunk);
+                    record_count += 1;
+                }
+            }
+        }
+
+        if record_count <= 0xffff
+            { push_srec(&mut result, 5, 2, record_count, &[]); }
+        else if record_count <= 0xff_ffff
+            { push_srec(&mut result, 6, 3, record_count, &[]); }
+
+        push_srec(&mut result, end_kind, addr_bytes, 0, &[]);
+        result
+    }
+
+
+    /// Formats the output as TI-TXT, with a section starting
+    /// at the address of each bank. The output can be
+    /// restricted to a single bank.
+    pub fn format_titxt(&self, binary: &util::BitVec, bank_ref: Option<asm::BankRef>) -> String
+    {
+        let mut result = String::new();
+
+        for output in self.get_bank_outputs(binary)
+        {
+            if bank_ref.map_or(false, |b| b != output.bank_ref)
+            {
+                continue;
+            }
+
+            if let Some(data) = output.data
+            {
+                result.push_str(&format!("@{:04X}\n", output.addr));
+
+                for chunk in data.chunks(16)
+                {
+                    let bytes: Vec<String> = chunk
+                        .iter()
+                        .map(|b| format!("{:02X}", b))
+                        .collect();
+
+                    result.push_str(&bytes.join(" "));
+                    result.push('\n');
+                }
+            }
+        }
+
+        result.push_str("q\n");
+        result
+    }
+}
+
+
+fn push_srec(result: &mut String, kind: usize, addr_bytes: usize, addr: usize, data: &[u8])
+{
+    let count = addr_bytes + data.len() + 1;
+
+    let mut bytes = Vec::new();
+    bytes.push(count as u8);
+    for i in (0..addr_bytes).rev()
+    {
+        bytes.push((addr >> (i * 8)) as u8);
+    }
+    bytes.extend_from_slice(data);
+
+    let checksum = !bytes.iter().fold(0_u8, |sum, b| sum.wrapping_add(*b));
+
+    result.push_str(&format!("S{}", kind));
+    for byte in &bytes
+    {
+        result.push_str(&format!("{:02X}", byte));
+    }
+    result.push_str(&format!("{:02X}\n", checksum));
+}
\ No newline at end of file
@@ -19,6 +19,12 @@ pub fn parse_directive_res(
     state: &mut asm::parser::State)
     -> Result<(), ()>
 {
+    if state.parser.next_is(0, syntax::TokenKind::Identifier) &&
+        state.parser.next_is(1, syntax::TokenKind::Colon)
+    {
+        return asm::parser::parse_struct_instance(state);
+    }
+
     let words = asm::parser::parse_expr_usize(state)?;
 
     // FIXME: multiplication can overflow
@@ -46,6 +52,7 @@ pub fn parse_directive_align(
 
     let bankdata = state.asm_state.get_bankdata_mut(state.asm_state.cur_bank);
     bankdata.reserve(skip_bits);
+    bankdata.require_alignment(wordsize);
     
     Ok(())
 }
@@ -0,0 +1,124 @@
+use crate::*;
+
+
+/// Parses `#checksum algorithm [le]`, optionally followed by
+/// a bank name or by a start and end address in the current
+/// bank, and leaves space for the result at the current address.
+pub fn parse_directive_checksum(
+    state: &mut asm::parser::State,
+    tk_hash: &syntax::Token,
+    tk_directive: &syntax::Token)
+    -> Result<(), ()>
+{
+    let directive_span = tk_hash.span.join(&tk_directive.span);
+
+    if state.asm_state.object_mode
+    {
+        state.report.error_span("`#checksum` cannot be used in object files", &directive_span);
+        return Err(());
+    }
+
+    let tk_algorithm = state.parser.expect(syntax::TokenKind::Identifier)?;
+    let mut algorithm_name = tk_algorithm.excerpt.clone().unwrap();
+    let mut algorithm_span = tk_algorithm.span.clone();
+
+    // Names like `crc16-ccitt` are read as a subtraction
+    while !state.parser.next_is_linebreak() &&
+        state.parser.maybe_expect(syntax::TokenKind::Minus).is_some()
+    {
+        let tk_part = state.parser.expect(syntax::TokenKind::Identifier)?;
+        algorithm_name.push('-');
+        algorithm_name.push_str(tk_part.excerpt.as_ref().unwrap());
+        algorithm_span = algorithm_span.join(&tk_part.span);
+    }
+
+    let algorithm = match asm::ChecksumAlgorithm::from_name(&algorithm_name)
+    {
+        Some(algorithm) => algorithm,
+        None =>
+        {
+            state.report.error_span("unknown checksum algorithm", &algorithm_span);
+            return Err(());
+        }
+    };
+
+    let maybe_tk_le = if state.parser.next_is_linebreak()
+        { None }
+    else
+        { state.parser.maybe_expect(syntax::TokenKind::Identifier) };
+
+    let little_endian = match maybe_tk_le
+    {
+        None => false,
+        Some(tk_le) =>
+        {
+            if tk_le.excerpt.as_ref().unwrap() != "le"
+            {
+                state.report.error_span("expected `le`", &tk_le.span);
+                return Err(());
+            }
+
+            true
+        }
+    };
+
+    let mut range_bank = state.asm_state.cur_bank;
+    let mut range = None;
+
+    if state.parser.maybe_expect(syntax::TokenKind::Comma).is_some()
+    {
+        let start = expr::Expr::parse(&mut state.parser)?;
+
+        if state.parser.maybe_expect(syntax::TokenKind::Comma).is_some()
+        {
+            let end = expr::Expr::parse(&mut state.parser)?;
+            range = Some((start, end));
+        }
+        else
+        {
+            range_bank = match start
+            {
+                expr::Expr::Variable(ref span, 0, ref hierarchy) if hierarchy.len() == 1 =>
+                    state.asm_state.find_bank(&hierarchy[0][..], state.report.clone(), span)?,
+
+                _ =>
+                {
+                    state.report.error_span("expected bank name", &start.span());
+                    return Err(());
+                }
+            };
+        }
+    }
+
+    let span = directive_span.join(&state.parser.prev().span);
+    let ctx = state.asm_state.get_ctx(state);
+    let size = algorithm.size();
+
+    let bankdata = state.asm_state.get_bankdata(state.asm_state.cur_bank);
+    bankdata.check_writable(&state.asm_state, state.report.clone(), &span)?;
+
+    let bankdata = state.asm_state.get_bankdata_mut(state.asm_state.cur_bank);
+    bankdata.push_invocation(asm::Invocation
+    {
+        ctx: ctx.clone(),
+        size_guess: size,
+        span: span.clone(),
+        kind: asm::InvocationKind::Data(asm::DataInvocation
+        {
+            expr: expr::Value::make_integer(0).make_literal(),
+            elem_size: Some(size),
+        })
+    });
+
+    state.asm_state.checksums.push(asm::Checksum
+    {
+        decl_span: span,
+        algorithm,
+        little_endian,
+        ctx,
+        range_bank,
+        range,
+    });
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,199 @@
+use crate::*;
+
+
+pub fn parse_directive_if(
+    state: &mut asm::parser::State,
+    tk_hash: &syntax::Token,
+    tk_directive: &syntax::Token)
+    -> Result<(), ()>
+{
+    let if_span = tk_hash.span.join(&tk_directive.span);
+
+    let mut taken = parse_condition(state)?;
+    let mut any_taken = taken;
+    let mut seen_else = false;
+
+    loop
+    {
+        state.parser.expect_linebreak()?;
+
+        if taken
+        {
+            while !state.parser.is_over() && !next_is_branch_directive(state)
+            {
+                asm::parser::parse_line(state)?;
+            }
+        }
+        else
+        {
+            skip_branch(state);
+        }
+
+        if state.parser.is_over()
+        {
+            state.report.error_span("unterminated `#if`", &if_span);
+            return Err(());
+        }
+
+        let tk_branch_hash = state.parser.expect(syntax::TokenKind::Hash)?;
+        let tk_branch = state.parser.expect(syntax::TokenKind::Identifier)?;
+        let branch = tk_branch.excerpt.as_ref().unwrap().to_ascii_lowercase();
+        let branch_span = tk_branch_hash.span.join(&tk_branch.span);
+
+        match branch.as_ref()
+        {
+            "endif" => return Ok(()),
+
+            "elif" =>
+            {
+                if seen_else
+                {
+                    state.report.error_span("`#elif` after `#else`", &branch_span);
+                    return Err(());
+                }
+
+                if any_taken
+                {
+                    state.parser.skip_until_linebreak();
+                    taken = false;
+                }
+                else
+                {
+                    taken = parse_condition(state)?;
+                    any_taken = taken;
+                }
+            }
+
+            "else" =>
+            {
+                if seen_else
+                {
+                    state.report.error_span("duplicate `#else`", &branch_span);
+                    return Err(());
+                }
+
+                seen_else = true;
+                taken = !any_taken;
+                any_taken = true;
+            }
+
+            _ => unreachable!()
+        }
+    }
+}
+
+
+pub fn parse_directive_branch_without_if(
+    state: &mut asm::parser::State,
+    tk_hash: &syntax::Token,
+    tk_directive: &syntax::Token)
+    -> Result<(), ()>
+{
+    state.report.error_span(
+        format!(
+            "`#{}` without matching `#if`",
+            tk_directive.excerpt.as_ref().unwrap().to_ascii_lowercase()),
+        &tk_hash.span.join(&tk_directive.span));
+
+    Err(())
+}
+
+
+fn parse_condition(
+    state: &mut asm::parser::State)
+    -> Result<bool, ()>
+{
+    let expr = expr::Expr::parse(&mut state.parser)?;
+    let ctx = state.asm_state.get_ctx(&state);
+
+    // Evaluate with symbol guesses, so that conditions may
+    // refer to labels declared further ahead. The result is
+    // checked again against the final symbols after the pass.
+    let value = state.asm_state.eval_expr(
+        state.report.clone(),
+        &expr,
+        &ctx,
+        &mut expr::EvalContext::new(),
+        state.fileserver,
+        false)?;
+
+    let value = match value
+    {
+        expr::Value::Bool(value) => value,
+        expr::Value::Unknown => false,
+        _ =>
+        {
+            state.report.error_span("expected boolean condition", &expr.span());
+            return Err(());
+        }
+    };
+
+    state.asm_state.conditions.push(asm::Condition
+    {
+        ctx,
+        expr,
+        value,
+    });
+
+    Ok(value)
+}
+
+
+fn peek_directive_name(
+    state: &asm::parser::State)
+    -> Option<String>
+{
+    if !state.parser.next_is(0, syntax::TokenKind::Hash) ||
+        !state.parser.next_is(1, syntax::TokenKind::Identifier)
+    {
+        return None;
+    }
+
+    let mut parser = state.parser.clone();
+    parser.advance();
+    let tk_directive = parser.advance();
+
+    Some(tk_directive.excerpt.as_ref().unwrap().to_ascii_lowercase())
+}
+
+
+fn next_is_branch_directive(
+    state: &asm::parser::State)
+    -> bool
+{
+    match peek_directive_name(state).as_ref().map(|s| s.as_ref())
+    {
+        Some("elif") | Some("else") | Some("endif") => true,
+        _ => false,
+    }
+}
+
+
+fn skip_branch(
+    state: &mut asm::parser::State)
+{
+    let mut nesting = 0;
+
+    while !state.parser.is_over()
+    {
+        match peek_directive_name(state).as_ref().map(|s| s.as_ref())
+        {
+            Some("if") => nesting += 1,
+
+            Some("endif") if nesting > 0 => nesting -= 1,
+
+            Some("elif") | Some("else") | Some("endif") if nesting == 0 => return,
+
+            _ => {}
+        }
+
+        if !state.parser.next_is_linebreak()
+        {
+            let span = state.parser.next().span.clone();
+            state.asm_state.listing.push(asm::ListingEntry::Skipped(span));
+        }
+
+        state.parser.skip_until_linebreak();
+        state.parser.maybe_expect_linebreak();
+    }
+}
\ No newline at end of file
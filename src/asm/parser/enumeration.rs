@@ -0,0 +1,178 @@
+use crate::*;
+
+
+pub fn parse_directive_enum(
+    state: &mut asm::parser::State)
+    -> Result<(), ()>
+{
+    let tk_name = state.parser.expect(syntax::TokenKind::Identifier)?;
+    let name = tk_name.excerpt.clone().unwrap();
+
+    let mut step = util::BigInt::from(1);
+    let mut unique = false;
+
+    while !state.parser.next_is(0, syntax::TokenKind::BraceOpen)
+    {
+        let tk_option = state.parser.expect_msg(syntax::TokenKind::Identifier, "expected `{`")?;
+
+        match tk_option.excerpt.as_ref().unwrap().as_ref()
+        {
+            "step" => step = asm::parser::parse_expr_bigint(state)?.0,
+            "unique" => unique = true,
+            _ =>
+            {
+                state.report.error_span("unknown enum option", &tk_option.span);
+                return Err(());
+            }
+        }
+    }
+
+    state.parser.expect(syntax::TokenKind::BraceOpen)?;
+
+    // The enum's own symbol only groups its members
+    let prev_ctx = state.asm_state.symbols.get_ctx();
+
+    state.asm_state.symbols.create(
+        &asm::SymbolContext::new(Vec::new()),
+        name.clone(),
+        0,
+        asm::SymbolKind::Constant,
+        expr::Value::Void,
+        state.asm_state.cur_bank,
+        state.report.clone(),
+        &tk_name.span)?;
+
+    let mut next_value = util::BigInt::from(0);
+    let mut used_values: Vec<(util::BigInt, diagn::Span)> = Vec::new();
+    let mut prev_member: Option<String> = None;
+
+    while !state.parser.next_is(0, syntax::TokenKind::BraceClose)
+    {
+        let tk_member = state.parser.expect(syntax::TokenKind::Identifier)?;
+        let member = tk_member.excerpt.clone().unwrap();
+        let span = tk_member.span.clone();
+
+        if state.parser.maybe_expect(syntax::TokenKind::Equal).is_some()
+        {
+            next_value = parse_member_value(state, &span)?;
+            prev_member = Some(member.clone());
+        }
+        else if let Some(prev) = prev_member.replace(member.clone())
+        {
+            // Once a value was given, the members after it are
+            // recorded as the previous one plus the step, so
+            // that they follow a value imported at the link step
+            record_implicit_member(state, &name, &prev, &step, &span);
+        }
+
+        if unique
+        {
+            if let Some((_, first_span)) = used_values.iter().find(|(value, _)| value == &next_value)
+            {
+                let _guard = state.report.push_parent("duplicate enum value", &span);
+                state.report.note_span("first used here", first_span);
+                return Err(());
+            }
+
+            used_values.push((next_value.clone(), span.clone()));
+        }
+
+        state.asm_state.symbols.create(
+            &asm::SymbolContext::new(vec![name.clone()]),
+            member,
+            1,
+            asm::SymbolKind::Constant,
+            expr::Value::make_integer(next_value.clone()),
+            state.asm_state.cur_bank,
+            state.report.clone(),
+            &span)?;
+
+        next_value = &next_value + &step;
+
+        if state.parser.maybe_expect_linebreak().is_some()
+            { continue; }
+
+        if state.parser.next_is(0, syntax::TokenKind::BraceClose)
+            { break; }
+
+        state.parser.expect(syntax::TokenKind::Comma)?;
+    }
+
+    state.parser.expect(syntax::TokenKind::BraceClose)?;
+
+    state.asm_state.symbols.set_ctx(prev_ctx);
+
+    Ok(())
+}
+
+
+/// Evaluates an explicit member value like the expression
+/// of a constant, and records it for the link step.
+fn parse_member_value(
+    state: &mut asm::parser::State,
+    span: &diagn::Span)
+    -> Result<util::BigInt, ()>
+{
+    let ctx = state.asm_state.get_ctx(state);
+    let expr = expr::Expr::parse(&mut state.parser)?;
+    let value = state.asm_state.eval_expr(
+        state.report.clone(),
+        &expr,
+        &ctx,
+        &mut expr::EvalContext::new(),
+        state.fileserver,
+        true)?;
+
+    let bigint = match value.get_bigint()
+    {
+        Some(bigint) => bigint,
+        None =>
+        {
+            state.report.error_span("expected integer value", &expr.span());
+            return Err(());
+        }
+    };
+
+    state.asm_state.constant_decls.push(asm::ConstantDecl
+    {
+        decl_span: span.clone(),
+        ctx,
+        expr,
+    });
+
+    Ok(bigint)
+}
+
+
+/// Records an implicit member as the expression
+/// `Enum.prev + step`, for the link step.
+fn record_implicit_member(
+    state: &mut asm::parser::State,
+    enum_name: &str,
+    prev_member: &str,
+    step: &util::BigInt,
+    span: &diagn::Span)
+{
+    let prev = expr::Expr::Variable(
+        span.clone(),
+        0,
+        vec![enum_name.to_string(), prev_member.to_string()]);
+
+    let step = expr::Expr::Literal(
+        span.clone(),
+        expr::Value::make_integer(step.clone()));
+
+    let ctx = state.asm_state.get_ctx(state);
+
+    state.asm_state.constant_decls.push(asm::ConstantDecl
+    {
+        decl_span: span.clone(),
+        ctx,
+        expr: expr::Expr::BinaryOp(
+            span.clone(),
+            span.clone(),
+            expr::BinaryOp::Add,
+            Box::new(prev),
+            Box::new(step)),
+    });
+}
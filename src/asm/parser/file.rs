@@ -18,11 +18,18 @@ pub fn parse_file<TFilename: Into<String>>(
         return Ok(());
     }
 
+    asm_state.track_read_file(&filename);
     let chars = fileserver.get_chars(report.clone(), &filename, span)?;
     let tokens = syntax::tokenize(report.clone(), &filename, &chars)?;
     let parser = syntax::Parser::new(Some(report.clone()), &tokens);
 
     parsed_filenames.insert(filename.clone());
+
+    asm_state.listing.push(asm::ListingEntry::FileBegin
+    {
+        filename: filename.clone(),
+        include_span: span.cloned(),
+    });
     
     let mut state = asm::parser::State
     {
@@ -30,6 +37,7 @@ pub fn parse_file<TFilename: Into<String>>(
         asm_state,
         fileserver,
         filename: std::rc::Rc::new(filename.clone()),
+        locals: std::rc::Rc::new(Vec::new()),
         parser,
         parsed_filenames,
         once_filenames,
@@ -41,6 +49,8 @@ pub fn parse_file<TFilename: Into<String>>(
     {
         parse_line(&mut state)?;
     }
+
+    state.asm_state.listing.push(asm::ListingEntry::FileEnd);
     
     parsed_filenames.remove(&filename);
 	Ok(())
@@ -49,6 +59,57 @@ pub fn parse_file<TFilename: Into<String>>(
 
 pub fn parse_line(state: &mut asm::parser::State)
     -> Result<(), ()>
+{
+    if state.parser.next_is_linebreak()
+    {
+        state.parser.expect_linebreak()?;
+        return Ok(());
+    }
+
+    // Record where this line's output begins, so that
+    // the listing can later be matched against the source.
+    let listing_index = state.asm_state.listing.len();
+    let ctx = state.asm_state.get_ctx(&state);
+    let invocations_start = state.asm_state.get_bankdata(ctx.bank_ref).invocations.len();
+
+    state.asm_state.listing.push(asm::ListingEntry::Line
+    {
+        span: state.parser.next().span.clone(),
+        ctx,
+        invocations_start,
+        invocations_end: invocations_start,
+        bit_offset_end: 0,
+        nested: false,
+    });
+
+    parse_line_inner(state)?;
+
+    // What follows a label on the same line
+    // is listed in the same row
+    while state.parser.prev().kind == syntax::TokenKind::Colon &&
+        !state.parser.is_over() &&
+        !state.parser.next_is_linebreak()
+    {
+        parse_line_inner(state)?;
+    }
+
+    let is_nested = state.asm_state.listing.len() > listing_index + 1;
+
+    if let asm::ListingEntry::Line { ctx, invocations_end, bit_offset_end, nested, .. } =
+        &mut state.asm_state.listing[listing_index]
+    {
+        let bankdata = &state.asm_state.bankdata[ctx.bank_ref.index];
+        *invocations_end = bankdata.invocations.len();
+        *bit_offset_end = bankdata.cur_bit_offset;
+        *nested = is_nested;
+    }
+
+    Ok(())
+}
+
+
+fn parse_line_inner(state: &mut asm::parser::State)
+    -> Result<(), ()>
 {
     if state.parser.next_is(0, syntax::TokenKind::Hash)
     {
@@ -68,9 +129,9 @@ pub fn parse_line(state: &mut asm::parser::State)
     {
         asm::parser::parse_symbol(state)?;
     }
-    else if state.parser.maybe_expect_linebreak().is_some()
+    else if asm::parser::next_is_macro_invocation(state)
     {
-        return Ok(());
+        asm::parser::parse_macro_invocation(state)?;
     }
     else
     {
@@ -123,6 +184,14 @@ pub fn parse_directive(state: &mut asm::parser::State)
             "labelalign" => asm::parser::parse_directive_labelalign(state)?,
             "addr" => asm::parser::parse_directive_addr(state)?,
             "fn" => asm::parser::parse_directive_fn(state)?,
+            "macro" => asm::parser::parse_directive_macro(state)?,
+            "if" => asm::parser::parse_directive_if(state, &tk_hash, &tk_directive)?,
+            "elif" | "else" | "endif" => asm::parser::parse_directive_branch_without_if(state, &tk_hash, &tk_directive)?,
+            "repeat" => asm::parser::parse_directive_repeat(state, &tk_hash, &tk_directive)?,
+            "for" => asm::parser::parse_directive_for(state, &tk_hash, &tk_directive)?,
+            "struct" => asm::parser::parse_directive_struct(state)?,
+            "enum" => asm::parser::parse_directive_enum(state)?,
+            "checksum" => asm::parser::parse_directive_checksum(state, &tk_hash, &tk_directive)?,
             //"enable" => asm::parser::parse_directive_enable(state)?,
             _ =>
             {
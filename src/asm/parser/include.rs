@@ -11,8 +11,10 @@ pub fn parse_directive_include(
         tk_filename.excerpt.as_ref().unwrap(),
         &tk_filename.span)?;
 
-    let new_filename = util::filename_navigate(
+    let new_filename = util::filename_search(
         state.report.clone(),
+        state.fileserver,
+        &state.asm_state.include_paths,
         &state.filename,
         &filename,
         &tk_filename.span)?;
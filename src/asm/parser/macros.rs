@@ -0,0 +1,281 @@
+use crate::*;
+
+
+pub fn parse_directive_macro(
+    state: &mut asm::parser::State)
+    -> Result<(), ()>
+{
+    let tk_name = state.parser.expect(syntax::TokenKind::Identifier)?;
+    let name = tk_name.excerpt.unwrap().clone();
+
+    if let Some(duplicate) = state.asm_state.macros.iter().find(|m| m.name == name)
+    {
+        let _guard = state.report.push_parent("duplicate macro", &tk_name.span);
+        state.report.note_span("first declared here", &duplicate.decl_span);
+        return Err(());
+    }
+
+    state.parser.expect(syntax::TokenKind::ParenOpen)?;
+
+    let mut params = Vec::new();
+    while !state.parser.is_over() && !state.parser.next_is(0, syntax::TokenKind::ParenClose)
+    {
+        let tk_param = state.parser.expect(syntax::TokenKind::Identifier)?;
+        let param = tk_param.excerpt.unwrap().clone();
+
+        if params.contains(&param)
+        {
+            state.report.error_span("duplicate parameter", &tk_param.span);
+            return Err(());
+        }
+
+        params.push(param);
+
+        state.parser.maybe_expect(syntax::TokenKind::Comma);
+    }
+
+    state.parser.expect(syntax::TokenKind::ParenClose)?;
+
+    state.parser.expect(syntax::TokenKind::BraceOpen)?;
+    let body = state.parser
+        .slice_until_token_over_nested_braces(syntax::TokenKind::BraceClose)
+        .get_cloned_tokens();
+    state.parser.expect(syntax::TokenKind::BraceClose)?;
+
+    let mac = asm::Macro
+    {
+        decl_span: tk_name.span.clone(),
+        name,
+        params,
+        body,
+    };
+
+    state.asm_state.macros.push(mac);
+
+    Ok(())
+}
+
+
+pub fn next_is_macro_invocation(
+    state: &asm::parser::State)
+    -> bool
+{
+    if !state.parser.next_is(0, syntax::TokenKind::Identifier)
+    {
+        return false;
+    }
+
+    let tk_name = state.parser.next();
+    let name = tk_name.excerpt.as_ref().unwrap();
+
+    state.asm_state.macros.iter().any(|m| m.name == *name)
+}
+
+
+pub fn parse_macro_invocation(
+    state: &mut asm::parser::State)
+    -> Result<(), ()>
+{
+    let tk_name = state.parser.expect(syntax::TokenKind::Identifier)?;
+    let name = tk_name.excerpt.unwrap().clone();
+
+    let args_parser = state.parser.slice_until_linebreak();
+    let call_span = tk_name.span.join(&args_parser.get_full_span());
+    let args = split_arguments(args_parser.tokens);
+
+    state.parser.expect_linebreak()?;
+
+    let macro_index = state.asm_state.macros
+        .iter()
+        .position(|m| m.name == name)
+        .unwrap();
+
+    let _guard = state.report.push_parent(
+        format!("failed to expand macro `{}`", name),
+        &call_span);
+
+    if state.asm_state.expanding_macros.contains(&macro_index)
+    {
+        state.report.error_span("recursive macro expansion", &tk_name.span);
+        return Err(());
+    }
+
+    let mac = &state.asm_state.macros[macro_index];
+
+    if args.len() != mac.params.len()
+    {
+        let _guard = state.report.push_parent("wrong number of arguments", &call_span);
+        state.report.note_span(
+            format!(
+                "macro takes {} argument{}",
+                mac.params.len(),
+                if mac.params.len() == 1 { "" } else { "s" }),
+            &mac.decl_span);
+
+        return Err(());
+    }
+
+    let body = substitute_arguments(mac, &args);
+    let filename = mac.decl_span.file.clone();
+
+    // Every expansion gets a scope of its own, so that local
+    // labels in the body don't clash between expansions.
+    // The scope names are stable between passes, which
+    // lets symbol guesses carry over.
+    state.asm_state.macro_expansions += 1;
+    let scope_name = format!("{}#{}", name, state.asm_state.macro_expansions);
+
+    let ctx = state.asm_state.get_ctx(&state);
+    let addr = state.asm_state.get_addr_aprox(&ctx);
+
+    let prev_ctx = state.asm_state.symbols.enter_scope(
+        scope_name,
+        expr::Value::make_integer(addr),
+        state.asm_state.cur_bank,
+        state.report.clone(),
+        &call_span)?;
+
+    state.asm_state.expanding_macros.push(macro_index);
+
+    let mut body_state = asm::parser::State
+    {
+        report: state.report.clone(),
+        asm_state: &mut *state.asm_state,
+        fileserver: state.fileserver,
+        filename,
+        // Arguments are pasted into the body, so values bound
+        // by a `#for` around the invocation stay visible
+        locals: state.locals.clone(),
+        parser: syntax::Parser::new(Some(state.report.clone()), &body),
+        parsed_filenames: &mut *state.parsed_filenames,
+        once_filenames: &mut *state.once_filenames,
+    };
+
+    while !body_state.parser.is_over()
+    {
+        asm::parser::parse_line(&mut body_state)?;
+    }
+
+    state.asm_state.expanding_macros.pop();
+    state.asm_state.symbols.set_ctx(prev_ctx);
+
+    Ok(())
+}
+
+
+/// Splits the rest of an invocation line into arguments
+/// at the commas that aren't nested inside brackets.
+fn split_arguments(
+    tokens: &[syntax::Token])
+    -> Vec<Vec<syntax::Token>>
+{
+    let mut args = Vec::new();
+    if tokens.iter().all(|t| t.kind.ignorable())
+    {
+        return args;
+    }
+
+    let mut arg = Vec::new();
+    let mut nesting = 0;
+
+    for token in tokens
+    {
+        match token.kind
+        {
+            syntax::TokenKind::ParenOpen |
+            syntax::TokenKind::BracketOpen |
+            syntax::TokenKind::BraceOpen =>
+                nesting += 1,
+
+            syntax::TokenKind::ParenClose |
+            syntax::TokenKind::BracketClose |
+            syntax::TokenKind::BraceClose =>
+                nesting -= 1,
+
+            syntax::TokenKind::Comma if nesting == 0 =>
+            {
+                args.push(trim_ignorable(&arg));
+                arg.clear();
+                continue;
+            }
+
+            _ => {}
+        }
+
+        arg.push(token.clone());
+    }
+
+    args.push(trim_ignorable(&arg));
+    args
+}
+
+
+fn trim_ignorable(
+    tokens: &[syntax::Token])
+    -> Vec<syntax::Token>
+{
+    let start = tokens.iter().position(|t| !t.kind.ignorable()).unwrap_or(tokens.len());
+    let end = tokens.iter().rposition(|t| !t.kind.ignorable()).map(|i| i + 1).unwrap_or(start);
+
+    tokens[start..end].to_vec()
+}
+
+
+/// Replaces `{param}` occurrences in the macro body with the
+/// argument tokens, taking the span of the occurrence so that
+/// every token in the expansion belongs to the body's file.
+fn substitute_arguments(
+    mac: &asm::Macro,
+    args: &[Vec<syntax::Token>])
+    -> Vec<syntax::Token>
+{
+    let body = &mac.body;
+    let next_significant = |index: usize|
+    {
+        let mut index = index;
+        while index < body.len() && body[index].kind.ignorable()
+        {
+            index += 1;
+        }
+
+        index
+    };
+
+    let mut result = Vec::new();
+    let mut index = 0;
+
+    while index < body.len()
+    {
+        if body[index].kind == syntax::TokenKind::BraceOpen
+        {
+            let name_index = next_significant(index + 1);
+            let close_index = next_significant(name_index + 1);
+
+            if close_index < body.len() &&
+                body[name_index].kind == syntax::TokenKind::Identifier &&
+                body[close_index].kind == syntax::TokenKind::BraceClose
+            {
+                let name = body[name_index].excerpt.as_ref().unwrap();
+                if let Some(param_index) = mac.params.iter().position(|p| p == name)
+                {
+                    let sub_span = body[index].span.join(&body[close_index].span);
+
+                    for token in &args[param_index]
+                    {
+                        let mut sub_token = token.clone();
+                        sub_token.span = sub_span.clone();
+                        result.push(sub_token);
+                    }
+
+                    index = close_index + 1;
+                    continue;
+                }
+            }
+        }
+
+        result.push(body[index].clone());
+        index += 1;
+    }
+
+    result
+}
\ No newline at end of file
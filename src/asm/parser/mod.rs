@@ -9,6 +9,12 @@ mod data;
 mod addr_related;
 mod include;
 mod function;
+mod macros;
+mod condition;
+mod repeat;
+mod structure;
+mod enumeration;
+mod checksum;
 
 
 pub use self::state::State;
@@ -21,4 +27,10 @@ pub use self::symbol::*;
 pub use self::data::*;
 pub use self::addr_related::*;
 pub use self::include::*;
-pub use self::function::*;
\ No newline at end of file
+pub use self::function::*;
+pub use self::macros::*;
+pub use self::condition::*;
+pub use self::repeat::*;
+pub use self::structure::*;
+pub use self::enumeration::*;
+pub use self::checksum::*;
\ No newline at end of file
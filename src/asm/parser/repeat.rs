@@ -0,0 +1,199 @@
+use crate::*;
+
+
+pub fn parse_directive_repeat(
+    state: &mut asm::parser::State,
+    tk_hash: &syntax::Token,
+    tk_directive: &syntax::Token)
+    -> Result<(), ()>
+{
+    let directive_span = tk_hash.span.join(&tk_directive.span);
+
+    let count = asm::parser::parse_expr_usize(state)?;
+    let body = parse_body(state)?;
+
+    for index in 0..count
+    {
+        expand_iteration(
+            state,
+            &body,
+            state.locals.clone(),
+            format!("failed to expand `#repeat` iteration {}", index),
+            &directive_span)?;
+    }
+
+    Ok(())
+}
+
+
+pub fn parse_directive_for(
+    state: &mut asm::parser::State,
+    tk_hash: &syntax::Token,
+    tk_directive: &syntax::Token)
+    -> Result<(), ()>
+{
+    let directive_span = tk_hash.span.join(&tk_directive.span);
+
+    let tk_var = state.parser.expect(syntax::TokenKind::Identifier)?;
+    let var = tk_var.excerpt.clone().unwrap();
+
+    let tk_in = state.parser.expect_msg(syntax::TokenKind::Identifier, "expected `in`")?;
+    if tk_in.excerpt.as_ref().unwrap() != "in"
+    {
+        state.report.error_span("expected `in`", &tk_in.span);
+        return Err(());
+    }
+
+    // The start bound is parsed on its own, since an
+    // identifier followed by `.` reads as a nested symbol
+    let mut start_parser = slice_until_range(state);
+    let start = parse_bound(state, &mut start_parser, "`..`")?;
+
+    state.parser.expect(syntax::TokenKind::Dot)?;
+    state.parser.expect(syntax::TokenKind::Dot)?;
+
+    let mut end_parser = state.parser.slice_until_token(syntax::TokenKind::BraceOpen);
+    let end = parse_bound(state, &mut end_parser, "`{`")?;
+
+    let body = parse_body(state)?;
+
+    for value in start..end
+    {
+        let mut locals = (*state.locals).clone();
+        locals.push((var.clone(), util::BigInt::from(value)));
+
+        expand_iteration(
+            state,
+            &body,
+            std::rc::Rc::new(locals),
+            format!("failed to expand `#for` with `{} = {}`", var, value),
+            &directive_span)?;
+    }
+
+    Ok(())
+}
+
+
+fn parse_body(
+    state: &mut asm::parser::State)
+    -> Result<Vec<syntax::Token>, ()>
+{
+    state.parser.expect(syntax::TokenKind::BraceOpen)?;
+    let body = state.parser
+        .slice_until_token_over_nested_braces(syntax::TokenKind::BraceClose)
+        .get_cloned_tokens();
+    state.parser.expect(syntax::TokenKind::BraceClose)?;
+
+    Ok(body)
+}
+
+
+fn slice_until_range<'a>(
+    state: &mut asm::parser::State<'a>)
+    -> syntax::Parser<'a>
+{
+    let start = state.parser.get_current_token_index();
+
+    while !state.parser.is_over() && !state.parser.next_is_linebreak()
+    {
+        if state.parser.next_is(0, syntax::TokenKind::Dot) &&
+            state.parser.next_is(1, syntax::TokenKind::Dot)
+        {
+            break;
+        }
+
+        state.parser.advance();
+    }
+
+    state.parser.clone_slice(start, state.parser.get_current_token_index())
+}
+
+
+fn parse_bound(
+    state: &mut asm::parser::State,
+    parser: &mut syntax::Parser,
+    expected: &str)
+    -> Result<isize, ()>
+{
+    let expr = expr::Expr::parse(parser)?;
+    if !parser.is_over()
+    {
+        state.report.error_span(format!("expected {}", expected), &parser.next().span);
+        return Err(());
+    }
+
+    let value = state.asm_state.eval_expr(
+        state.report.clone(),
+        &expr,
+        &state.asm_state.get_ctx(&state),
+        &mut expr::EvalContext::new(),
+        state.fileserver,
+        true)?;
+
+    match value.get_bigint().and_then(|bigint| bigint.checked_to_isize())
+    {
+        Some(value) => Ok(value),
+        None =>
+        {
+            state.report.error_span("expected integer value", &expr.span());
+            Err(())
+        }
+    }
+}
+
+
+/// Parses one copy of the body, with the given values bound.
+/// Like a macro expansion, every iteration gets a scope of
+/// its own, so that local labels don't clash between iterations.
+fn expand_iteration(
+    state: &mut asm::parser::State,
+    body: &[syntax::Token],
+    locals: std::rc::Rc<Vec<(String, util::BigInt)>>,
+    descr: String,
+    span: &diagn::Span)
+    -> Result<(), ()>
+{
+    let _guard = state.report.push_parent(descr.clone(), span);
+
+    state.asm_state.macro_expansions += 1;
+    let scope_name = format!("iteration#{}", state.asm_state.macro_expansions);
+
+    let ctx = state.asm_state.get_ctx(&state);
+    let addr = state.asm_state.get_addr_aprox(&ctx);
+
+    let prev_ctx = state.asm_state.symbols.enter_scope(
+        scope_name.clone(),
+        expr::Value::make_integer(addr),
+        state.asm_state.cur_bank,
+        state.report.clone(),
+        span)?;
+
+    state.asm_state.iterations.insert(
+        scope_name,
+        asm::Iteration
+        {
+            descr,
+            span: span.clone(),
+        });
+
+    let mut body_state = asm::parser::State
+    {
+        report: state.report.clone(),
+        asm_state: &mut *state.asm_state,
+        fileserver: state.fileserver,
+        filename: state.filename.clone(),
+        locals,
+        parser: syntax::Parser::new(Some(state.report.clone()), body),
+        parsed_filenames: &mut *state.parsed_filenames,
+        once_filenames: &mut *state.once_filenames,
+    };
+
+    while !body_state.parser.is_over()
+    {
+        asm::parser::parse_line(&mut body_state)?;
+    }
+
+    state.asm_state.symbols.set_ctx(prev_ctx);
+
+    Ok(())
+}
\ No newline at end of file
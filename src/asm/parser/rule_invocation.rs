@@ -120,7 +120,7 @@ pub fn match_rule_invocation(
             {
                 // If the production expression couldn't be resolved,
                 // try using a size guess from a previous iteration.
-                match asm_state.instruction_size_guesses.get(&invocation.span)
+                match asm_state.instruction_size_guesses.get(&invocation.size_guess_key())
                 {
                     Some(guess) => *guess,
                     None => 0,
@@ -7,6 +7,9 @@ pub struct State<'a>
     pub asm_state: &'a mut asm::State,
     pub fileserver: &'a dyn util::FileServer,
     pub filename: std::rc::Rc<String>,
+    /// Values bound by the enclosing `#for` directives,
+    /// with the innermost ones last.
+    pub locals: std::rc::Rc<Vec<(String, util::BigInt)>>,
     pub parser: syntax::Parser<'a>,
     pub parsed_filenames: &'a mut std::collections::HashSet<String>,
     pub once_filenames: &'a mut std::collections::HashSet<String>,
@@ -0,0 +1,235 @@
+use crate::*;
+
+
+pub fn parse_directive_struct(
+    state: &mut asm::parser::State)
+    -> Result<(), ()>
+{
+    let tk_name = state.parser.expect(syntax::TokenKind::Identifier)?;
+    let name = tk_name.excerpt.clone().unwrap();
+
+    if let Some(duplicate) = state.asm_state.structs.iter().find(|s| s.name == name)
+    {
+        let _guard = state.report.push_parent("duplicate struct", &tk_name.span);
+        state.report.note_span("first declared here", &duplicate.decl_span);
+        return Err(());
+    }
+
+    state.parser.expect(syntax::TokenKind::BraceOpen)?;
+
+    let mut fields = Vec::new();
+    let mut size = 0;
+
+    while !state.parser.next_is(0, syntax::TokenKind::BraceClose)
+    {
+        let field = parse_field(state, size)?;
+        size += field.size;
+        fields.push(field);
+
+        if state.parser.maybe_expect_linebreak().is_some()
+            { continue; }
+
+        if state.parser.next_is(0, syntax::TokenKind::BraceClose)
+            { break; }
+
+        state.parser.expect(syntax::TokenKind::Comma)?;
+    }
+
+    state.parser.expect(syntax::TokenKind::BraceClose)?;
+
+    state.asm_state.structs.push(asm::Struct
+    {
+        decl_span: tk_name.span.clone(),
+        name: name.clone(),
+        fields,
+        size,
+        wordsize: state.asm_state.cur_wordsize,
+    });
+
+    // The struct's own symbol is the offset of its
+    // start, so that nested fields read the same way
+    let prev_ctx = state.asm_state.symbols.get_ctx();
+
+    state.asm_state.symbols.create(
+        &asm::SymbolContext::new(Vec::new()),
+        name.clone(),
+        0,
+        asm::SymbolKind::Constant,
+        expr::Value::make_integer(0),
+        state.asm_state.cur_bank,
+        state.report.clone(),
+        &tk_name.span)?;
+
+    state.asm_state.create_struct_field_symbols(
+        &mut vec![name.clone()],
+        state.asm_state.structs.len() - 1,
+        &util::BigInt::from(0),
+        asm::SymbolKind::Constant,
+        None,
+        state.report.clone())?;
+
+    state.asm_state.symbols.create(
+        &asm::SymbolContext::new(vec![name]),
+        "size".to_string(),
+        1,
+        asm::SymbolKind::Constant,
+        expr::Value::make_integer(size),
+        state.asm_state.cur_bank,
+        state.report.clone(),
+        &tk_name.span)?;
+
+    state.asm_state.symbols.set_ctx(prev_ctx);
+
+    Ok(())
+}
+
+
+/// Parses a `name: size` field, where the size is either
+/// a number of bits or the name of a previously declared struct.
+fn parse_field(
+    state: &mut asm::parser::State,
+    offset: usize)
+    -> Result<asm::StructField, ()>
+{
+    let tk_name = state.parser.expect(syntax::TokenKind::Identifier)?;
+    state.parser.expect(syntax::TokenKind::Colon)?;
+
+    // `Name.size` is declared along with the fields
+    if tk_name.excerpt.as_ref().unwrap() == "size"
+    {
+        state.report.error_span(
+            "field cannot be named `size`, which holds the size of the struct",
+            &tk_name.span);
+        return Err(());
+    }
+
+    let inner_struct_index = if state.parser.next_is(0, syntax::TokenKind::Identifier) &&
+        !state.parser.next_is(1, syntax::TokenKind::Dot)
+    {
+        state.asm_state.find_struct(state.parser.next().excerpt.as_ref().unwrap())
+    }
+    else
+    {
+        None
+    };
+
+    let size = match inner_struct_index
+    {
+        Some(index) =>
+        {
+            let tk_struct = state.parser.advance();
+            state.asm_state.check_struct_wordsize(
+                index,
+                state.report.clone(),
+                &tk_struct.span)?;
+
+            state.asm_state.structs[index].size
+        }
+
+        None =>
+        {
+            let size_span = state.parser.next().span.clone();
+            let bits = asm::parser::parse_expr_usize(state)?;
+            let size_span = size_span.join(&state.parser.prev().span);
+
+            let wordsize = state.asm_state.cur_wordsize;
+            if bits % wordsize != 0
+            {
+                state.report.error_span(
+                    format!("field size is not a multiple of the word size ({} bits)", wordsize),
+                    &size_span);
+                return Err(());
+            }
+
+            bits / wordsize
+        }
+    };
+
+    Ok(asm::StructField
+    {
+        decl_span: tk_name.span.clone(),
+        name: tk_name.excerpt.clone().unwrap(),
+        offset,
+        size,
+        struct_index: inner_struct_index,
+    })
+}
+
+
+/// Parses `#res name: Struct`, which declares a label at the
+/// current address, with a label for each field, and reserves
+/// space for the whole struct after it.
+pub fn parse_struct_instance(
+    state: &mut asm::parser::State)
+    -> Result<(), ()>
+{
+    let tk_name = state.parser.expect(syntax::TokenKind::Identifier)?;
+    let name = tk_name.excerpt.clone().unwrap();
+    state.parser.expect(syntax::TokenKind::Colon)?;
+
+    let tk_struct = state.parser.expect(syntax::TokenKind::Identifier)?;
+    let struct_index = match state.asm_state.find_struct(tk_struct.excerpt.as_ref().unwrap())
+    {
+        Some(index) => index,
+        None =>
+        {
+            state.report.error_span("unknown struct", &tk_struct.span);
+            return Err(());
+        }
+    };
+
+    state.asm_state.check_struct_wordsize(
+        struct_index,
+        state.report.clone(),
+        &tk_struct.span)?;
+
+    let span = tk_name.span.join(&tk_struct.span);
+
+    let ctx = state.asm_state.get_ctx(state);
+    let addr = state.asm_state.get_addr(
+        state.report.clone(),
+        &ctx,
+        &span)?;
+
+    let bankdata = state.asm_state.get_bankdata_mut(state.asm_state.cur_bank);
+    bankdata.push_invocation(asm::Invocation
+    {
+        ctx: ctx.clone(),
+        size_guess: 0,
+        span: span.clone(),
+        kind: asm::InvocationKind::Label(asm::LabelInvocation)
+    });
+
+    // Unlike a plain label, an instance doesn't
+    // become the parent of the local labels after it
+    let prev_ctx = state.asm_state.symbols.get_ctx();
+
+    state.asm_state.symbols.create(
+        &asm::SymbolContext::new(Vec::new()),
+        name.clone(),
+        0,
+        asm::SymbolKind::Label,
+        expr::Value::make_integer(addr.clone()),
+        state.asm_state.cur_bank,
+        state.report.clone(),
+        &span)?;
+
+    state.asm_state.create_struct_field_symbols(
+        &mut vec![name],
+        struct_index,
+        &addr,
+        asm::SymbolKind::Label,
+        Some(&span),
+        state.report.clone())?;
+
+    state.asm_state.symbols.set_ctx(prev_ctx);
+
+    // FIXME: multiplication can overflow
+    let structure = &state.asm_state.structs[struct_index];
+    let bits = structure.size * structure.wordsize;
+
+    let bankdata = state.asm_state.get_bankdata_mut(state.asm_state.cur_bank);
+    bankdata.reserve(bits);
+
+    Ok(())
+}
\ No newline at end of file
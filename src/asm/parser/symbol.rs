@@ -35,6 +35,14 @@ pub fn parse_symbol(
             true)?;
 
         state.parser.expect_linebreak()?;
+
+        state.asm_state.constant_decls.push(asm::ConstantDecl
+        {
+            decl_span: span.clone(),
+            ctx: ctx.clone(),
+            expr,
+        });
+
         value
     }
     else
@@ -43,13 +51,15 @@ pub fn parse_symbol(
 
         if hierarchy_level == 0 && state.asm_state.cur_labelalign != 0
         {
+            let labelalign = state.asm_state.cur_labelalign;
             let bankdata = state.asm_state.get_bankdata(state.asm_state.cur_bank);
             let skip_bits = bankdata.bits_until_aligned(
                 state.asm_state,
-                state.asm_state.cur_labelalign);
+                labelalign);
         
             let bankdata = state.asm_state.get_bankdata_mut(state.asm_state.cur_bank);
             bankdata.reserve(skip_bits);
+            bankdata.require_alignment(labelalign);
         }
 
         let tk_colon = state.parser.expect(syntax::TokenKind::Colon)?;
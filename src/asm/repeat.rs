@@ -0,0 +1,23 @@
+use crate::*;
+
+
+/// An iteration of a `#repeat` or `#for` body, kept so that
+/// errors found while resolving the body can say which
+/// iteration they come from.
+#[derive(Debug)]
+pub struct Iteration
+{
+    pub descr: String,
+    pub span: diagn::Span,
+}
+
+
+impl asm::State
+{
+    /// Returns the iteration whose scope the context is in.
+    pub fn get_iteration(&self, ctx: &asm::Context) -> Option<&Iteration>
+    {
+        let scope = ctx.symbol_ctx.hierarchy().first()?;
+        self.iterations.get(scope)
+    }
+}
\ No newline at end of file
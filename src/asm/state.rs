@@ -5,9 +5,18 @@ use std::collections::HashMap;
 static DEBUG_CANDIDATE_RESOLUTION: bool = false;
 
 
+/// The largest size, in bits, that built-in functions like
+/// `sext` accept, so that a mistaken size reports an error
+/// instead of building a huge integer.
+const MAX_FUNCTION_SIZE_ARG: usize = 1 << 16;
+
+
 pub struct Assembler
 {
 	pub root_files: Vec<String>,
+	pub defined_symbols: Vec<(String, expr::Value)>,
+	pub include_paths: Vec<String>,
+	pub object_mode: bool,
 	pub state: State,
 }
 
@@ -19,10 +28,24 @@ pub struct State
 	pub bankdata: Vec<asm::BankData>,
 	pub symbols: asm::SymbolManager,
 	pub symbol_guesses: asm::SymbolManager,
-	pub instruction_size_guesses: HashMap<diagn::Span, usize>,
+	pub instruction_size_guesses: HashMap<(diagn::Span, Vec<String>), usize>,
 	pub rulesets: Vec<asm::Ruleset>,
 	pub active_rulesets: Vec<RulesetRef>,
 	pub functions: Vec<asm::Function>,
+	pub macros: Vec<asm::Macro>,
+	pub structs: Vec<asm::Struct>,
+	pub checksums: Vec<asm::Checksum>,
+	pub macro_expansions: usize,
+	pub expanding_macros: Vec<usize>,
+	pub iterations: HashMap<String, asm::Iteration>,
+	pub conditions: Vec<asm::Condition>,
+	pub listing: Vec<asm::ListingEntry>,
+	pub constant_decls: Vec<asm::ConstantDecl>,
+	pub object_mode: bool,
+	pub include_paths: Vec<String>,
+	pub imports: std::cell::RefCell<Vec<(String, diagn::Span)>>,
+	pub import_reads: std::cell::Cell<usize>,
+	pub read_files: std::cell::RefCell<Vec<String>>,
 	pub cur_bank: BankRef,
 	pub cur_wordsize: usize,
 	pub cur_labelalign: usize,
@@ -37,6 +60,7 @@ pub struct Context
 	pub bank_ref: BankRef,
 	pub symbol_ctx: asm::SymbolContext,
 	pub cur_filename: std::rc::Rc<String>,
+	pub locals: std::rc::Rc<Vec<(String, util::BigInt)>>,
 }
 
 
@@ -77,6 +101,9 @@ impl Assembler
 		Assembler
 		{
 			root_files: Vec::new(),
+			defined_symbols: Vec::new(),
+			include_paths: Vec::new(),
+			object_mode: false,
 			state: State::new(),
 		}
 	}
@@ -90,6 +117,23 @@ impl Assembler
 	}
 	
 	
+	pub fn define_symbol<S: Into<String>>(
+        &mut self,
+        name: S,
+        value: expr::Value)
+	{
+		self.defined_symbols.push((name.into(), value));
+	}
+	
+	
+	pub fn add_include_path<S: Into<String>>(
+        &mut self,
+        path: S)
+	{
+		self.include_paths.push(path.into());
+	}
+	
+	
 	pub fn assemble(
         mut self,
         report: diagn::RcReport,
@@ -98,13 +142,15 @@ impl Assembler
         -> Result<AssemblyOutput, ()>
 	{
 		let mut symbol_guesses = asm::SymbolManager::new();
-		let mut instruction_size_guesses = HashMap::<diagn::Span, usize>::new();
+		let mut instruction_size_guesses = HashMap::<(diagn::Span, Vec<String>), usize>::new();
 
 		let mut iteration = 0;
 		loop
 		{
 			self.state = State::new();
 			self.state.is_first_pass = iteration == 0;
+			self.state.object_mode = self.object_mode;
+			self.state.include_paths = self.include_paths.clone();
 			std::mem::swap(&mut self.state.symbol_guesses, &mut symbol_guesses);
 			std::mem::swap(&mut self.state.instruction_size_guesses, &mut instruction_size_guesses);
 
@@ -117,6 +163,31 @@ impl Assembler
 
 			let pass_report = diagn::RcReport::new();
 
+			for (name, value) in &self.defined_symbols
+			{
+				let ctx = self.state.symbols.get_ctx();
+
+				let result = self.state.symbols.create(
+					&ctx,
+					name.clone(),
+					0,
+					asm::SymbolKind::Constant,
+					value.clone(),
+					self.state.cur_bank,
+					pass_report.clone(),
+					&diagn::Span::new_dummy());
+
+				// Predefined symbols must not become the parent
+				// of local labels declared at the top of the source.
+				self.state.symbols.set_ctx(ctx);
+
+				if result.is_err()
+				{
+					pass_report.transfer_to(report);
+					return Err(());
+				}
+			}
+
 			for filename in &self.root_files
 			{
 				let result = asm::parser::parse_file(
@@ -138,46 +209,20 @@ impl Assembler
 			//dbg!(&self.state.symbols);
 			//dbg!(pass_report.has_errors());
 
-			let mut full_output = util::BitVec::new();
-			let mut all_bankdata_resolved = true;
+			let output = self.state.resolve_output(
+				pass_report.clone(),
+				fileserver,
+				&mut instruction_size_guesses);
 
-			for bank_index in 0..self.state.banks.len()
+			if let Ok(mut full_output) = output
 			{
-				let bank = &self.state.banks[bank_index];
-				let bankdata = &self.state.bankdata[bank_index];
-
-				let bank_output = self.state.resolve_bankdata(
+				let checksums = self.state.write_checksums(
 					pass_report.clone(),
-					bank,
-					bankdata,
 					fileserver,
-					&mut instruction_size_guesses);
-					
-				if pass_report.has_errors() || !bank_output.is_ok()
-				{
-					all_bankdata_resolved = false;
-					break;
-				}
-
-				if let Some(output_offset) = bank.output_offset
-				{
-					//println!("output {:?}, {:x}", bank.output_offset, &bank_output.as_ref().unwrap());
-
-					full_output.write_bitvec(
-						output_offset,
-						&bank_output.unwrap());
-				}
-				else
-				{
-					full_output.mark_spans_from(
-						0,
-						&bank_output.unwrap());
-				}
-			}
+					&mut full_output);
 
-			if all_bankdata_resolved
-			{
 				pass_report.transfer_to(report);
+				checksums?;
 
 				return Ok(AssemblyOutput
 				{
@@ -214,6 +259,20 @@ impl State
 			rulesets: Vec::new(),
 			active_rulesets: Vec::new(),
 			functions: Vec::new(),
+			macros: Vec::new(),
+			structs: Vec::new(),
+			checksums: Vec::new(),
+			macro_expansions: 0,
+			expanding_macros: Vec::new(),
+			iterations: HashMap::new(),
+			conditions: Vec::new(),
+			listing: Vec::new(),
+			constant_decls: Vec::new(),
+			object_mode: false,
+			include_paths: Vec::new(),
+			imports: std::cell::RefCell::new(Vec::new()),
+			import_reads: std::cell::Cell::new(0),
+			read_files: std::cell::RefCell::new(Vec::new()),
 			cur_bank: BankRef { index: 0 },
 			cur_wordsize: 8,
 			cur_labelalign: 0,
@@ -232,6 +291,7 @@ impl State
 		let bank_ref = self.cur_bank;
 		let symbol_ctx = self.symbols.get_ctx();
 		let cur_filename = state.filename.clone();
+		let locals = state.locals.clone();
 
 		Context
 		{
@@ -240,6 +300,7 @@ impl State
 			bank_ref,
 			symbol_ctx,
 			cur_filename,
+			locals,
 		}
 	}
 	
@@ -305,26 +366,7 @@ impl State
 			{
 				let other_bank = &self.banks[j];
 
-				if other_bank.output_offset.is_none()
-					{ continue; }
-
-				// FIXME: multiplication by wordsize can overflow
-				let outp1 = bank.output_offset.unwrap();
-				let outp2 = other_bank.output_offset.unwrap();
-
-				// FIXME: multiplication by wordsize can overflow
-				let size1 = bank.addr_size.map(|s| s * bank.wordsize);
-				let size2 = other_bank.addr_size.map(|s| s * other_bank.wordsize);
-
-				let overlap = match (size1, size2)
-				{
-					(None, None) => true,
-					(Some(size1), None) => outp1 + size1 > outp2,
-					(None, Some(size2)) => outp2 + size2 > outp1,
-					(Some(size1), Some(size2)) => outp1 + size1 > outp2 && outp2 + size2 > outp1,
-				};
-
-				if overlap
+				if bank.output_overlaps(other_bank)
 				{
 					report.error_span(
 						format!(
@@ -350,12 +392,25 @@ impl State
 			bank_ref,
 			cur_bit_offset: 0,
 			invocations: Vec::new(),
+			alignment: 1,
 		});
 
 		Ok(())
 	}
 
 
+	/// Records a file read while assembling. Each pass starts
+	/// with a new state, so only the final pass's files remain.
+	pub fn track_read_file(&self, filename: &str)
+	{
+		let mut read_files = self.read_files.borrow_mut();
+		if !read_files.iter().any(|f| f == filename)
+		{
+			read_files.push(filename.to_string());
+		}
+	}
+
+
 	pub fn find_bank<TName: std::borrow::Borrow<str>>(
 		&self,
 		name: TName,
@@ -435,19 +490,70 @@ impl State
 	}
 
 
+	pub fn resolve_output(
+		&self,
+		report: diagn::RcReport,
+		fileserver: &dyn util::FileServer,
+		instruction_size_guesses: &mut HashMap<(diagn::Span, Vec<String>), usize>)
+		-> Result<util::BitVec, ()>
+	{
+		let mut full_output = util::BitVec::new();
+
+		for bank_index in 0..self.banks.len()
+		{
+			let bank = &self.banks[bank_index];
+			let bankdata = &self.bankdata[bank_index];
+
+			let bank_output = self.resolve_bankdata(
+				report.clone(),
+				bank,
+				bankdata,
+				fileserver,
+				instruction_size_guesses);
+				
+			if report.has_errors() || !bank_output.is_ok()
+			{
+				return Err(());
+			}
+
+			if let Some(output_offset) = bank.output_offset
+			{
+				full_output.write_bitvec(
+					output_offset,
+					&bank_output.unwrap());
+			}
+			else
+			{
+				full_output.mark_spans_from(
+					0,
+					&bank_output.unwrap());
+			}
+		}
+
+		self.resolve_conditions(
+			report,
+			fileserver)?;
+
+		Ok(full_output)
+	}
+
+
 	pub fn resolve_bankdata(
 		&self,
 		report: diagn::RcReport,
 		bank: &asm::Bank,
 		bankdata: &asm::BankData,
 		fileserver: &dyn util::FileServer,
-		instruction_size_guesses: &mut HashMap<diagn::Span, usize>)
+		instruction_size_guesses: &mut HashMap<(diagn::Span, Vec<String>), usize>)
 		-> Result<util::BitVec, ()>
 	{
 		let mut bitvec = util::BitVec::new();
 
 		for invoc in &bankdata.invocations
 		{
+			let _iteration_guard = self.get_iteration(&invoc.ctx)
+				.map(|iteration| report.push_parent(iteration.descr.clone(), &iteration.span));
+
 			let maybe_resolved = match invoc.kind
 			{
 				asm::InvocationKind::Rule(_) =>
@@ -524,7 +630,7 @@ impl State
 							// couldn't be inferred.
 							if invoc.size_guess == 0
 							{
-								instruction_size_guesses.insert(invoc.span.clone(), size);
+								instruction_size_guesses.insert(invoc.size_guess_key(), size);
 							}
 							
 							if size == invoc.size_guess
@@ -601,6 +707,51 @@ impl State
 	}
 
 
+	pub fn resolve_conditions(
+		&self,
+		report: diagn::RcReport,
+		fileserver: &dyn util::FileServer)
+		-> Result<(), ()>
+	{
+		for condition in &self.conditions
+		{
+			let value = self.eval_expr(
+				report.clone(),
+				&condition.expr,
+				&condition.ctx,
+				&mut expr::EvalContext::new(),
+				fileserver,
+				true)?;
+
+			match value
+			{
+				expr::Value::Bool(value) =>
+				{
+					if value != condition.value
+					{
+						report.error_span(
+							"condition did not converge after iterations",
+							&condition.expr.span());
+
+						return Err(());
+					}
+				}
+
+				_ =>
+				{
+					report.error_span(
+						"expected boolean condition",
+						&condition.expr.span());
+
+					return Err(());
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+
 	pub fn resolve_data_invocation(
 		&self,
 		report: diagn::RcReport,
@@ -692,6 +843,7 @@ impl State
 		for candidate in candidates
 		{
 			let candidate_report = diagn::RcReport::new();
+			let import_reads = self.import_reads.get();
 
 			if DEBUG_CANDIDATE_RESOLUTION
 			{
@@ -718,7 +870,8 @@ impl State
 						println!("  ok");
 					}
 
-					successful_candidates.push((candidate, resolved, candidate_report));
+					let uses_imports = self.import_reads.get() != import_reads;
+					successful_candidates.push((candidate, resolved, candidate_report, uses_imports));
 				}
 				Err(()) =>
 				{
@@ -727,15 +880,25 @@ impl State
 			}
 		}
 
-		// Retain only the candidates which produced the fewest bits
-		let mut smallest_output = usize::MAX;
+		// Retain only the candidates which produced the fewest bits.
+		// Imports read as zero until the link step, so when they were
+		// used, keep the most bits instead, which leaves room for
+		// whatever value they end up with.
+		let uses_imports = successful_candidates.iter().any(|c| c.3);
+
+		let mut chosen_output = None;
 		for c in &successful_candidates
 		{
 			if let expr::Value::Integer(ref bigint) = c.1
 			{
 				if let Some(size) = bigint.size
 				{
-					smallest_output = std::cmp::min(smallest_output, size);
+					chosen_output = match chosen_output
+					{
+						None => Some(size),
+						Some(chosen) if uses_imports => Some(std::cmp::max(chosen, size)),
+						Some(chosen) => Some(std::cmp::min(chosen, size)),
+					};
 				}
 			}
 		}
@@ -746,7 +909,7 @@ impl State
 			{
 				if let Some(size) = bigint.size
 				{
-					return size == smallest_output;
+					return Some(size) == chosen_output;
 				}
 			}
 
@@ -1018,6 +1181,12 @@ impl State
 	{
 		if info.hierarchy_level == 0 && info.hierarchy.len() == 1
 		{
+			// Values bound by `#for`, innermost first
+			if let Some((_, value)) = ctx.locals.iter().rev().find(|l| l.0 == info.hierarchy[0])
+			{
+				return Ok(expr::Value::make_integer(value.clone()));
+			}
+
 			match info.hierarchy[0].as_ref()
 			{
 				"$" | "pc" =>
@@ -1032,21 +1201,6 @@ impl State
 					};
 				}
 
-				"assert" |
-				"incbin" |
-				"incbinstr" |
-				"inchexstr" |
-				"le" |
-				"utf8" |
-				"utf16be" |
-				"utf16le" |
-				"utf32be" |
-				"utf32le" |
-				"ascii" =>
-				{
-					return Ok(expr::Value::BuiltInFunction(info.hierarchy[0].clone()));
-				}
-
 				_ => {}
 			}
 			
@@ -1060,27 +1214,91 @@ impl State
 
 		if let Some(symbol) = self.symbols.get(&ctx.symbol_ctx, info.hierarchy_level, info.hierarchy)
 		{
-			Ok(symbol.value.clone())
+			return Ok(symbol.value.clone());
 		}
-		else if !final_pass
+
+		if !final_pass
 		{
 			if let Some(symbol) = self.symbol_guesses.get(&ctx.symbol_ctx, info.hierarchy_level, info.hierarchy)
 			{
-				Ok(symbol.value.clone())
-			}
-			else if self.is_first_pass
-			{
-				Ok(expr::Value::Unknown)
+				return Ok(symbol.value.clone());
 			}
-			else
+		}
+
+		// Built-in functions come after user functions and
+		// symbols, so that those can reuse their names
+		if info.hierarchy_level == 0 && info.hierarchy.len() == 1
+		{
+			match info.hierarchy[0].as_ref()
 			{
-				Err(false)
+				"assert" |
+				"incbin" |
+				"incbinstr" |
+				"inchexstr" |
+				"le" |
+				"utf8" |
+				"utf16be" |
+				"utf16le" |
+				"utf32be" |
+				"utf32le" |
+				"ascii" |
+				"strlen" |
+				"substr" |
+				"upper" |
+				"lower" |
+				"chr" |
+				"ord" |
+				"fmt" |
+				"clog2" |
+				"min" |
+				"max" |
+				"abs" |
+				"popcount" |
+				"bitrev" |
+				"sext" |
+				"zext" |
+				"bswap" =>
+				{
+					return Ok(expr::Value::BuiltInFunction(info.hierarchy[0].clone()));
+				}
+
+				_ => {}
 			}
 		}
+
+		if !final_pass && self.is_first_pass
+		{
+			Ok(expr::Value::Unknown)
+		}
 		else
 		{
-			Err(false)
+			self.eval_import(info).ok_or(false)
+		}
+	}
+
+
+	/// When assembling an object file, unknown global symbols are
+	/// left for the link step to resolve, and are assumed to be
+	/// zero until then.
+	fn eval_import(
+		&self,
+		info: &expr::EvalVariableInfo)
+		-> Option<expr::Value>
+	{
+		if !self.object_mode || info.hierarchy_level != 0
+		{
+			return None;
+		}
+
+		let mut imports = self.imports.borrow_mut();
+		if !imports.iter().any(|i| i.0 == info.hierarchy[0])
+		{
+			imports.push((info.hierarchy[0].clone(), info.span.clone()));
 		}
+
+		self.import_reads.set(self.import_reads.get() + 1);
+
+		Some(expr::Value::make_integer(0))
 	}
 
 
@@ -1154,6 +1372,63 @@ impl State
 	}
 
 
+	fn eval_fn_get_usize_arg(
+		info: &expr::EvalFunctionInfo,
+		index: usize)
+		-> Result<usize, ()>
+	{
+		match State::eval_fn_get_bigint_arg(info, index)?.checked_to_usize()
+		{
+			Some(value) => Ok(value),
+			None =>
+			{
+				info.report.error_span("value is outside of valid range", &info.arg_spans[index]);
+				Err(())
+			}
+		}
+	}
+
+
+	fn eval_fn_get_size_arg(
+		info: &expr::EvalFunctionInfo,
+		index: usize)
+		-> Result<usize, ()>
+	{
+		let size = State::eval_fn_get_usize_arg(info, index)?;
+		if size == 0 || size > MAX_FUNCTION_SIZE_ARG
+		{
+			info.report.error_span(
+				format!("size (= {}) is outside the range of 1 to {} bits", size, MAX_FUNCTION_SIZE_ARG),
+				&info.arg_spans[index]);
+			return Err(());
+		}
+
+		Ok(size)
+	}
+
+
+	/// Gets an integer and the number of bits to operate on, taken
+	/// from the second argument if given or else from the integer's size.
+	fn eval_fn_get_bigint_and_size_args(
+		info: &expr::EvalFunctionInfo)
+		-> Result<(util::BigInt, usize), ()>
+	{
+		if info.args.len() == 2
+		{
+			let bigint = State::eval_fn_get_bigint_arg(info, 0)?;
+			let size = State::eval_fn_get_size_arg(info, 1)?;
+			Ok((bigint, size))
+		}
+		else
+		{
+			State::eval_fn_check_arg_number(info, 1)?;
+			let bigint = State::eval_fn_get_sized_bigint_arg(info, 0)?;
+			let size = bigint.size.unwrap();
+			Ok((bigint, size))
+		}
+	}
+
+
 	fn eval_fn_get_bigint_arg(
 		info: &expr::EvalFunctionInfo,
 		index: usize)
@@ -1199,6 +1474,154 @@ impl State
 	}
 
 
+	/// Formats the arguments after the first into the first, replacing
+	/// each `{}` in turn. A placeholder can give a width, a `0` to pad
+	/// numbers with zeros instead of spaces, and one of `x`, `X`, `b`,
+	/// `o` or `d` for the radix, as in `{:04x}`.
+	fn eval_fn_fmt(
+		info: &expr::EvalFunctionInfo)
+		-> Result<expr::Value, ()>
+	{
+		if info.args.len() == 0
+		{
+			info.report.error_span("wrong number of arguments", info.span);
+			return Err(());
+		}
+
+		let format = &State::eval_fn_get_string_arg(info, 0)?.utf8_contents;
+
+		let mut result = String::new();
+		let mut arg_index = 1;
+		let mut chars = format.chars();
+
+		while let Some(c) = chars.next()
+		{
+			if c == '}'
+			{
+				if chars.next() != Some('}')
+				{
+					info.report.error_span("invalid format string", &info.arg_spans[0]);
+					return Err(());
+				}
+
+				result.push('}');
+				continue;
+			}
+
+			if c != '{'
+			{
+				result.push(c);
+				continue;
+			}
+
+			let mut spec = String::new();
+			loop
+			{
+				match chars.next()
+				{
+					Some('{') if spec.len() == 0 =>
+					{
+						result.push('{');
+						break;
+					}
+
+					Some('}') =>
+					{
+						if arg_index >= info.args.len()
+						{
+							info.report.error_span("missing argument for format string", info.span);
+							return Err(());
+						}
+
+						let formatted = State::eval_fn_fmt_arg(info, arg_index, &spec)?;
+						result.push_str(&formatted);
+						arg_index += 1;
+						break;
+					}
+
+					Some(c) => spec.push(c),
+
+					None =>
+					{
+						info.report.error_span("invalid format string", &info.arg_spans[0]);
+						return Err(());
+					}
+				}
+			}
+		}
+
+		if arg_index < info.args.len()
+		{
+			info.report.error_span("too many arguments for format string", &info.arg_spans[arg_index]);
+			return Err(());
+		}
+
+		Ok(expr::Value::make_string(result, "utf8"))
+	}
+
+
+	fn eval_fn_fmt_arg(
+		info: &expr::EvalFunctionInfo,
+		index: usize,
+		spec: &str)
+		-> Result<String, ()>
+	{
+		let options = if spec.len() == 0
+			{ "" }
+		else if spec.starts_with(':')
+			{ &spec[1..] }
+		else
+		{
+			info.report.error_span(format!("invalid format placeholder `{{{}}}`", spec), &info.arg_spans[0]);
+			return Err(());
+		};
+
+		let zero_pad = options.starts_with('0');
+		let width_len = options.chars().take_while(|c| c.is_ascii_digit()).count();
+		let width = options[..width_len].parse::<usize>().unwrap_or(0);
+
+		let radix = match &options[width_len..]
+		{
+			"" | "d" => 10,
+			"x" | "X" => 16,
+			"b" => 2,
+			"o" => 8,
+			_ =>
+			{
+				info.report.error_span(format!("invalid format placeholder `{{{}}}`", spec), &info.arg_spans[0]);
+				return Err(());
+			}
+		};
+
+		let (sign, mut digits) = match &info.args[index]
+		{
+			expr::Value::String(s) if radix == 10 => ("", s.utf8_contents.clone()),
+			expr::Value::Bool(b) if radix == 10 => ("", format!("{}", b)),
+			_ =>
+			{
+				let bigint = State::eval_fn_get_bigint_arg(info, index)?;
+
+				if bigint.sign() < 0
+					{ ("-", (-&bigint).to_str_radix(radix)) }
+				else
+					{ ("", bigint.to_str_radix(radix)) }
+			}
+		};
+
+		if options.ends_with('X')
+		{
+			digits = digits.to_uppercase();
+		}
+
+		let padding = width.saturating_sub(sign.len() + digits.chars().count());
+
+		if zero_pad
+			{ Ok(format!("{}{}{}", sign, "0".repeat(padding), digits)) }
+		else
+			{ Ok(format!("{}{}{}", " ".repeat(padding), sign, digits)) }
+	}
+
+
 	fn eval_fn(
 		&self,
 		ctx: &Context,
@@ -1214,10 +1637,22 @@ impl State
 				{
 					"assert" =>
 					{
-						State::eval_fn_check_arg_number(info, 1)?;
+						if info.args.len() != 2
+						{
+							State::eval_fn_check_arg_number(info, 1)?;
+						}
+
 						match State::eval_fn_get_bool_arg(info, 0)?
 						{
 							true => Ok(expr::Value::Void),
+							false if info.args.len() == 2 =>
+							{
+								let message = State::eval_fn_get_string_arg(info, 1)?;
+								info.report.error_span(
+									format!("assertion failed: {}", message.utf8_contents),
+									info.span);
+								Err(())
+							}
 							false =>
 							{
 								info.report.error_span("assertion failed", info.span);
@@ -1243,6 +1678,132 @@ impl State
 						Ok(expr::Value::make_integer(bigint.convert_le()))
 					}
 
+					"bswap" =>
+					{
+						let (bigint, size) = State::eval_fn_get_bigint_and_size_args(info)?;
+
+						if size % 8 != 0
+						{
+							info.report.error_span(
+								format!("argument size (= {}) is not a multiple of 8", size),
+								info.span);
+							return Err(());
+						}
+
+						Ok(expr::Value::make_integer(bigint.slice(size, 0).convert_le()))
+					}
+
+					"bitrev" =>
+					{
+						let (bigint, size) = State::eval_fn_get_bigint_and_size_args(info)?;
+						Ok(expr::Value::make_integer(bigint.reverse_bits(size)))
+					}
+
+					"sext" |
+					"zext" =>
+					{
+						State::eval_fn_check_arg_number(info, 2)?;
+						let bigint = State::eval_fn_get_bigint_arg(info, 0)?;
+						let size = State::eval_fn_get_size_arg(info, 1)?;
+						let from = bigint.size.unwrap_or(size);
+
+						if from > size
+						{
+							info.report.error_span(
+								format!("argument size (= {}) is larger than the extended size (= {})", from, size),
+								info.span);
+							return Err(());
+						}
+
+						let mut result = match name.as_ref()
+						{
+							"sext" => bigint.sign_extend(from, size),
+							"zext" => bigint.slice(from, 0),
+							_ => unreachable!(),
+						};
+
+						result.size = Some(size);
+						Ok(expr::Value::make_integer(result))
+					}
+
+					"clog2" =>
+					{
+						State::eval_fn_check_arg_number(info, 1)?;
+						let bigint = State::eval_fn_get_bigint_arg(info, 0)?;
+
+						if bigint.sign() < 0
+						{
+							info.report.error_span("negative argument", &info.arg_spans[0]);
+							return Err(());
+						}
+
+						if bigint <= util::BigInt::from(1)
+							{ return Ok(expr::Value::make_integer(0)); }
+
+						let below = &bigint - &util::BigInt::from(1);
+						Ok(expr::Value::make_integer(below.min_size()))
+					}
+
+					"min" |
+					"max" =>
+					{
+						if info.args.len() == 0
+						{
+							info.report.error_span("wrong number of arguments", info.span);
+							return Err(());
+						}
+
+						let mut result = State::eval_fn_get_bigint_arg(info, 0)?;
+						for index in 1..info.args.len()
+						{
+							let bigint = State::eval_fn_get_bigint_arg(info, index)?;
+
+							let replace = match name.as_ref()
+							{
+								"min" => bigint < result,
+								"max" => bigint > result,
+								_ => unreachable!(),
+							};
+
+							if replace
+								{ result = bigint; }
+						}
+
+						Ok(expr::Value::make_integer(result))
+					}
+
+					"abs" =>
+					{
+						State::eval_fn_check_arg_number(info, 1)?;
+						let bigint = State::eval_fn_get_bigint_arg(info, 0)?;
+
+						if bigint.sign() < 0
+							{ Ok(expr::Value::make_integer(-&bigint)) }
+						else
+							{ Ok(expr::Value::make_integer(bigint)) }
+					}
+
+					"popcount" =>
+					{
+						State::eval_fn_check_arg_number(info, 1)?;
+						let bigint = State::eval_fn_get_bigint_arg(info, 0)?;
+
+						// Negative values only have a
+						// finite number of ones when sized
+						let bits = match bigint.size
+						{
+							Some(size) => bigint.slice(size, 0),
+							None if bigint.sign() < 0 =>
+							{
+								info.report.error_span("unsized negative argument", &info.arg_spans[0]);
+								return Err(());
+							}
+							None => bigint,
+						};
+
+						Ok(expr::Value::make_integer(bits.count_ones()))
+					}
+
 					"incbin" |
 					"incbinstr" |
 					"inchexstr" =>
@@ -1254,12 +1815,16 @@ impl State
 						}
 
 						let value_string = State::eval_fn_get_string_arg(info, 0)?;
-						let new_filename = util::filename_navigate(
+						let new_filename = util::filename_search(
 							info.report.clone(),
+							fileserver,
+							&self.include_paths,
 							&ctx.cur_filename,
 							&value_string.utf8_contents,
 							&info.span)?;
 
+						self.track_read_file(&new_filename);
+
 						match name.as_ref()
 						{
 							"incbin" =>
@@ -1336,6 +1901,86 @@ impl State
 						Ok(expr::Value::make_string(&s.utf8_contents, name))
 					}
 
+					"strlen" =>
+					{
+						State::eval_fn_check_arg_number(info, 1)?;
+						let s = State::eval_fn_get_string_arg(info, 0)?;
+						Ok(expr::Value::make_integer(s.utf8_contents.chars().count()))
+					}
+
+					"substr" =>
+					{
+						State::eval_fn_check_arg_number(info, 3)?;
+						let s = State::eval_fn_get_string_arg(info, 0)?;
+						let start = State::eval_fn_get_usize_arg(info, 1)?;
+						let len = State::eval_fn_get_usize_arg(info, 2)?;
+
+						if start.checked_add(len).map_or(true, |end| end > s.utf8_contents.chars().count())
+						{
+							info.report.error_span("substring out of range", info.span);
+							return Err(());
+						}
+
+						let contents: String = s.utf8_contents.chars().skip(start).take(len).collect();
+						Ok(expr::Value::make_string(contents, &s.encoding))
+					}
+
+					"upper" |
+					"lower" =>
+					{
+						State::eval_fn_check_arg_number(info, 1)?;
+						let s = State::eval_fn_get_string_arg(info, 0)?;
+
+						let contents = match name.as_ref()
+						{
+							"upper" => s.utf8_contents.to_uppercase(),
+							"lower" => s.utf8_contents.to_lowercase(),
+							_ => unreachable!(),
+						};
+
+						Ok(expr::Value::make_string(contents, &s.encoding))
+					}
+
+					"chr" =>
+					{
+						State::eval_fn_check_arg_number(info, 1)?;
+						let code = State::eval_fn_get_bigint_arg(info, 0)?;
+
+						let c = code
+							.checked_to_usize()
+							.filter(|c| *c <= u32::MAX as usize)
+							.and_then(|c| std::char::from_u32(c as u32));
+
+						match c
+						{
+							Some(c) => Ok(expr::Value::make_string(c.to_string(), "utf8")),
+							None =>
+							{
+								info.report.error_span("invalid character code", &info.arg_spans[0]);
+								Err(())
+							}
+						}
+					}
+
+					"ord" =>
+					{
+						State::eval_fn_check_arg_number(info, 1)?;
+						let s = State::eval_fn_get_string_arg(info, 0)?;
+
+						let mut chars = s.utf8_contents.chars();
+						match (chars.next(), chars.next())
+						{
+							(Some(c), None) => Ok(expr::Value::make_integer(c as u32)),
+							_ =>
+							{
+								info.report.error_span("expected a string of one character", &info.arg_spans[0]);
+								Err(())
+							}
+						}
+					}
+
+					"fmt" => State::eval_fn_fmt(info),
+
 					_ => unreachable!()
 				}
 			}
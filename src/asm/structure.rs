@@ -0,0 +1,115 @@
+use crate::*;
+
+
+/// A layout declared with `#struct`. Offsets and
+/// sizes are given in words of the `#bits` in effect
+/// where the struct was declared, kept in `wordsize`.
+#[derive(Debug)]
+pub struct Struct
+{
+    pub decl_span: diagn::Span,
+    pub name: String,
+    pub fields: Vec<StructField>,
+    pub size: usize,
+    pub wordsize: usize,
+}
+
+
+#[derive(Debug)]
+pub struct StructField
+{
+    pub decl_span: diagn::Span,
+    pub name: String,
+    pub offset: usize,
+    pub size: usize,
+    pub struct_index: Option<usize>,
+}
+
+
+impl asm::State
+{
+    pub fn find_struct(&self, name: &str) -> Option<usize>
+    {
+        self.structs.iter().position(|s| s.name == name)
+    }
+
+
+    /// Reports an error if the struct was declared under
+    /// a different `#bits` than the current one, since its
+    /// offsets and size would then be counted in other words.
+    pub fn check_struct_wordsize(
+        &self,
+        struct_index: usize,
+        report: diagn::RcReport,
+        span: &diagn::Span)
+        -> Result<(), ()>
+    {
+        let structure = &self.structs[struct_index];
+        if structure.wordsize != self.cur_wordsize
+        {
+            let _guard = report.push_parent(
+                format!(
+                    "struct `{}` was declared with `#bits {}`, but the current `#bits` is {}",
+                    structure.name,
+                    structure.wordsize,
+                    self.cur_wordsize),
+                span);
+
+            report.note_span("declared here", &structure.decl_span);
+            return Err(());
+        }
+
+        Ok(())
+    }
+
+
+    /// Creates a symbol for every field under the given hierarchy,
+    /// valued at `base` plus the field's offset, and recurses into
+    /// fields that are themselves structs.
+    pub fn create_struct_field_symbols(
+        &mut self,
+        hierarchy: &mut Vec<String>,
+        struct_index: usize,
+        base: &util::BigInt,
+        kind: asm::SymbolKind,
+        instance_span: Option<&diagn::Span>,
+        report: diagn::RcReport)
+        -> Result<(), ()>
+    {
+        for field_index in 0..self.structs[struct_index].fields.len()
+        {
+            let field = &self.structs[struct_index].fields[field_index];
+            let name = field.name.clone();
+            let value = base + &util::BigInt::from(field.offset);
+            let inner_struct_index = field.struct_index;
+            let span = instance_span.unwrap_or(&field.decl_span).clone();
+
+            self.symbols.create(
+                &asm::SymbolContext::new(hierarchy.clone()),
+                name.clone(),
+                hierarchy.len(),
+                kind,
+                expr::Value::make_integer(value.clone()),
+                self.cur_bank,
+                report.clone(),
+                &span)?;
+
+            if let Some(inner_struct_index) = inner_struct_index
+            {
+                hierarchy.push(name);
+
+                self.create_struct_field_symbols(
+                    hierarchy,
+                    inner_struct_index,
+                    &value,
+                    kind,
+                    instance_span,
+                    report.clone())?;
+
+                hierarchy.pop();
+            }
+        }
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -21,7 +21,7 @@ pub struct Symbol
 }
 
 
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug)]
 pub enum SymbolKind
 {
     Label,
@@ -36,6 +36,35 @@ pub struct SymbolContext
 }
 
 
+/// The expression a constant was declared with, kept
+/// so that it can be evaluated again at link time.
+#[derive(Debug)]
+pub struct ConstantDecl
+{
+    pub decl_span: diagn::Span,
+    pub ctx: asm::Context,
+    pub expr: expr::Expr,
+}
+
+
+impl SymbolContext
+{
+    pub fn new(hierarchy: Vec<String>) -> SymbolContext
+    {
+        SymbolContext
+        {
+            hierarchy,
+        }
+    }
+
+
+    pub fn hierarchy(&self) -> &[String]
+    {
+        &self.hierarchy
+    }
+}
+
+
 impl SymbolManager
 {
     pub fn new() -> SymbolManager
@@ -168,6 +197,44 @@ impl SymbolManager
     }
 
 
+    pub fn set_ctx(&mut self, ctx: SymbolContext)
+    {
+        self.cur_ctx = ctx;
+    }
+
+
+    /// Creates a global label that becomes the parent of the
+    /// local labels declared next, so that they can't clash with
+    /// the ones in the enclosing scope. Returns the context to
+    /// restore once the scope ends.
+    ///
+    /// The name must be one accepted by `is_scope_name`, so that
+    /// the scope is left out of symbol files and listings.
+    pub fn enter_scope(
+        &mut self,
+        name: String,
+        value: expr::Value,
+        bankref: asm::BankRef,
+        report: diagn::RcReport,
+        span: &diagn::Span)
+        -> Result<SymbolContext, ()>
+    {
+        let prev_ctx = self.cur_ctx.clone();
+
+        self.create(
+            &SymbolContext::new(Vec::new()),
+            name,
+            0,
+            SymbolKind::Label,
+            value,
+            bankref,
+            report,
+            span)?;
+
+        Ok(prev_ctx)
+    }
+
+
     pub fn create(
         &mut self,
         ctx: &SymbolContext,
@@ -194,7 +261,17 @@ impl SymbolManager
         if let Some(duplicate) = parent.get(&name)
         {
             let _guard = report.push_parent("duplicate symbol", span);
-            report.note_span("first declared here", &duplicate.decl_span);
+
+            // Only symbols given with `-D` have no place in the source
+            if duplicate.decl_span.location.is_none()
+            {
+                report.note(format!("first declared on the command line with `-D {}`", name));
+            }
+            else
+            {
+                report.note_span("first declared here", &duplicate.decl_span);
+            }
+
             return Err(());
         }
 
@@ -224,8 +301,12 @@ impl SymbolManager
     }
 
 
-    pub fn format_mesen_mlb(&self, state: &asm::State) -> String
+    /// Formats labels for the Mesen debugger, with ROM
+    /// addresses given relative to the end of the iNES header.
+    pub fn format_mesen_mlb(&self, state: &asm::State, binary: &[u8]) -> String
 	{
+        let header_size = get_ines_header_size(binary);
+
         self.format(&mut |result: &mut String, symbol: &Symbol, name: &str, bigint: &util::BigInt|
         {
             if let SymbolKind::Constant = symbol.kind
@@ -234,19 +315,19 @@ impl SymbolManager
             }
 
             let bank = &state.banks[symbol.bankref.index];
-            if let Some(output_offset) = bank.output_offset
+            if bank.output_offset.is_some()
             {
-                if let Some(addr) = bigint.checked_to_usize()
+                let prg_offset = bank
+                    .get_output_byte_offset(bigint)
+                    .and_then(|offset| offset.checked_sub(header_size));
+
+                if let Some(prg_offset) = prg_offset
                 {
-                    if let Some(addr_start) = bank.addr_start.checked_to_usize()
-                    {
-                        let prg_offset = addr - addr_start + output_offset / 8 - 0x10;
-                        result.push_str("P:");
-                        result.push_str(&format!("{:x}", prg_offset));
-                        result.push_str(":");
-                        result.push_str(&name.replace(".", "_"));
-                        result.push_str("\n");
-                    }
+                    result.push_str("P:");
+                    result.push_str(&format!("{:x}", prg_offset));
+                    result.push_str(":");
+                    result.push_str(&name.replace(".", "_"));
+                    result.push_str("\n");
                 }
             }
             else
@@ -261,6 +342,150 @@ impl SymbolManager
     }
 
 
+    /// Formats labels as `bank:addr name` lines, with the
+    /// bank numbered by its position in the output.
+    pub fn format_sym(&self, state: &asm::State) -> String
+	{
+        self.format(&mut |result: &mut String, symbol: &Symbol, name: &str, bigint: &util::BigInt|
+        {
+            if let SymbolKind::Constant = symbol.kind
+            {
+                return;
+            }
+
+            let bank_number = state.banks[symbol.bankref.index].get_bank_number();
+
+            result.push_str(&format!("{:02x}:{:04x} {}\n", bank_number, bigint, name));
+        })
+    }
+
+
+    /// Formats labels as VICE monitor `al` commands.
+    pub fn format_vice(&self) -> String
+	{
+        self.format(&mut |result: &mut String, symbol: &Symbol, name: &str, bigint: &util::BigInt|
+        {
+            if let SymbolKind::Constant = symbol.kind
+            {
+                return;
+            }
+
+            let name: String = name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+
+            result.push_str(&format!("al C:{:04x} .{}\n", bigint, name));
+        })
+    }
+
+
+    /// Formats labels like the RGBDS linker does, with the
+    /// bank numbered by its position in the output.
+    pub fn format_rgbds(&self, state: &asm::State) -> String
+	{
+        let mut result = "; File generated by customasm\n".to_string();
+
+        result.push_str(&self.format(&mut |result: &mut String, symbol: &Symbol, name: &str, bigint: &util::BigInt|
+        {
+            if let SymbolKind::Constant = symbol.kind
+            {
+                return;
+            }
+
+            let bank_number = state.banks[symbol.bankref.index].get_bank_number();
+
+            let addr = format!("{:04x}", bigint).to_uppercase();
+            result.push_str(&format!("{:02X}:{} {}\n", bank_number, addr, name));
+        }));
+
+        result
+    }
+
+
+    /// Creates an ELF file holding only a symbol table,
+    /// with every label as an absolute symbol.
+    pub fn format_elf(&self, state: &asm::State) -> Vec<u8>
+	{
+        let mut elf = util::ElfWriter::new();
+        self.add_elf_symbols(state, &mut elf, &vec![None; state.banks.len()]);
+        elf.build()
+    }
+
+
+    /// Adds every label to the symbol table of an ELF file, at
+    /// its byte address. Labels in a bank that was given a section
+    /// are bound to it, and the others become absolute symbols.
+    pub fn add_elf_symbols(
+        &self,
+        state: &asm::State,
+        elf: &mut util::ElfWriter,
+        bank_sections: &[Option<usize>])
+	{
+        self.format(&mut |_, symbol: &Symbol, name: &str, bigint: &util::BigInt|
+        {
+            if let SymbolKind::Constant = symbol.kind
+            {
+                return;
+            }
+
+            let bank = &state.banks[symbol.bankref.index];
+            let value = bigint
+                .checked_to_usize()
+                .and_then(|addr| addr.checked_mul(bank.wordsize))
+                .map(|bits| bits / 8)
+                .filter(|value| *value <= u32::MAX as usize);
+
+            if let Some(value) = value
+            {
+                elf.symbols.push(util::ElfSymbol
+                {
+                    name: name.to_string(),
+                    value: value as u32,
+                    is_local: name.contains('.'),
+                    is_object: false,
+                    section: bank_sections[symbol.bankref.index],
+                });
+            }
+        });
+    }
+
+
+    /// Calls the visitor for every symbol, with parents
+    /// before their children and siblings sorted by name.
+    pub fn visit<FnVisit>(
+        &self,
+        visitor: &mut FnVisit)
+        where FnVisit: FnMut(&[String], &Symbol)
+    {
+        SymbolManager::visit_recursive(&self.globals, &mut Vec::new(), visitor);
+    }
+
+
+    fn visit_recursive<FnVisit>(
+        symbols: &HashMap<String, Symbol>,
+        hierarchy: &mut Vec<String>,
+        visitor: &mut FnVisit)
+        where FnVisit: FnMut(&[String], &Symbol)
+    {
+        let mut names: Vec<&String> = symbols.keys().collect();
+        names.sort();
+
+        for name in names
+        {
+            let symbol = &symbols[name];
+
+            hierarchy.push(name.clone());
+            visitor(hierarchy, symbol);
+            SymbolManager::visit_recursive(&symbol.children, hierarchy, visitor);
+            hierarchy.pop();
+        }
+    }
+
+
+    /// Calls the formatter for every symbol with an integer
+    /// value, except for macro expansion and loop iteration
+    /// scopes and the labels inside them.
     pub fn format<FnFormat>(
         &self,
         formatter: &mut FnFormat)
@@ -269,8 +494,17 @@ impl SymbolManager
 	{
 		let mut result = String::new();
 
-		for (name, data) in &self.globals
+        let mut names: Vec<&String> = self.globals.keys().collect();
+        names.sort();
+
+		for name in names
 		{
+            if is_scope_name(name)
+            {
+                continue;
+            }
+
+            let data = &self.globals[name];
             self.format_recursive(
                 &mut result,
                 &mut vec![name.clone()],
@@ -311,8 +545,12 @@ impl SymbolManager
             _ => {}
         }
 
-        for (child_name, child_data) in &data.children
+        let mut child_names: Vec<&String> = data.children.keys().collect();
+        child_names.sort();
+
+        for child_name in child_names
         {
+            let child_data = &data.children[child_name];
             hierarchy.push(child_name.clone());
 
             self.format_recursive(
@@ -324,4 +562,29 @@ impl SymbolManager
             hierarchy.pop();
         }
     }
+}
+
+
+/// Whether the name is one of the scopes created for each
+/// macro expansion or loop iteration. These contain a `#`,
+/// which can't appear in names declared in the source.
+pub fn is_scope_name(name: &str) -> bool
+{
+    name.contains('#')
+}
+
+
+/// Returns the size of the iNES header at the start of the
+/// output, including the trainer, or zero if there is none.
+fn get_ines_header_size(binary: &[u8]) -> usize
+{
+    if binary.len() < 16 || &binary[0..4] != b"NES\x1a"
+    {
+        return 0;
+    }
+
+    if binary[6] & 0x04 != 0
+        { 16 + 512 }
+    else
+        { 16 }
 }
\ No newline at end of file
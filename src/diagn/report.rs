@@ -270,6 +270,12 @@ impl Report
 	}
 	
 	
+	pub fn messages(&self) -> &[Message]
+	{
+		&self.messages
+	}
+	
+	
 	pub fn len(&self) -> usize
 	{
 		self.messages.len()
@@ -384,6 +390,74 @@ impl Report
 	}
 	
 	
+	pub fn print_all_json(&self, writer: &mut dyn Write, fileserver: &dyn FileServer)
+	{
+		write!(writer, "[").unwrap();
+
+		for (i, msg) in self.messages.iter().enumerate()
+		{
+			if i > 0
+				{ write!(writer, ",").unwrap(); }
+
+			self.print_msg_json(writer, fileserver, msg);
+		}
+
+		writeln!(writer, "]").unwrap();
+	}
+	
+	
+	fn print_msg_json(&self, writer: &mut dyn Write, fileserver: &dyn FileServer, msg: &Message)
+	{
+		write!(writer, "{{").unwrap();
+		write!(writer, "\"kind\":\"{}\"", msg.kind.get_label()).unwrap();
+		write!(writer, ",\"descr\":\"{}\"", escape_json_string(&msg.descr)).unwrap();
+
+		match msg.span
+		{
+			None => write!(writer, ",\"file\":null,\"range\":null").unwrap(),
+			Some(ref span) =>
+			{
+				write!(writer, ",\"file\":\"{}\"", escape_json_string(&span.file)).unwrap();
+
+				let chars = match span.location
+				{
+					Some(_) => fileserver.get_chars(RcReport::new(), &span.file, None).ok(),
+					None => None,
+				};
+
+				match (span.location, chars)
+				{
+					(Some((start, end)), Some(chars)) =>
+					{
+						let counter = CharCounter::new(&chars);
+						
+						let (line1, col1) = counter.get_line_column_at_index(start);
+						let (line2, col2) = counter.get_line_column_at_index(end);
+
+						write!(writer,
+							",\"range\":{{\"start\":{{\"line\":{},\"column\":{}}},\"end\":{{\"line\":{},\"column\":{}}}}}",
+							line1 + 1, col1 + 1, line2 + 1, col2 + 1).unwrap();
+					}
+
+					_ => write!(writer, ",\"range\":null").unwrap(),
+				}
+			}
+		}
+
+		write!(writer, ",\"inner\":[").unwrap();
+
+		for (i, inner) in msg.inner.iter().enumerate()
+		{
+			if i > 0
+				{ write!(writer, ",").unwrap(); }
+
+			self.print_msg_json(writer, fileserver, inner);
+		}
+
+		write!(writer, "]}}").unwrap();
+	}
+	
+	
 	fn print_msg(&self, writer: &mut dyn Write, fileserver: &dyn FileServer, msg: &Message, indent: usize)
 	{
 		let kind_label = msg.kind.get_label();
@@ -453,7 +527,18 @@ impl Report
 		let (start, end) = spans[0].location.unwrap();
 
 		// Print location information.
-		let chars = fileserver.get_chars(RcReport::new(), &spans[0].file, None).ok().unwrap();
+		// The source may no longer be available, e.g.
+		// when reporting errors from an object file.
+		let chars = match fileserver.get_chars(RcReport::new(), &spans[0].file, None)
+		{
+			Ok(chars) => chars,
+			Err(()) =>
+			{
+				writeln!(writer).unwrap();
+				return;
+			}
+		};
+
 		let counter = CharCounter::new(&chars);
 		
 		let (line1, col1) = counter.get_line_column_at_index(start);
@@ -682,6 +767,12 @@ impl RcReport
 	{
 		self.report.borrow_mut().print_all(writer, fileserver);
 	}
+
+	
+	pub fn print_all_json(&self, writer: &mut dyn Write, fileserver: &dyn FileServer)
+	{
+		self.report.borrow_mut().print_all_json(writer, fileserver);
+	}
 }
 
 
@@ -716,4 +807,26 @@ impl MessageKind
 			&MessageKind::Note => C_NOTE,
 		}
 	}
+}
+
+
+fn escape_json_string(s: &str) -> String
+{
+	let mut result = String::new();
+
+	for c in s.chars()
+	{
+		match c
+		{
+			'"' => result.push_str("\\\""),
+			'\\' => result.push_str("\\\\"),
+			'\n' => result.push_str("\\n"),
+			'\r' => result.push_str("\\r"),
+			'\t' => result.push_str("\\t"),
+			c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+			c => result.push(c),
+		}
+	}
+
+	result
 }
\ No newline at end of file
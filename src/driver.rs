@@ -13,6 +13,11 @@ enum OutputFormat
 	HexDump,
 	Mif,
 	IntelHex,
+	Coe,
+	ReadMemH,
+	ReadMemB,
+	SRecord,
+	TiTxt,
 	DecComma,
 	HexComma,
 	DecC,
@@ -20,6 +25,9 @@ enum OutputFormat
 	LogiSim8,
 	LogiSim16,
 	AddressSpan,
+	Listing,
+	Object,
+	Elf,
 }
 
 
@@ -27,33 +35,134 @@ enum SymbolFormat
 {
 	Default,
 	MesenMlb,
+	Sym,
+	Vice,
+	Rgbds,
+	Elf,
+}
+
+
+enum DiagnosticsFormat
+{
+	Human,
+	Json,
 }
 
 
 pub fn drive(args: &Vec<String>, fileserver: &mut dyn util::FileServer) -> Result<(), ()>
 {
-	let opts = make_opts();
+	drive_mode(args, fileserver, false)
+}
+
+
+pub fn drive_link(args: &Vec<String>, fileserver: &mut dyn util::FileServer) -> Result<(), ()>
+{
+	drive_mode(args, fileserver, true)
+}
+
+
+fn drive_mode(args: &Vec<String>, fileserver: &mut dyn util::FileServer, link: bool) -> Result<(), ()>
+{
+	let mut std_fileserver = util::FileServerStd::new(fileserver);
+	let fileserver = &mut std_fileserver;
 	
-	let report = diagn::RcReport::new();
+	let opts = make_opts(link);
+	
+	let (watch, quiet) = match opts.parse(&args[1..])
+	{
+		Ok(matches) => (
+			matches.opt_present("watch") && !matches.opt_present("h") && !matches.opt_present("v"),
+			matches.opt_present("q")),
+		Err(_) => (false, false),
+	};
+	
+	if !watch
+	{
+		return drive_once(&opts, args, fileserver, link).map_err(|_| ());
+	}
 	
-	let result = drive_inner(report.clone(), &opts, args, fileserver);
+	loop
+	{
+		let mut tracked_fileserver = util::FileServerTracked::new(fileserver);
+		let result = drive_once(&opts, args, &mut tracked_fileserver, link);
+		let read_files = tracked_fileserver.into_read_files();
+		
+		if let Err(true) = result
+			{ return Err(()); }
+		
+		if !quiet
+		{
+			println!("");
+			println!("watching {} file{} for changes...",
+				read_files.len(),
+				if read_files.len() == 1 { "" } else { "s" });
+		}
+		
+		wait_for_changes(fileserver, &read_files);
+		
+		if !quiet
+			{ println!(""); }
+	}
+}
+
+
+fn drive_once(
+	opts: &getopts::Options,
+	args: &Vec<String>,
+	fileserver: &mut dyn util::FileServer,
+	link: bool)
+	-> Result<(), bool>
+{
+	let report = diagn::RcReport::new();
 	
-	if report.has_messages()
-		{ println!(""); }
+	let result = drive_inner(report.clone(), &opts, args, fileserver, link);
 	
-	util::enable_windows_ansi_support();
-	report.print_all(&mut std::io::stderr(), fileserver);
+	match get_diagnostics_format(&opts, args)
+	{
+		DiagnosticsFormat::Human =>
+		{
+			if report.has_messages()
+				{ println!(""); }
+			
+			util::enable_windows_ansi_support();
+			report.print_all(&mut std::io::stderr(), fileserver);
+		}
+
+		DiagnosticsFormat::Json =>
+		{
+			report.print_all_json(&mut std::io::stderr(), fileserver);
+		}
+	}
 	
 	if let Err(show_usage) = result
 	{
 		if show_usage
 		{
 			print_version_short();
-			print_usage(&opts);
+			print_usage(&opts, link);
 		}
 	}
 	
-	result.map_err(|_| ())
+	result
+}
+
+
+/// Polls the files read during the last assembly until
+/// one of them is modified, created, or removed.
+fn wait_for_changes(
+	fileserver: &dyn util::FileServer,
+	read_files: &[(String, Option<std::time::SystemTime>)])
+{
+	loop
+	{
+		std::thread::sleep(std::time::Duration::from_millis(250));
+		
+		for (filename, modified_time) in read_files
+		{
+			if fileserver.get_modified_time(filename) != *modified_time
+				{ return; }
+		}
+	}
 }
 
 
@@ -61,7 +170,8 @@ fn drive_inner(
 	report: diagn::RcReport,
 	opts: &getopts::Options,
 	args: &Vec<String>,
-	fileserver: &mut dyn util::FileServer)
+	fileserver: &mut dyn util::FileServer,
+	link: bool)
 	-> Result<(), bool>
 {
 	let matches = parse_opts(report.clone(), opts, args).map_err(|_| true)?;
@@ -69,7 +179,7 @@ fn drive_inner(
 	if matches.opt_present("h")
 	{
 		print_version_full();
-		print_usage(&opts);
+		print_usage(&opts, link);
 		return Ok(());
 	}
 	
@@ -95,6 +205,11 @@ fn drive_inner(
 		Some("binary")    => OutputFormat::Binary,
 		Some("mif")       => OutputFormat::Mif,
 		Some("intelhex")  => OutputFormat::IntelHex,
+		Some("coe")       => OutputFormat::Coe,
+		Some("readmemh")  => OutputFormat::ReadMemH,
+		Some("readmemb")  => OutputFormat::ReadMemB,
+		Some("srec")      => OutputFormat::SRecord,
+		Some("titxt")     => OutputFormat::TiTxt,
 		Some("deccomma")  => OutputFormat::DecComma,
 		Some("hexcomma")  => OutputFormat::HexComma,
 		Some("decc")      => OutputFormat::DecC,
@@ -103,6 +218,9 @@ fn drive_inner(
 		Some("logisim8")  => OutputFormat::LogiSim8,
 		Some("logisim16") => OutputFormat::LogiSim16,
 		Some("addrspan")  => OutputFormat::AddressSpan,
+		Some("listing")   => OutputFormat::Listing,
+		Some("elf")       => OutputFormat::Elf,
+		Some("object") if !link => OutputFormat::Object,
 		
 		None => if out_stdout
 			{ OutputFormat::AnnotatedHex }
@@ -116,11 +234,25 @@ fn drive_inner(
 		}
 	};
 
+	match matches.opt_str("diagnostics-format").as_ref().map(|s| s.as_ref())
+	{
+		None | Some("human") | Some("json") => {}
+		_ =>
+		{
+			report.error("invalid diagnostics format");
+			return Err(true);
+		}
+	}
+	
 	let symbol_format = match matches.opt_str("symbol-format").as_ref().map(|s| s.as_ref())
 	{
 		None |
 		Some("default")   => SymbolFormat::Default,
 		Some("mesen-mlb") => SymbolFormat::MesenMlb,
+		Some("sym")       => SymbolFormat::Sym,
+		Some("vice")      => SymbolFormat::Vice,
+		Some("rgbds")     => SymbolFormat::Rgbds,
+		Some("elf")       => SymbolFormat::Elf,
 		Some(_) =>
 		{
 			report.error("invalid symbol format");
@@ -128,6 +260,23 @@ fn drive_inner(
 		}
 	};
 	
+	let split_banks = matches.opt_present("split-banks");
+	if split_banks
+	{
+		match out_format
+		{
+			OutputFormat::Listing |
+			OutputFormat::Elf |
+			OutputFormat::Object =>
+			{
+				report.error("output format cannot be used with `--split-banks`");
+				return Err(true);
+			}
+			
+			_ => {}
+		}
+	}
+	
 	if matches.free.len() < 1
 	{
 		report.error("no input files");
@@ -135,11 +284,16 @@ fn drive_inner(
 	}
 	
 	let main_asm_file = matches.free[0].clone();
+	let is_object = matches!(out_format, OutputFormat::Object);
 	
 	let output_symbol_requested = matches.opt_present("s");
 	let output_requested = matches.opt_present("o");
 
 	let output_symbol_file = matches.opt_str("s");
+	let output_file_requested = matches.opt_str("o");
+	let memory_map_requested = matches.opt_present("memory-map");
+	let memory_map_file = matches.opt_str("memory-map");
+	let depfile = matches.opt_str("depfile");
 	let output_file = match matches.opt_str("o")
 	{
 		Some(f) => Some(f),
@@ -149,7 +303,13 @@ fn drive_inner(
 				{ None }
 			else
 			{
-				match get_default_output_filename(report.clone(), &main_asm_file)
+				let extension = match out_format
+				{
+					OutputFormat::Object => "o",
+					OutputFormat::Elf => "elf",
+					_ => "bin",
+				};
+				match get_default_output_filename(report.clone(), &main_asm_file, extension)
 				{
 					Ok(f) => Some(f),
 					Err(_) => None,
@@ -158,79 +318,184 @@ fn drive_inner(
 		}
 	};
 
-	let max_iterations = match matches.opt_str("t")
+	let output = if link
 	{
-		None => 10,
-		Some(t) =>
+		if !quiet
+			{ print_version_short(); }
+
+		link_objects(report.clone(), matches.free, fileserver, quiet)?
+	}
+	else
+	{
+		let max_iterations = match matches.opt_str("t")
 		{
-			match t.parse::<usize>()
+			None => 10,
+			Some(t) =>
 			{
-				Ok(t) => t,
-				Err(_) =>
+				match t.parse::<usize>()
 				{
-					report.error("invalid number of iterations");
-					return Err(true);
+					Ok(t) => t,
+					Err(_) =>
+					{
+						report.error("invalid number of iterations");
+						return Err(true);
+					}
 				}
 			}
-		}
-	};
-	
-	if !quiet
-		{ print_version_short(); }
-	
-	let mut assembler = asm::Assembler::new();
-	for filename in matches.free
-	{
+		};
+		
+		let disassemble_file = matches.opt_str("disassemble");
+		let disassemble_bank = matches.opt_str("bank");
+		
+		let defines = parse_defines(report.clone(), &matches.opt_strs("D")).map_err(|_| true)?;
+		
 		if !quiet
+			{ print_version_short(); }
+		
+		let mut assembler = asm::Assembler::new();
+		for (name, value) in defines
 		{
-			println!("assembling `{}`...", filename);
+			assembler.define_symbol(name, expr::Value::make_integer(value));
 		}
 		
-		assembler.register_file(filename);
-	}
+		for include_path in matches.opt_strs("I")
+		{
+			assembler.add_include_path(include_path);
+		}
+		
+		// The library path is searched after any `-I` paths
+		if let Some(library_path) = std::env::var_os("CUSTOMASM_PATH")
+		{
+			for include_path in std::env::split_paths(&library_path)
+			{
+				if include_path.as_os_str().len() > 0
+					{ assembler.add_include_path(include_path.to_string_lossy().into_owned()); }
+			}
+		}
+		
+		for filename in matches.free
+		{
+			if !quiet
+			{
+				println!("assembling `{}`...", filename);
+			}
+			
+			assembler.register_file(filename);
+		}
+
+		assembler.object_mode = is_object;
+
+		let output = assembler.assemble(
+			report.clone(),
+			fileserver,
+			max_iterations)
+			.map_err(|_| false)?;
+
+		if let Some(disassemble_file) = disassemble_file
+		{
+			let bytes = fileserver.get_bytes(report.clone(), &disassemble_file, None).map_err(|_| false)?;
+			output.state.track_read_file(&disassemble_file);
 
-	let output = assembler.assemble(
-		report.clone(),
-		fileserver,
-		max_iterations)
-		.map_err(|_| false)?;
+			let mut binary = util::BitVec::new();
+			for (i, byte) in bytes.iter().enumerate()
+			{
+				for bit in 0..8
+					{ binary.write(i * 8 + bit, byte & (0x80 >> bit) != 0); }
+			}
+
+			let disassembly = output.state.disassemble(
+				report.clone(),
+				fileserver,
+				&binary,
+				disassemble_bank.as_deref())
+				.map_err(|_| false)?;
+
+			match output_file_requested
+			{
+				None => println!("{}", disassembly),
+				Some(output_file) =>
+				{
+					if !quiet
+						{ println!("writing `{}`...", &output_file); }
+
+					fileserver.write_bytes(report.clone(), &output_file, &disassembly.bytes().collect(), None).map_err(|_| false)?;
+				}
+			}
+
+			return Ok(());
+		}
+
+		output
+	};
 
 	let binary = output.binary;
 
-	let output_symbol_data = if output_symbol_file.is_none()
+	let output_symbol_data: Option<Vec<u8>> = if output_symbol_file.is_none()
 	{
 		None
 	}
 	else
 	{
+		let symbols = &output.state.symbols;
+
 		Some(match symbol_format
 		{
-			SymbolFormat::Default  => output.state.symbols.format_default(),
-			SymbolFormat::MesenMlb => output.state.symbols.format_mesen_mlb(&output.state),
+			SymbolFormat::Default  => symbols.format_default().bytes().collect(),
+			SymbolFormat::MesenMlb => symbols.format_mesen_mlb(&output.state, &binary.format_binary()).bytes().collect(),
+			SymbolFormat::Sym      => symbols.format_sym(&output.state).bytes().collect(),
+			SymbolFormat::Vice     => symbols.format_vice().bytes().collect(),
+			SymbolFormat::Rgbds    => symbols.format_rgbds(&output.state).bytes().collect(),
+			SymbolFormat::Elf      => symbols.format_elf(&output.state),
 		})
 	};
 
-	let output_data: Vec<u8> = match out_format
+	let memory_map = if memory_map_requested
+		{ Some(output.state.format_memory_map()) }
+	else
+		{ None };
+
+	let mut outputs: Vec<(Option<String>, Vec<u8>)> = Vec::new();
+	if split_banks
 	{
-		OutputFormat::Binary    => binary.format_binary(),
-		
-		OutputFormat::BinStr    => binary.format_binstr  ()  .bytes().collect(),
-		OutputFormat::HexStr    => binary.format_hexstr  ()  .bytes().collect(),
-		OutputFormat::BinDump   => binary.format_bindump ()  .bytes().collect(),
-		OutputFormat::HexDump   => binary.format_hexdump ()  .bytes().collect(),
-		OutputFormat::Mif       => binary.format_mif     ()  .bytes().collect(),
-		OutputFormat::IntelHex  => binary.format_intelhex()  .bytes().collect(),
-		OutputFormat::DecComma  => binary.format_comma   (10).bytes().collect(),
-		OutputFormat::HexComma  => binary.format_comma   (16).bytes().collect(),
-		OutputFormat::DecC      => binary.format_c_array (10).bytes().collect(),
-		OutputFormat::HexC      => binary.format_c_array (16).bytes().collect(),
-		OutputFormat::LogiSim8  => binary.format_logisim (8) .bytes().collect(),
-		OutputFormat::LogiSim16 => binary.format_logisim (16).bytes().collect(),
+		for (index, bank) in output.state.banks.iter().enumerate()
+		{
+			let bank_ref = asm::BankRef { index };
+			
+			match output.state.get_bank_bitvec(bank_ref, &binary)
+			{
+				Some(ref bank_binary) if bank_binary.len() > 0 => {}
+				_ => continue,
+			}
+			
+			let bank_output_file = output_file
+				.as_ref()
+				.map(|f| get_bank_output_filename(f, &bank.name));
+			
+			let bank_output_data = format_output(
+				report.clone(),
+				&out_format,
+				&output.state,
+				&binary,
+				Some(bank_ref),
+				fileserver)?;
+			
+			outputs.push((bank_output_file, bank_output_data));
+		}
+	}
+	else
+	{
+		let output_data = format_output(
+			report.clone(),
+			&out_format,
+			&output.state,
+			&binary,
+			None,
+			fileserver)?;
 		
-		OutputFormat::AnnotatedHex => binary.format_annotated_hex(fileserver).bytes().collect(),
-		OutputFormat::AnnotatedBin => binary.format_annotated_bin(fileserver).bytes().collect(),
-		OutputFormat::AddressSpan  => binary.format_addrspan     (fileserver).bytes().collect(),
-	};
+		outputs.push((output_file.clone(), output_data));
+	}
+	
+	let mut written_files = Vec::new();
 	
 	if out_stdout
 	{
@@ -243,23 +508,38 @@ fn drive_inner(
 		}
 		
 		if output_requested || output_file.is_some()
-			{ println!("{}", String::from_utf8_lossy(&output_data)); }
+		{
+			for (bank_output_file, output_data) in &outputs
+			{
+				if split_banks
+				{
+					if let Some(ref bank_output_file) = bank_output_file
+						{ println!("{}:", bank_output_file); }
+				}
+				
+				println!("{}", String::from_utf8_lossy(&output_data));
+			}
+		}
 			
 		if output_symbol_requested || output_symbol_file.is_some()
 		{
 			if let Some(output_symbol_data) = output_symbol_data
-				{ println!("{}", &output_symbol_data); }
+				{ println!("{}", String::from_utf8_lossy(&output_symbol_data)); }
 		}
+		
+		if let Some(ref memory_map) = memory_map
+			{ println!("{}", memory_map); }
 	}
 	else
 	{
-		let mut any_files_written = false;
-
-		if let Some(ref output_file) = output_file
+		for (output_file, output_data) in &outputs
 		{
-			println!("writing `{}`...", &output_file);
-			fileserver.write_bytes(report.clone(), &output_file, &output_data, None).map_err(|_| false)?;
-			any_files_written = true;
+			if let Some(ref output_file) = output_file
+			{
+				println!("writing `{}`...", &output_file);
+				fileserver.write_bytes(report.clone(), &output_file, &output_data, None).map_err(|_| false)?;
+				written_files.push(output_file.clone());
+			}
 		}
 
 		if let Some(output_symbol_data) = output_symbol_data
@@ -267,12 +547,56 @@ fn drive_inner(
 			if let Some(ref output_symbol_file) = output_symbol_file
 			{
 				println!("writing `{}`...", &output_symbol_file);
-				fileserver.write_bytes(report.clone(), &output_symbol_file, &output_symbol_data.bytes().collect::<Vec<u8>>(), None).map_err(|_| false)?;
-				any_files_written = true;
+				fileserver.write_bytes(report.clone(), &output_symbol_file, &output_symbol_data, None).map_err(|_| false)?;
+				written_files.push(output_symbol_file.clone());
 			}
 		}
 
-		if !any_files_written
+		if let Some(ref memory_map) = memory_map
+		{
+			match memory_map_file
+			{
+				Some(ref memory_map_file) =>
+				{
+					println!("writing `{}`...", &memory_map_file);
+					fileserver.write_bytes(report.clone(), &memory_map_file, &memory_map.bytes().collect(), None).map_err(|_| false)?;
+					written_files.push(memory_map_file.clone());
+				}
+				
+				None => print!("{}", memory_map),
+			}
+		}
+	}
+	
+	if let Some(ref depfile) = depfile
+	{
+		// With nothing else written, the depfile
+		// is its own target
+		let targets = if written_files.len() == 0
+			{ vec![depfile.clone()] }
+		else
+			{ written_files.clone() };
+		
+		// Standard library files aren't on disk
+		let prerequisites: Vec<String> = output.state.read_files
+			.borrow()
+			.iter()
+			.filter(|f| !util::is_std_filename(f))
+			.cloned()
+			.collect();
+		
+		let depfile_data = format_depfile(&targets, &prerequisites);
+		
+		if !out_stdout
+			{ println!("writing `{}`...", &depfile); }
+		
+		fileserver.write_bytes(report.clone(), &depfile, &depfile_data.bytes().collect(), None).map_err(|_| false)?;
+		written_files.push(depfile.clone());
+	}
+	
+	if !out_stdout
+	{
+		if written_files.len() == 0
 			{ println!("no files written"); }
 
 		if !quiet
@@ -287,14 +611,143 @@ fn drive_inner(
 }
 
 
-fn make_opts() -> getopts::Options
+/// Formats a Make rule with every file that was
+/// read as a prerequisite of the given targets.
+fn format_depfile(targets: &[String], prerequisites: &[String]) -> String
+{
+	let mut result = String::new();
+	
+	let targets: Vec<String> = targets
+		.iter()
+		.map(|t| escape_make_path(t))
+		.collect();
+	
+	result.push_str(&targets.join(" "));
+	result.push(':');
+	
+	for prerequisite in prerequisites
+	{
+		result.push_str(" \\\n  ");
+		result.push_str(&escape_make_path(prerequisite));
+	}
+	
+	result.push('\n');
+	result
+}
+
+
+fn escape_make_path(path: &str) -> String
 {
+	let mut result = String::new();
+	
+	for c in path.chars()
+	{
+		match c
+		{
+			' ' | '#' | ':' => { result.push('\\'); result.push(c); }
+			'$' => result.push_str("$$"),
+			_ => result.push(c),
+		}
+	}
+	
+	result
+}
+
+
+fn format_output(
+	report: diagn::RcReport,
+	out_format: &OutputFormat,
+	state: &asm::State,
+	full_binary: &util::BitVec,
+	bank_ref: Option<asm::BankRef>,
+	fileserver: &dyn util::FileServer)
+	-> Result<Vec<u8>, bool>
+{
+	// Word-based formats use the wordsize and start address
+	// of the bank being written. The whole output starts
+	// at zero, since it can hold any number of banks.
+	let bank_binary;
+	let (binary, wordsize, word_addr) = match bank_ref
+	{
+		None => (full_binary, state.get_output_wordsize(), 0),
+		Some(bank_ref) =>
+		{
+			let bank = &state.banks[bank_ref.index];
+			let word_addr = bank.addr_start.checked_to_usize().unwrap_or(0);
+			
+			bank_binary = state.get_bank_bitvec(bank_ref, full_binary).unwrap();
+			(&bank_binary, bank.wordsize, word_addr)
+		}
+	};
+	
+	let byte_addr = word_addr * wordsize / 8;
+	
+	Ok(match out_format
+	{
+		OutputFormat::Binary    => binary.format_binary(),
+		
+		OutputFormat::BinStr    => binary.format_binstr  ()  .bytes().collect(),
+		OutputFormat::HexStr    => binary.format_hexstr  ()  .bytes().collect(),
+		OutputFormat::BinDump   => binary.format_bindump ()  .bytes().collect(),
+		OutputFormat::HexDump   => binary.format_hexdump ()  .bytes().collect(),
+		OutputFormat::Mif       => binary.format_mif     (wordsize, word_addr).bytes().collect(),
+		OutputFormat::IntelHex  => binary.format_intelhex(byte_addr).bytes().collect(),
+		OutputFormat::Coe       => binary.format_coe     (wordsize).bytes().collect(),
+		OutputFormat::ReadMemH  => binary.format_readmem (wordsize, 16, word_addr).bytes().collect(),
+		OutputFormat::ReadMemB  => binary.format_readmem (wordsize, 2,  word_addr).bytes().collect(),
+		OutputFormat::DecComma  => binary.format_comma   (10).bytes().collect(),
+		OutputFormat::HexComma  => binary.format_comma   (16).bytes().collect(),
+		OutputFormat::DecC      => binary.format_c_array (10).bytes().collect(),
+		OutputFormat::HexC      => binary.format_c_array (16).bytes().collect(),
+		OutputFormat::LogiSim8  => binary.format_logisim (8) .bytes().collect(),
+		OutputFormat::LogiSim16 => binary.format_logisim (16).bytes().collect(),
+		
+		OutputFormat::AnnotatedHex => binary.format_annotated_hex(fileserver).bytes().collect(),
+		OutputFormat::AnnotatedBin => binary.format_annotated_bin(fileserver).bytes().collect(),
+		OutputFormat::AddressSpan  => binary.format_addrspan     (fileserver).bytes().collect(),
+		
+		OutputFormat::SRecord => state.format_srec(full_binary, bank_ref).bytes().collect(),
+		OutputFormat::TiTxt   => state.format_titxt(full_binary, bank_ref).bytes().collect(),
+
+		OutputFormat::Listing => state.format_listing(full_binary, fileserver).bytes().collect(),
+
+		OutputFormat::Elf => state.format_elf(full_binary),
+
+		OutputFormat::Object => state.make_object(report.clone(), fileserver)
+			.map_err(|_| false)?
+			.serialize()
+			.bytes()
+			.collect(),
+	})
+}
+
+
+fn make_opts(link: bool) -> getopts::Options
+{
+    let formats = "binary, annotated, annotatedbin, binstr, hexstr, bindump, hexdump, mif, intelhex, coe, readmemh, readmemb, srec, titxt, deccomma, hexcomma, decc, hexc, logisim8, logisim16, addrspan, listing, elf";
+
     let mut opts = getopts::Options::new();
-    opts.optopt("f", "format", "The format of the output file. Possible formats: binary, annotated, annotatedbin, binstr, hexstr, bindump, hexdump, mif, intelhex, deccomma, hexcomma, decc, hexc, logisim8, logisim16, addrspan", "FORMAT");
+    if link
+        { opts.optopt("f", "format", &format!("The format of the output file. Possible formats: {}", formats), "FORMAT"); }
+    else
+        { opts.optopt("f", "format", &format!("The format of the output file. Possible formats: {}, object", formats), "FORMAT"); }
+
     opts.opt("o", "output", "The name of the output file.", "FILE", getopts::HasArg::Maybe, getopts::Occur::Optional);
-    opts.optopt("", "symbol-format", "The format of the symbol file. Possible formats: default, mesen-mlb", "SYMBOL-FORMAT");
+    opts.optopt("", "symbol-format", "The format of the symbol file. Possible formats: default, mesen-mlb, sym, vice, rgbds, elf", "SYMBOL-FORMAT");
     opts.opt("s", "symbol", "The name of the output symbol file.", "FILE", getopts::HasArg::Maybe, getopts::Occur::Optional);
-    opts.opt("t", "iter", "The max number of passes the assembler will attempt (default: 10).", "NUM", getopts::HasArg::Maybe, getopts::Occur::Optional);
+    opts.opt("", "memory-map", "Report the space used in each bank, written to the given file or printed.", "FILE", getopts::HasArg::Maybe, getopts::Occur::Optional);
+    opts.optopt("", "depfile", "Write a Make dependency file listing the input files of the output.", "FILE");
+    opts.optopt("", "diagnostics-format", "The format of error and warning messages. Possible formats: human, json", "FORMAT");
+    if !link
+    {
+        opts.optopt("", "disassemble", "Disassemble a binary file using the rules declared in the input files.", "FILE");
+        opts.optopt("", "bank", "The bank whose word size and start address are used by `--disassemble`.", "NAME");
+        opts.optmulti("I", "include-path", "Add a directory to search for included files that aren't found relative to the including file. Searched before the directories in `CUSTOMASM_PATH`.", "DIR");
+        opts.optmulti("D", "define", "Define a constant symbol. The value defaults to 1 if omitted.", "NAME[=VALUE]");
+        opts.opt("t", "iter", "The max number of passes the assembler will attempt (default: 10).", "NUM", getopts::HasArg::Maybe, getopts::Occur::Optional);
+    }
+    opts.optflag("", "split-banks", "Write the output of each bank to its own file, named after the bank.");
+    opts.optflag("w", "watch", "Assemble again whenever one of the files read changes.");
     opts.optflag("p", "print", "Print output to stdout instead of writing to a file.");
     opts.optflag("q", "quiet", "Suppress progress reports.");
     opts.optflag("v", "version", "Display version information.");
@@ -304,6 +757,20 @@ fn make_opts() -> getopts::Options
 }
 
 
+fn get_diagnostics_format(opts: &getopts::Options, args: &Vec<String>) -> DiagnosticsFormat
+{
+	let format = opts.parse(&args[1..])
+		.ok()
+		.and_then(|m| m.opt_str("diagnostics-format"));
+
+	match format.as_ref().map(|s| s.as_ref())
+	{
+		Some("json") => DiagnosticsFormat::Json,
+		_ => DiagnosticsFormat::Human,
+	}
+}
+
+
 fn parse_opts(report: diagn::RcReport, opts: &getopts::Options, args: &Vec<String>) -> Result<getopts::Matches, ()>
 {
 	match opts.parse(&args[1..])
@@ -314,10 +781,124 @@ fn parse_opts(report: diagn::RcReport, opts: &getopts::Options, args: &Vec<Strin
 }
 
 
-fn print_usage(opts: &getopts::Options)
+fn parse_defines(report: diagn::RcReport, defines: &[String]) -> Result<Vec<(String, util::BigInt)>, ()>
+{
+	let mut result: Vec<(String, util::BigInt)> = Vec::new();
+	
+	for define in defines
+	{
+		let (name, value_str) = match define.find('=')
+		{
+			Some(index) => (&define[..index], Some(&define[(index + 1)..])),
+			None => (&define[..], None),
+		};
+		
+		let valid_name = name.len() > 0 &&
+			!name.starts_with(|c: char| c.is_ascii_digit()) &&
+			name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+		
+		if !valid_name
+		{
+			report.error(format!("invalid symbol name in definition `{}`", define));
+			return Err(());
+		}
+		
+		if result.iter().any(|d| d.0 == name)
+		{
+			report.error(format!("duplicate definition of symbol `{}`", name));
+			return Err(());
+		}
+		
+		let value = match value_str
+		{
+			None => util::BigInt::from(1),
+			Some(value_str) =>
+			{
+				let value_str = value_str.trim();
+				
+				let (negative, value_str) = match value_str.strip_prefix('-')
+				{
+					Some(rest) => (true, rest.trim_start()),
+					None => (false, value_str),
+				};
+				
+				let value = if value_str.starts_with(|c: char| c.is_ascii_digit())
+					{ syntax::excerpt_as_bigint(None, value_str, &diagn::Span::new_dummy()).ok() }
+				else
+					{ None };
+				
+				let value = match (negative, value)
+				{
+					(true, Some(value)) => Some(-&value),
+					(_, value) => value,
+				};
+				
+				match value
+				{
+					Some(value) => value,
+					None =>
+					{
+						report.error(format!("invalid value in definition `{}`", define));
+						return Err(());
+					}
+				}
+			}
+		};
+		
+		result.push((name.to_string(), value));
+	}
+	
+	Ok(result)
+}
+
+
+fn print_usage(opts: &getopts::Options, link: bool)
 {
 	println!("");
-	println!("{}", opts.usage(&format!("Usage: {} [options] <asm-file-1> ... <asm-file-N>", env!("CARGO_PKG_NAME"))));
+
+	if link
+		{ println!("{}", opts.usage(&format!("Usage: {}-link [options] <object-file-1> ... <object-file-N>", env!("CARGO_PKG_NAME")))); }
+	else
+		{ println!("{}", opts.usage(&format!("Usage: {} [options] <asm-file-1> ... <asm-file-N>", env!("CARGO_PKG_NAME")))); }
+}
+
+
+fn link_objects(
+	report: diagn::RcReport,
+	filenames: Vec<String>,
+	fileserver: &dyn util::FileServer,
+	quiet: bool)
+	-> Result<asm::AssemblyOutput, bool>
+{
+	let mut objects = Vec::new();
+
+	for filename in filenames
+	{
+		if !quiet
+		{
+			println!("linking `{}`...", filename);
+		}
+
+		let bytes = fileserver.get_bytes(report.clone(), &filename, None).map_err(|_| false)?;
+
+		let object = asm::Object::deserialize(
+			report.clone(),
+			&filename,
+			&String::from_utf8_lossy(&bytes))
+			.map_err(|_| false)?;
+
+		objects.push((filename, object));
+	}
+
+	let filenames: Vec<String> = objects.iter().map(|(filename, _)| filename.clone()).collect();
+	let output = asm::link(report, objects, fileserver).map_err(|_| false)?;
+
+	for filename in &filenames
+	{
+		output.state.track_read_file(filename);
+	}
+
+	Ok(output)
 }
 
 
@@ -352,12 +933,12 @@ fn print_version_full()
 }
 
 
-fn get_default_output_filename(report: diagn::RcReport, input_filename: &str) -> Result<String, ()>
+fn get_default_output_filename(report: diagn::RcReport, input_filename: &str, extension: &str) -> Result<String, ()>
 {
 	use std::path::PathBuf;
 	
 	let mut output_filename = PathBuf::from(input_filename);
-	output_filename.set_extension("bin");
+	output_filename.set_extension(extension);
 	
 	let output_filename = output_filename.to_string_lossy().into_owned().replace("\\", "/");
 	
@@ -366,3 +947,23 @@ fn get_default_output_filename(report: diagn::RcReport, input_filename: &str) ->
 	
 	Ok(output_filename)
 }
+
+
+fn get_bank_output_filename(output_filename: &str, bank_name: &str) -> String
+{
+	if bank_name.len() == 0
+		{ return output_filename.to_string(); }
+	
+	let name_start = output_filename.rfind('/').map(|i| i + 1).unwrap_or(0);
+	
+	match output_filename[name_start..].rfind('.')
+	{
+		Some(dot) if dot > 0 =>
+		{
+			let dot = name_start + dot;
+			format!("{}.{}{}", &output_filename[..dot], bank_name, &output_filename[dot..])
+		}
+		
+		_ => format!("{}.{}", output_filename, bank_name),
+	}
+}
@@ -2,10 +2,21 @@ use crate::*;
 use std::collections::HashMap;
 
 
+/// The number of loop iterations a single evaluation may run
+/// before giving up, so that a runaway `while` reports an error
+/// instead of hanging the assembler.
+const MAX_LOOP_ITERATIONS: usize = 100000;
+
+
 pub struct EvalContext
 {
 	locals: HashMap<String, expr::Value>,
 	token_subs: HashMap<String, Vec<syntax::Token>>,
+	loop_iterations: usize,
+	
+	/// For each block being evaluated, the locals it declared
+	/// with `let`, and the values they shadowed
+	scopes: Vec<Vec<(String, Option<expr::Value>)>>,
 }
 
 
@@ -17,6 +28,8 @@ impl EvalContext
 		{
 			locals: HashMap::new(),
 			token_subs: HashMap::new(),
+			loop_iterations: 0,
+			scopes: Vec::new(),
 		}
 	}
 	
@@ -38,6 +51,40 @@ impl EvalContext
 	}
 	
 	
+	/// Sets a local that lasts until the end of the
+	/// current block, restoring any value it shadows.
+	pub fn declare_local<S>(&mut self, name: S, value: expr::Value)
+	where S: Into<String>
+	{
+		let name = name.into();
+		let prev = self.locals.insert(name.clone(), value);
+		
+		if let Some(scope) = self.scopes.last_mut()
+			{ scope.push((name, prev)); }
+	}
+	
+	
+	fn enter_scope(&mut self)
+	{
+		self.scopes.push(Vec::new());
+	}
+	
+	
+	fn exit_scope(&mut self)
+	{
+		let scope = self.scopes.pop().unwrap();
+		
+		for (name, prev) in scope.into_iter().rev()
+		{
+			match prev
+			{
+				Some(value) => { self.locals.insert(name, value); }
+				None => { self.locals.remove(&name); }
+			}
+		}
+	}
+	
+	
 	pub fn set_token_sub<S>(&mut self, name: S, tokens: Vec<syntax::Token>)
 	where S: Into<String>
 	{
@@ -49,6 +96,22 @@ impl EvalContext
 	{
 		self.token_subs.get(name)
 	}
+	
+	
+	fn count_loop_iteration(&mut self, report: diagn::RcReport, span: &diagn::Span) -> Result<(), ()>
+	{
+		self.loop_iterations += 1;
+		
+		if self.loop_iterations > MAX_LOOP_ITERATIONS
+		{
+			report.error_span(
+				format!("loop exceeded the limit of {} iterations", MAX_LOOP_ITERATIONS),
+				span);
+			return Err(());
+		}
+		
+		Ok(())
+	}
 }
 
 
@@ -220,6 +283,15 @@ impl expr::Expr
 							}
 						}
 
+						(expr::Value::String(lhs), expr::Value::String(rhs)) if op == expr::BinaryOp::Add =>
+						{
+							if lhs.encoding != rhs.encoding
+								{ return Err(report.error_span("concatenation of strings with different encodings", &span)); }
+
+							let contents = format!("{}{}", lhs.utf8_contents, rhs.utf8_contents);
+							return Ok(expr::Value::make_string(contents, &lhs.encoding));
+						}
+
 						_ => {}
 					}
 					
@@ -315,12 +387,83 @@ impl expr::Expr
 			
 			&expr::Expr::Block(_, ref exprs) =>
 			{
-				let mut result = expr::Value::Void;
+				ctx.enter_scope();
+				
+				let mut result = Ok(expr::Value::Void);
 				
 				for expr in exprs
-					{ result = expr.eval(report.clone(), ctx, eval_var, eval_fn, eval_asm)?; }
+				{
+					result = expr.eval(report.clone(), ctx, eval_var, eval_fn, eval_asm);
+					if result.is_err()
+						{ break; }
+				}
+				
+				ctx.exit_scope();
+				result
+			}
+			
+			&expr::Expr::While(ref span, ref cond, ref body) =>
+			{
+				loop
+				{
+					match cond.eval(report.clone(), ctx, eval_var, eval_fn, eval_asm)?
+					{
+						expr::Value::Bool(true) => {}
+						expr::Value::Bool(false) => break,
+						_ => return Err(report.error_span("invalid condition type", &cond.span()))
+					}
+					
+					ctx.count_loop_iteration(report.clone(), span)?;
+					body.eval(report.clone(), ctx, eval_var, eval_fn, eval_asm)?;
+				}
+				
+				Ok(expr::Value::Void)
+			}
+			
+			&expr::Expr::For(ref span, ref var, ref start_expr, ref end_expr, ref body) =>
+			{
+				let eval_bound = |bound_expr: &expr::Expr, ctx: &mut EvalContext|
+				{
+					match bound_expr.eval(report.clone(), ctx, eval_var, eval_fn, eval_asm)?
+						.get_bigint()
+						.and_then(|bigint| bigint.checked_to_isize())
+					{
+						Some(value) => Ok(value),
+						None => Err(report.error_span("invalid range bound", &bound_expr.span()))
+					}
+				};
+				
+				let start = eval_bound(start_expr, ctx)?;
+				let end = eval_bound(end_expr, ctx)?;
+				
+				// The loop variable only exists inside the loop
+				ctx.enter_scope();
+				
+				let mut result = Ok(expr::Value::Void);
+				
+				for value in start..end
+				{
+					result = ctx.count_loop_iteration(report.clone(), span)
+						.and_then(|_|
+						{
+							ctx.declare_local(var.clone(), expr::Value::make_integer(value));
+							body.eval(report.clone(), ctx, eval_var, eval_fn, eval_asm)
+						})
+						.map(|_| expr::Value::Void);
 					
-				Ok(result)
+					if result.is_err()
+						{ break; }
+				}
+				
+				ctx.exit_scope();
+				result
+			}
+			
+			&expr::Expr::Let(_, ref name, ref value_expr) =>
+			{
+				let value = value_expr.eval(report.clone(), ctx, eval_var, eval_fn, eval_asm)?;
+				ctx.declare_local(name.clone(), value);
+				Ok(expr::Value::Void)
 			}
 			
 			&expr::Expr::Call(ref span, ref target, ref arg_exprs) =>
@@ -12,6 +12,9 @@ pub enum Expr
 	BitSlice(diagn::Span, diagn::Span, usize, usize, Box<Expr>),
 	SoftSlice(diagn::Span, diagn::Span, usize, usize, Box<Expr>),
 	Block(diagn::Span, Vec<Expr>),
+	While(diagn::Span, Box<Expr>, Box<Expr>),
+	For(diagn::Span, String, Box<Expr>, Box<Expr>, Box<Expr>),
+	Let(diagn::Span, String, Box<Expr>),
 	Call(diagn::Span, Box<Expr>, Vec<Expr>),
 	Asm(diagn::Span, Vec<syntax::Token>),
 }
@@ -85,6 +88,9 @@ impl Expr
 			&Expr::BitSlice (ref span, ..) => span.clone(),
 			&Expr::SoftSlice(ref span, ..) => span.clone(),
 			&Expr::Block    (ref span, ..) => span.clone(),
+			&Expr::While    (ref span, ..) => span.clone(),
+			&Expr::For      (ref span, ..) => span.clone(),
+			&Expr::Let      (ref span, ..) => span.clone(),
 			&Expr::Call     (ref span, ..) => span.clone(),
 			&Expr::Asm      (ref span, ..) => span.clone(),
 		}
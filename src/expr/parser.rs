@@ -369,6 +369,18 @@ impl<'a, 'parser> ExpressionParser<'a, 'parser>
 		else if self.parser.next_is(0, syntax::TokenKind::ParenOpen)
 			{ self.parse_parenthesized() }
 	
+		else if self.next_is_keyword("if")
+			{ self.parse_if() }
+	
+		else if self.next_is_keyword("while")
+			{ self.parse_while() }
+	
+		else if self.next_is_keyword("for")
+			{ self.parse_for() }
+	
+		else if self.next_is_keyword("let")
+			{ self.parse_let() }
+	
 		else if self.parser.next_is(0, syntax::TokenKind::Identifier)
 			{ self.parse_variable() }
 			
@@ -420,6 +432,108 @@ impl<'a, 'parser> ExpressionParser<'a, 'parser>
 	}
 	
 	
+	fn next_is_keyword(&self, keyword: &str) -> bool
+	{
+		self.parser.next_is(0, syntax::TokenKind::Identifier) &&
+			self.parser.next().excerpt.as_ref().unwrap() == keyword
+	}
+	
+	
+	fn expect_keyword(&mut self, keyword: &str) -> Result<syntax::Token, ()>
+	{
+		let descr = format!("expected `{}`", keyword);
+		let tk = self.parser.expect_msg(syntax::TokenKind::Identifier, descr.clone())?;
+		
+		if tk.excerpt.as_ref().unwrap() != keyword
+		{
+			if let Some(ref report) = self.parser.report
+			{
+				report.error_span(descr, &tk.span);
+			}
+			return Err(());
+		}
+		
+		Ok(tk)
+	}
+	
+	
+	fn parse_if(&mut self) -> Result<expr::Expr, ()>
+	{
+		let tk_if = self.expect_keyword("if")?;
+		let cond = self.parse_expr()?;
+		let true_branch = self.parse_block()?;
+		
+		// An `else` may also start the line after the closing brace
+		let false_branch =
+		{
+			if self.next_is_keyword("else")
+			{
+				self.parser.advance();
+				
+				if self.next_is_keyword("if")
+					{ self.parse_if()? }
+				else
+					{ self.parse_block()? }
+			}
+			else
+				{ expr::Expr::Block(true_branch.span(), Vec::new()) }
+		};
+		
+		let span = tk_if.span.join(&false_branch.span());
+		
+		Ok(expr::Expr::TernaryOp(span, Box::new(cond), Box::new(true_branch), Box::new(false_branch)))
+	}
+	
+	
+	fn parse_while(&mut self) -> Result<expr::Expr, ()>
+	{
+		let tk_while = self.expect_keyword("while")?;
+		let cond = self.parse_expr()?;
+		let body = self.parse_block()?;
+		
+		let span = tk_while.span.join(&body.span());
+		
+		Ok(expr::Expr::While(span, Box::new(cond), Box::new(body)))
+	}
+	
+	
+	fn parse_for(&mut self) -> Result<expr::Expr, ()>
+	{
+		let tk_for = self.expect_keyword("for")?;
+		let tk_var = self.parser.expect(syntax::TokenKind::Identifier)?;
+		self.expect_keyword("in")?;
+		
+		let start = self.parse_expr()?;
+		self.parser.expect(syntax::TokenKind::Dot)?;
+		self.parser.expect(syntax::TokenKind::Dot)?;
+		let end = self.parse_expr()?;
+		
+		let body = self.parse_block()?;
+		
+		let span = tk_for.span.join(&body.span());
+		
+		Ok(expr::Expr::For(
+			span,
+			tk_var.excerpt.clone().unwrap(),
+			Box::new(start),
+			Box::new(end),
+			Box::new(body)))
+	}
+	
+	
+	fn parse_let(&mut self) -> Result<expr::Expr, ()>
+	{
+		let tk_let = self.expect_keyword("let")?;
+		let tk_name = self.parser.expect(syntax::TokenKind::Identifier)?;
+		self.parser.expect(syntax::TokenKind::Equal)?;
+		let value = self.parse_expr()?;
+		
+		let span = tk_let.span.join(&value.span());
+		
+		Ok(expr::Expr::Let(span, tk_name.excerpt.clone().unwrap(), Box::new(value)))
+	}
+	
+	
 	fn parse_parenthesized(&mut self) -> Result<expr::Expr, ()>
 	{
 		self.parser.expect(syntax::TokenKind::ParenOpen)?;
@@ -449,10 +563,14 @@ impl<'a, 'parser> ExpressionParser<'a, 'parser>
 			hierarchy.push(name);
 			span = span.join(&tk_name.span);
 
-			if self.parser.maybe_expect(syntax::TokenKind::Dot).is_none()
+			// Stop before a range operator, as in `0..count`
+			if !self.parser.next_is(0, syntax::TokenKind::Dot) ||
+				self.parser.next_is(1, syntax::TokenKind::Dot)
 			{
 				break;
 			}
+			
+			self.parser.advance();
 		}
 		
 		Ok(expr::Expr::Variable(span, hierarchy_level, hierarchy))
@@ -13,6 +13,7 @@ pub mod expr;
 pub mod asm;
 pub mod util;
 pub mod driver;
+pub mod lsp;
 
 
 pub mod webasm;
@@ -27,7 +28,9 @@ pub fn assemble_str_to_binary(src: &str) -> (Option<Vec<u8>>, diagn::Report)
 	let mut fileserver = util::FileServerMock::new();
 	fileserver.add("str", src.clone());
 	
-	let assemble = |report: diagn::RcReport, fileserver: &util::FileServerMock, filename: &str| -> Result<Vec<u8>, ()>
+	let fileserver = util::FileServerStd::new(&mut fileserver);
+	
+	let assemble = |report: diagn::RcReport, fileserver: &dyn util::FileServer, filename: &str| -> Result<Vec<u8>, ()>
 	{
 		let mut asm = asm::Assembler::new();
 		asm.register_file(filename);
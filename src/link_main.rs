@@ -0,0 +1,12 @@
+extern crate customasm;
+
+
+fn main()
+{
+	let args: Vec<String> = std::env::args().collect();
+	
+	let mut fileserver = customasm::util::FileServerReal::new();
+	
+	if let Err(()) = customasm::driver::drive_link(&args, &mut fileserver)
+		{ std::process::exit(1); }
+}
\ No newline at end of file
@@ -0,0 +1,136 @@
+use crate::*;
+use crate::diagn::{Span, RcReport};
+use std::collections::HashMap;
+
+
+/// Serves the contents of documents currently open in the
+/// editor, falling back to the filesystem for everything else.
+pub struct DocumentFileServer
+{
+	documents: HashMap<String, Vec<u8>>,
+	filesystem: util::FileServerReal,
+}
+
+
+impl DocumentFileServer
+{
+	pub fn new() -> DocumentFileServer
+	{
+		DocumentFileServer
+		{
+			documents: HashMap::new(),
+			filesystem: util::FileServerReal::new(),
+		}
+	}
+
+
+	pub fn open<S, T>(&mut self, filename: S, contents: T)
+	where S: Into<String>, T: Into<Vec<u8>>
+	{
+		self.documents.insert(filename.into(), contents.into());
+	}
+
+
+	pub fn close(&mut self, filename: &str)
+	{
+		self.documents.remove(filename);
+	}
+
+
+	pub fn is_open(&self, filename: &str) -> bool
+	{
+		self.documents.contains_key(filename)
+	}
+}
+
+
+impl util::FileServer for DocumentFileServer
+{
+	fn exists(&self, filename: &str) -> bool
+	{
+		util::get_std_file(filename).is_some() ||
+			self.documents.contains_key(filename) ||
+			std::path::Path::new(&filename_to_path(filename)).exists()
+	}
+
+
+	fn get_bytes(&self, report: RcReport, filename: &str, span: Option<&Span>) -> Result<Vec<u8>, ()>
+	{
+		if let Some(contents) = util::get_std_file(filename)
+			{ return Ok(contents.bytes().collect()); }
+		
+		match self.documents.get(filename)
+		{
+			Some(bytes) => Ok(bytes.clone()),
+			None => self.filesystem.get_bytes(report, &filename_to_path(filename), span),
+		}
+	}
+
+
+	fn write_bytes(&mut self, report: RcReport, filename: &str, _data: &Vec<u8>, span: Option<&Span>) -> Result<(), ()>
+	{
+		let descr = format!("cannot write file `{}` from the language server", filename);
+
+		if let Some(span) = span
+			{ report.error_span(descr, span); }
+		else
+			{ report.error(descr); }
+
+		Err(())
+	}
+}
+
+
+// Filenames are kept without the leading slash of absolute
+// paths, since `util::filename_navigate` always produces
+// paths relative to the root.
+pub fn uri_to_filename(uri: &str) -> String
+{
+	let path = uri.trim_start_matches("file://");
+
+	let mut bytes = Vec::new();
+	let mut chars = path.bytes();
+	while let Some(b) = chars.next()
+	{
+		if b == b'%'
+		{
+			let hex: Vec<u8> = chars.by_ref().take(2).collect();
+			match u8::from_str_radix(&String::from_utf8_lossy(&hex), 16)
+			{
+				Ok(decoded) => bytes.push(decoded),
+				Err(_) => { bytes.push(b); bytes.extend(hex); }
+			}
+		}
+		else
+			{ bytes.push(b); }
+	}
+
+	String::from_utf8_lossy(&bytes).trim_start_matches('/').to_string()
+}
+
+
+pub fn filename_to_uri(filename: &str) -> String
+{
+	let mut uri = "file:///".to_string();
+
+	for b in filename.bytes()
+	{
+		if b.is_ascii_alphanumeric() || b"/-_.~".contains(&b)
+			{ uri.push(b as char); }
+		else
+			{ uri.push_str(&format!("%{:02X}", b)); }
+	}
+
+	uri
+}
+
+
+fn filename_to_path(filename: &str) -> String
+{
+	let is_windows_drive = filename.chars().nth(1) == Some(':');
+
+	if is_windows_drive
+		{ filename.to_string() }
+	else
+		{ format!("/{}", filename) }
+}
\ No newline at end of file
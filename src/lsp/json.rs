@@ -0,0 +1,400 @@
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json
+{
+	Null,
+	Bool(bool),
+	Number(f64),
+	String(String),
+	Array(Vec<Json>),
+	Object(Vec<(String, Json)>),
+}
+
+
+impl Json
+{
+	pub fn parse(src: &str) -> Result<Json, ()>
+	{
+		let chars: Vec<char> = src.chars().collect();
+		let mut index = 0;
+
+		let value = parse_value(&chars, &mut index)?;
+
+		skip_whitespace(&chars, &mut index);
+		if index != chars.len()
+			{ return Err(()); }
+
+		Ok(value)
+	}
+
+
+	pub fn object() -> Json
+	{
+		Json::Object(Vec::new())
+	}
+
+
+	pub fn with<S: Into<String>>(mut self, key: S, value: Json) -> Json
+	{
+		if let Json::Object(ref mut fields) = self
+			{ fields.push((key.into(), value)); }
+
+		self
+	}
+
+
+	pub fn get(&self, key: &str) -> &Json
+	{
+		static NULL: Json = Json::Null;
+
+		match self
+		{
+			Json::Object(fields) =>
+			{
+				match fields.iter().find(|f| f.0 == key)
+				{
+					Some(field) => &field.1,
+					None => &NULL,
+				}
+			}
+			_ => &NULL,
+		}
+	}
+
+
+	pub fn as_str(&self) -> Option<&str>
+	{
+		match self
+		{
+			Json::String(s) => Some(s),
+			_ => None,
+		}
+	}
+
+
+	pub fn as_usize(&self) -> Option<usize>
+	{
+		match self
+		{
+			Json::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as usize),
+			_ => None,
+		}
+	}
+
+
+	pub fn as_array(&self) -> Option<&Vec<Json>>
+	{
+		match self
+		{
+			Json::Array(elems) => Some(elems),
+			_ => None,
+		}
+	}
+
+
+	pub fn is_null(&self) -> bool
+	{
+		*self == Json::Null
+	}
+
+
+	fn write(&self, result: &mut String)
+	{
+		match self
+		{
+			Json::Null => result.push_str("null"),
+			Json::Bool(b) => result.push_str(if *b { "true" } else { "false" }),
+			Json::Number(n) => result.push_str(&format!("{}", n)),
+			Json::String(s) => write_string(result, s),
+
+			Json::Array(elems) =>
+			{
+				result.push('[');
+				for (i, elem) in elems.iter().enumerate()
+				{
+					if i > 0
+						{ result.push(','); }
+
+					elem.write(result);
+				}
+				result.push(']');
+			}
+
+			Json::Object(fields) =>
+			{
+				result.push('{');
+				for (i, (key, value)) in fields.iter().enumerate()
+				{
+					if i > 0
+						{ result.push(','); }
+
+					write_string(result, key);
+					result.push(':');
+					value.write(result);
+				}
+				result.push('}');
+			}
+		}
+	}
+}
+
+
+impl std::fmt::Display for Json
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error>
+	{
+		let mut result = String::new();
+		self.write(&mut result);
+		write!(f, "{}", result)
+	}
+}
+
+
+impl From<&str> for Json
+{
+	fn from(s: &str) -> Json
+	{
+		Json::String(s.to_string())
+	}
+}
+
+
+impl From<String> for Json
+{
+	fn from(s: String) -> Json
+	{
+		Json::String(s)
+	}
+}
+
+
+impl From<usize> for Json
+{
+	fn from(n: usize) -> Json
+	{
+		Json::Number(n as f64)
+	}
+}
+
+
+impl From<bool> for Json
+{
+	fn from(b: bool) -> Json
+	{
+		Json::Bool(b)
+	}
+}
+
+
+fn write_string(result: &mut String, s: &str)
+{
+	result.push_str("\"");
+
+	for c in s.chars()
+	{
+		match c
+		{
+			'"' => result.push_str("\\\""),
+			'\\' => result.push_str("\\\\"),
+			'\n' => result.push_str("\\n"),
+			'\r' => result.push_str("\\r"),
+			'\t' => result.push_str("\\t"),
+			c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+			c => result.push(c),
+		}
+	}
+
+	result.push_str("\"");
+}
+
+
+fn skip_whitespace(chars: &[char], index: &mut usize)
+{
+	while *index < chars.len() && chars[*index].is_whitespace()
+		{ *index += 1; }
+}
+
+
+fn expect_char(chars: &[char], index: &mut usize, c: char) -> Result<(), ()>
+{
+	skip_whitespace(chars, index);
+
+	if *index < chars.len() && chars[*index] == c
+	{
+		*index += 1;
+		Ok(())
+	}
+	else
+		{ Err(()) }
+}
+
+
+fn maybe_expect_char(chars: &[char], index: &mut usize, c: char) -> bool
+{
+	expect_char(chars, index, c).is_ok()
+}
+
+
+fn expect_keyword(chars: &[char], index: &mut usize, keyword: &str) -> Result<(), ()>
+{
+	for c in keyword.chars()
+	{
+		if *index >= chars.len() || chars[*index] != c
+			{ return Err(()); }
+
+		*index += 1;
+	}
+
+	Ok(())
+}
+
+
+fn parse_value(chars: &[char], index: &mut usize) -> Result<Json, ()>
+{
+	skip_whitespace(chars, index);
+
+	if *index >= chars.len()
+		{ return Err(()); }
+
+	match chars[*index]
+	{
+		'n' => expect_keyword(chars, index, "null").map(|_| Json::Null),
+		't' => expect_keyword(chars, index, "true").map(|_| Json::Bool(true)),
+		'f' => expect_keyword(chars, index, "false").map(|_| Json::Bool(false)),
+		'"' => parse_string(chars, index).map(Json::String),
+
+		'[' =>
+		{
+			*index += 1;
+
+			let mut elems = Vec::new();
+			if maybe_expect_char(chars, index, ']')
+				{ return Ok(Json::Array(elems)); }
+
+			loop
+			{
+				elems.push(parse_value(chars, index)?);
+
+				if maybe_expect_char(chars, index, ']')
+					{ return Ok(Json::Array(elems)); }
+
+				expect_char(chars, index, ',')?;
+			}
+		}
+
+		'{' =>
+		{
+			*index += 1;
+
+			let mut fields = Vec::new();
+			if maybe_expect_char(chars, index, '}')
+				{ return Ok(Json::Object(fields)); }
+
+			loop
+			{
+				skip_whitespace(chars, index);
+				let key = parse_string(chars, index)?;
+				expect_char(chars, index, ':')?;
+				let value = parse_value(chars, index)?;
+				fields.push((key, value));
+
+				if maybe_expect_char(chars, index, '}')
+					{ return Ok(Json::Object(fields)); }
+
+				expect_char(chars, index, ',')?;
+			}
+		}
+
+		_ => parse_number(chars, index),
+	}
+}
+
+
+fn parse_number(chars: &[char], index: &mut usize) -> Result<Json, ()>
+{
+	let start = *index;
+
+	while *index < chars.len() && "+-0123456789.eE".contains(chars[*index])
+		{ *index += 1; }
+
+	let s: String = chars[start..*index].iter().collect();
+	match s.parse::<f64>()
+	{
+		Ok(n) => Ok(Json::Number(n)),
+		Err(_) => Err(()),
+	}
+}
+
+
+fn parse_string(chars: &[char], index: &mut usize) -> Result<String, ()>
+{
+	if *index >= chars.len() || chars[*index] != '"'
+		{ return Err(()); }
+
+	*index += 1;
+
+	let mut result = String::new();
+	let mut pending_surrogate: Option<u32> = None;
+
+	loop
+	{
+		if *index >= chars.len()
+			{ return Err(()); }
+
+		let c = chars[*index];
+		*index += 1;
+
+		match c
+		{
+			'"' => return Ok(result),
+
+			'\\' =>
+			{
+				if *index >= chars.len()
+					{ return Err(()); }
+
+				let escape = chars[*index];
+				*index += 1;
+
+				match escape
+				{
+					'"' => result.push('"'),
+					'\\' => result.push('\\'),
+					'/' => result.push('/'),
+					'b' => result.push('\u{8}'),
+					'f' => result.push('\u{c}'),
+					'n' => result.push('\n'),
+					'r' => result.push('\r'),
+					't' => result.push('\t'),
+					'u' =>
+					{
+						if *index + 4 > chars.len()
+							{ return Err(()); }
+
+						let hex: String = chars[*index..*index + 4].iter().collect();
+						*index += 4;
+
+						let code = u32::from_str_radix(&hex, 16).map_err(|_| ())?;
+
+						if (0xd800..0xdc00).contains(&code)
+						{
+							pending_surrogate = Some(code);
+							continue;
+						}
+
+						let code = match pending_surrogate.take()
+						{
+							Some(high) if (0xdc00..0xe000).contains(&code) =>
+								0x10000 + ((high - 0xd800) << 10) + (code - 0xdc00),
+							_ => code,
+						};
+
+						result.push(std::char::from_u32(code).unwrap_or('\u{fffd}'));
+					}
+					_ => return Err(()),
+				}
+			}
+
+			c => result.push(c),
+		}
+	}
+}
\ No newline at end of file
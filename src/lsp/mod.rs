@@ -0,0 +1,11 @@
+mod json;
+mod fileserver;
+mod server;
+
+
+pub use self::json::Json;
+pub use self::fileserver::DocumentFileServer;
+pub use self::fileserver::uri_to_filename;
+pub use self::fileserver::filename_to_uri;
+pub use self::server::Server;
+pub use self::server::run;
\ No newline at end of file
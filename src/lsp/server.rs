@@ -0,0 +1,511 @@
+use crate::*;
+use crate::lsp::Json;
+use crate::lsp::fileserver::{uri_to_filename, filename_to_uri};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+
+static MAX_ITERATIONS: usize = 10;
+
+
+pub struct Server
+{
+	fileserver: lsp::DocumentFileServer,
+	states: HashMap<String, asm::State>,
+	shutdown_requested: bool,
+	exited: bool,
+}
+
+
+enum Definition<'a>
+{
+	Symbol(String, &'a asm::Symbol),
+	Function(&'a asm::Function),
+}
+
+
+/// Runs the language server over the given streams until the
+/// client sends `exit`, and returns the process exit code.
+pub fn run(reader: &mut dyn BufRead, writer: &mut dyn Write) -> i32
+{
+	let mut server = Server::new();
+
+	loop
+	{
+		let body = match read_message(reader)
+		{
+			Some(body) => body,
+			None => return 1,
+		};
+
+		let responses = match Json::parse(&body)
+		{
+			Ok(msg) => server.handle_message(&msg),
+			Err(()) => vec![make_error_response(Json::Null, -32700, "parse error")],
+		};
+
+		for response in responses
+		{
+			let body = response.to_string();
+			write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+		}
+
+		writer.flush().unwrap();
+
+		if server.exited
+			{ return if server.shutdown_requested { 0 } else { 1 }; }
+	}
+}
+
+
+fn read_message(reader: &mut dyn BufRead) -> Option<String>
+{
+	let mut content_length = None;
+
+	loop
+	{
+		let mut line = String::new();
+		if reader.read_line(&mut line).ok()? == 0
+			{ return None; }
+
+		let line = line.trim_end();
+		if line.len() == 0
+			{ break; }
+
+		let mut parts = line.splitn(2, ':');
+		let name = parts.next()?.trim();
+		let value = parts.next().unwrap_or("").trim();
+
+		if name.eq_ignore_ascii_case("content-length")
+			{ content_length = value.parse::<usize>().ok(); }
+	}
+
+	let mut body = vec![0; content_length?];
+	reader.read_exact(&mut body).ok()?;
+
+	Some(String::from_utf8_lossy(&body).to_string())
+}
+
+
+impl Server
+{
+	pub fn new() -> Server
+	{
+		Server
+		{
+			fileserver: lsp::DocumentFileServer::new(),
+			states: HashMap::new(),
+			shutdown_requested: false,
+			exited: false,
+		}
+	}
+
+
+	pub fn has_exited(&self) -> bool
+	{
+		self.exited
+	}
+
+
+	pub fn handle_message(&mut self, msg: &Json) -> Vec<Json>
+	{
+		let id = msg.get("id");
+		let params = msg.get("params");
+
+		let method = match msg.get("method").as_str()
+		{
+			Some(method) => method,
+			None => return Vec::new(),
+		};
+
+		match method
+		{
+			"initialize" =>
+			{
+				let capabilities = Json::object()
+					.with("textDocumentSync", Json::from(1))
+					.with("definitionProvider", Json::from(true))
+					.with("hoverProvider", Json::from(true));
+
+				let server_info = Json::object()
+					.with("name", Json::from("customasm-lsp"))
+					.with("version", Json::from(env!("CARGO_PKG_VERSION")));
+
+				vec![make_response(id.clone(), Json::object()
+					.with("capabilities", capabilities)
+					.with("serverInfo", server_info))]
+			}
+
+			"shutdown" =>
+			{
+				self.shutdown_requested = true;
+				vec![make_response(id.clone(), Json::Null)]
+			}
+
+			"exit" =>
+			{
+				self.exited = true;
+				Vec::new()
+			}
+
+			"textDocument/didOpen" =>
+			{
+				let document = params.get("textDocument");
+				let uri = document.get("uri").as_str().unwrap_or("");
+				let text = document.get("text").as_str().unwrap_or("");
+
+				let filename = uri_to_filename(uri);
+				self.fileserver.open(filename.clone(), text);
+				vec![self.assemble(&filename)]
+			}
+
+			"textDocument/didChange" =>
+			{
+				let uri = params.get("textDocument").get("uri").as_str().unwrap_or("");
+				let filename = uri_to_filename(uri);
+
+				// Only full document synchronization is advertised,
+				// so the last change holds the whole text.
+				let changes = params.get("contentChanges").as_array();
+				match changes.and_then(|c| c.last()).and_then(|c| c.get("text").as_str())
+				{
+					Some(text) =>
+					{
+						self.fileserver.open(filename.clone(), text);
+						vec![self.assemble(&filename)]
+					}
+					None => Vec::new(),
+				}
+			}
+
+			"textDocument/didClose" =>
+			{
+				let uri = params.get("textDocument").get("uri").as_str().unwrap_or("");
+				let filename = uri_to_filename(uri);
+
+				self.fileserver.close(&filename);
+				self.states.remove(&filename);
+				vec![make_notification(
+					"textDocument/publishDiagnostics",
+					Json::object()
+						.with("uri", Json::from(uri))
+						.with("diagnostics", Json::Array(Vec::new())))]
+			}
+
+			"textDocument/definition" =>
+			{
+				let result = self.find_definition(params)
+					.and_then(|def| self.make_location(def.decl_span()))
+					.unwrap_or(Json::Null);
+
+				vec![make_response(id.clone(), result)]
+			}
+
+			"textDocument/hover" =>
+			{
+				let result = self.find_definition(params)
+					.map(|def| Json::object()
+						.with("contents", Json::object()
+							.with("kind", Json::from("markdown"))
+							.with("value", Json::from(format!("```\n{}\n```", def.describe())))))
+					.unwrap_or(Json::Null);
+
+				vec![make_response(id.clone(), result)]
+			}
+
+			_ =>
+			{
+				if id.is_null()
+					{ Vec::new() }
+				else
+					{ vec![make_error_response(id.clone(), -32601, "method not found")] }
+			}
+		}
+	}
+
+
+	fn assemble(&mut self, filename: &str) -> Json
+	{
+		let report = diagn::RcReport::new();
+
+		let mut assembler = asm::Assembler::new();
+		assembler.register_file(filename);
+
+		// Keep the state from the last successful assembly, so that
+		// navigation still works while the document has errors.
+		if let Ok(output) = assembler.assemble(report.clone(), &self.fileserver, MAX_ITERATIONS)
+			{ self.states.insert(filename.to_string(), output.state); }
+
+		let report = report.into_inner();
+
+		let diagnostics = report.messages()
+			.iter()
+			.map(|msg| self.make_diagnostic(filename, msg))
+			.collect();
+
+		make_notification(
+			"textDocument/publishDiagnostics",
+			Json::object()
+				.with("uri", Json::from(filename_to_uri(filename)))
+				.with("diagnostics", Json::Array(diagnostics)))
+	}
+
+
+	fn make_diagnostic(&self, filename: &str, msg: &diagn::Message) -> Json
+	{
+		let severity = match msg.kind
+		{
+			diagn::MessageKind::Error => 1,
+			diagn::MessageKind::Warning => 2,
+			diagn::MessageKind::Note => 3,
+		};
+
+		// Messages from other files are shown at
+		// the top of the document being assembled.
+		let (range, descr) = match msg.span
+		{
+			Some(ref span) if *span.file == filename && span.location.is_some() =>
+				(self.make_range(span), msg.descr.clone()),
+
+			Some(ref span) =>
+			{
+				let line = self.make_range(span).get("start").get("line").as_usize().unwrap();
+				(make_empty_range(), format!("{}:{}: {}", span.file, line + 1, msg.descr))
+			}
+
+			None =>
+				(make_empty_range(), msg.descr.clone()),
+		};
+
+		let mut related = Vec::new();
+		self.collect_related_information(msg, &mut related);
+
+		Json::object()
+			.with("range", range)
+			.with("severity", Json::from(severity as usize))
+			.with("source", Json::from("customasm"))
+			.with("message", Json::from(descr))
+			.with("relatedInformation", Json::Array(related))
+	}
+
+
+	fn collect_related_information(&self, msg: &diagn::Message, related: &mut Vec<Json>)
+	{
+		for inner in &msg.inner
+		{
+			if let Some(location) = inner.span.as_ref().and_then(|s| self.make_location(s))
+			{
+				related.push(Json::object()
+					.with("location", location)
+					.with("message", Json::from(inner.descr.clone())));
+			}
+
+			self.collect_related_information(inner, related);
+		}
+	}
+
+
+	fn make_location(&self, span: &diagn::Span) -> Option<Json>
+	{
+		span.location?;
+
+		Some(Json::object()
+			.with("uri", Json::from(filename_to_uri(&span.file)))
+			.with("range", self.make_range(span)))
+	}
+
+
+	fn make_range(&self, span: &diagn::Span) -> Json
+	{
+		use util::FileServer;
+
+		let chars = self.fileserver
+			.get_chars(diagn::RcReport::new(), &span.file, None)
+			.unwrap_or(Vec::new());
+
+		let counter = util::CharCounter::new(&chars);
+		let (start, end) = span.location.unwrap_or((0, 0));
+
+		let (line1, col1) = counter.get_line_column_at_index(start);
+		let (line2, col2) = counter.get_line_column_at_index(end);
+
+		Json::object()
+			.with("start", make_position(line1, col1))
+			.with("end", make_position(line2, col2))
+	}
+
+
+	fn find_definition(&self, params: &Json) -> Option<Definition<'_>>
+	{
+		use util::FileServer;
+
+		let uri = params.get("textDocument").get("uri").as_str()?;
+		let line = params.get("position").get("line").as_usize()?;
+		let character = params.get("position").get("character").as_usize()?;
+
+		let filename = uri_to_filename(uri);
+		let state = self.states.get(&filename)?;
+
+		let chars = self.fileserver.get_chars(diagn::RcReport::new(), &filename, None).ok()?;
+		let counter = util::CharCounter::new(&chars);
+		let line_range = counter.get_index_range_of_line(line);
+		let index = std::cmp::min(line_range.0 + character, line_range.1);
+
+		let tokens = syntax::tokenize(diagn::RcReport::new(), filename.clone(), &chars).ok()?;
+
+		// Find the identifier under the cursor, then extend it
+		// backwards to include its parents and leading dots,
+		// as in `.loop` or `start.loop`.
+		let cursor = tokens.iter().position(|t|
+		{
+			let (start, end) = t.span.location.unwrap();
+			t.kind == syntax::TokenKind::Identifier && index >= start && index <= end
+		})?;
+
+		let mut first = cursor;
+		while first > 0
+		{
+			let prev = &tokens[first - 1];
+			let is_name_part =
+				prev.kind == syntax::TokenKind::Identifier ||
+				prev.kind == syntax::TokenKind::Dot;
+
+			if !is_name_part || prev.span.location.unwrap().1 != tokens[first].span.location.unwrap().0
+				{ break; }
+
+			first -= 1;
+		}
+
+		let leading_dots = tokens[first..=cursor]
+			.iter()
+			.take_while(|t| t.kind == syntax::TokenKind::Dot)
+			.count();
+
+		let mut hierarchy: Vec<String> = tokens[first..=cursor]
+			.iter()
+			.filter(|t| t.kind == syntax::TokenKind::Identifier)
+			.map(|t| t.excerpt.clone().unwrap())
+			.collect();
+
+		if leading_dots == 0 && hierarchy.len() == 1
+		{
+			if let Some(function) = state.functions.iter().find(|f| f.name == hierarchy[0])
+				{ return Some(Definition::Function(function)); }
+		}
+
+		if leading_dots > 0
+		{
+			// Local symbols belong to the nearest preceding
+			// symbol declared at the level above.
+			let mut parent: Option<(usize, String)> = None;
+
+			state.symbols.format(&mut |_, symbol: &asm::Symbol, name: &str, _: &util::BigInt|
+			{
+				if *symbol.decl_span.file != filename || name.split('.').count() != leading_dots
+					{ return; }
+
+				if let Some((start, _)) = symbol.decl_span.location
+				{
+					if start <= index && parent.as_ref().map(|p| p.0 <= start).unwrap_or(true)
+						{ parent = Some((start, name.to_string())); }
+				}
+			});
+
+			let mut full_hierarchy: Vec<String> = parent?.1.split('.').map(|s| s.to_string()).collect();
+			full_hierarchy.append(&mut hierarchy);
+			hierarchy = full_hierarchy;
+		}
+
+		let ctx = state.symbols.get_ctx();
+		let symbol = state.symbols.get(&ctx, 0, &hierarchy)?;
+
+		Some(Definition::Symbol(hierarchy.join("."), symbol))
+	}
+}
+
+
+impl<'a> Definition<'a>
+{
+	fn decl_span(&self) -> &diagn::Span
+	{
+		match self
+		{
+			Definition::Symbol(_, symbol) => &symbol.decl_span,
+			Definition::Function(function) => &function.decl_span,
+		}
+	}
+
+
+	fn describe(&self) -> String
+	{
+		match self
+		{
+			Definition::Function(function) =>
+				format!("#fn {}({})", function.name, function.params.join(", ")),
+
+			Definition::Symbol(name, symbol) =>
+			{
+				let kind = match symbol.kind
+				{
+					asm::SymbolKind::Label => "label",
+					asm::SymbolKind::Constant => "constant",
+				};
+
+				let value = match symbol.value
+				{
+					expr::Value::Integer(ref bigint) => format!("0x{:x}", bigint),
+					expr::Value::Bool(value) => format!("{}", value),
+					expr::Value::String(ref s) => format!("{:?}", s.utf8_contents),
+					_ => "?".to_string(),
+				};
+
+				format!("({}) {} = {}", kind, name, value)
+			}
+		}
+	}
+}
+
+
+fn make_position(line: usize, character: usize) -> Json
+{
+	Json::object()
+		.with("line", Json::from(line))
+		.with("character", Json::from(character))
+}
+
+
+fn make_empty_range() -> Json
+{
+	Json::object()
+		.with("start", make_position(0, 0))
+		.with("end", make_position(0, 0))
+}
+
+
+fn make_response(id: Json, result: Json) -> Json
+{
+	Json::object()
+		.with("jsonrpc", Json::from("2.0"))
+		.with("id", id)
+		.with("result", result)
+}
+
+
+fn make_error_response(id: Json, code: i32, message: &str) -> Json
+{
+	Json::object()
+		.with("jsonrpc", Json::from("2.0"))
+		.with("id", id)
+		.with("error", Json::object()
+			.with("code", Json::Number(code as f64))
+			.with("message", Json::from(message)))
+}
+
+
+fn make_notification(method: &str, params: Json) -> Json
+{
+	Json::object()
+		.with("jsonrpc", Json::from("2.0"))
+		.with("method", Json::from(method))
+		.with("params", params)
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+extern crate customasm;
+
+
+fn main()
+{
+	let stdin = std::io::stdin();
+	let stdout = std::io::stdout();
+	
+	let exit_code = customasm::lsp::run(&mut stdin.lock(), &mut stdout.lock());
+	std::process::exit(exit_code);
+}
\ No newline at end of file
@@ -378,8 +378,14 @@ fn check_for_number(src: &[char]) -> Option<(TokenKind, usize)>
 		{ return None; }
 	
 	while length < src.len() && is_number_mid(src[length])
-		{ length += 1; }
-		
+	{
+		// Stop before a range operator, as in `0..16`
+		if src[length] == '.' && length + 1 < src.len() && src[length + 1] == '.'
+			{ break; }
+
+		length += 1;
+	}
+
 	Some((TokenKind::Number, length))
 }
 
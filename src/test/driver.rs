@@ -0,0 +1,638 @@
+use crate::*;
+
+
+fn drive(args: &[&str], files: &[(&str, &str)]) -> (Result<(), ()>, util::FileServerMock)
+{
+	let mut fileserver = util::FileServerMock::new();
+	for (filename, contents) in files
+	{
+		fileserver.add(*filename, *contents);
+	}
+
+	let mut full_args = vec!["customasm".to_string()];
+	for arg in args
+	{
+		full_args.push(arg.to_string());
+	}
+
+	let result = driver::drive(&full_args, &mut fileserver);
+	(result, fileserver)
+}
+
+
+fn read_output(fileserver: &util::FileServerMock, filename: &str) -> Vec<u8>
+{
+	use util::FileServer;
+	fileserver.get_bytes(diagn::RcReport::new(), filename, None).unwrap()
+}
+
+
+#[test]
+fn test_define()
+{
+	let src = "#d8 REV, FLAG";
+
+	let (result, fileserver) = drive(&["-q", "main.asm", "-o", "out.bin", "-D", "REV=0x2", "-D", "FLAG"], &[("main.asm", src)]);
+	assert_eq!(result, Ok(()));
+	assert_eq!(read_output(&fileserver, "out.bin"), vec![0x02, 0x01]);
+	
+	let (result, fileserver) = drive(&["-q", "main.asm", "-o", "out.bin", "-DREV=0b11", "-DFLAG=1_0"], &[("main.asm", src)]);
+	assert_eq!(result, Ok(()));
+	assert_eq!(read_output(&fileserver, "out.bin"), vec![0x03, 0x0a]);
+	
+	let (result, fileserver) = drive(&["-q", "main.asm", "-o", "out.bin", "-DREV=-4", "-D", "FLAG=-0x10"], &[("main.asm", src)]);
+	assert_eq!(result, Ok(()));
+	assert_eq!(read_output(&fileserver, "out.bin"), vec![0xfc, 0xf0]);
+}
+
+
+#[test]
+fn test_define_with_conditional()
+{
+	let src = "
+		#if REV == 1
+			#d8 0x11
+		#else
+			#d8 0x22
+		#endif";
+
+	let (result, fileserver) = drive(&["-q", "main.asm", "-o", "out.bin", "-D", "REV=1"], &[("main.asm", src)]);
+	assert_eq!(result, Ok(()));
+	assert_eq!(read_output(&fileserver, "out.bin"), vec![0x11]);
+
+	let (result, fileserver) = drive(&["-q", "main.asm", "-o", "out.bin", "-D", "REV=2"], &[("main.asm", src)]);
+	assert_eq!(result, Ok(()));
+	assert_eq!(read_output(&fileserver, "out.bin"), vec![0x22]);
+}
+
+
+#[test]
+fn test_define_errors()
+{
+	let src = "REV = 1";
+
+	assert_eq!(drive(&["-q", "main.asm", "-D", "REV=2"], &[("main.asm", src)]).0, Err(()));
+	assert_eq!(drive(&["-q", "main.asm", "-D", "A=1", "-D", "A=2"], &[("main.asm", "")]).0, Err(()));
+	assert_eq!(drive(&["-q", "main.asm", "-D", "1A=1"], &[("main.asm", "")]).0, Err(()));
+	assert_eq!(drive(&["-q", "main.asm", "-D", "A="], &[("main.asm", "")]).0, Err(()));
+	assert_eq!(drive(&["-q", "main.asm", "-D", "A=0xg"], &[("main.asm", "")]).0, Err(()));
+	assert_eq!(drive(&["-q", "main.asm", "-D", "A=x"], &[("main.asm", "")]).0, Err(()));
+	assert_eq!(drive(&["-q", "main.asm", "-D", "A=-"], &[("main.asm", "")]).0, Err(()));
+	assert_eq!(drive(&["-q", "main.asm", "-D", "A=--1"], &[("main.asm", "")]).0, Err(()));
+}
+
+#[test]
+fn test_define_duplicate_note()
+{
+	let mut fileserver = util::FileServerMock::new();
+	fileserver.add("main.asm", "REV = 1");
+
+	let mut assembler = asm::Assembler::new();
+	assembler.define_symbol("REV", expr::Value::make_integer(2));
+	assembler.register_file("main.asm");
+
+	let report = diagn::RcReport::new();
+	assert!(assembler.assemble(report.clone(), &fileserver, 10).is_err());
+	assert!(report.has_first_error_at(&fileserver, "main.asm", 0, "duplicate symbol"));
+
+	let mut text = Vec::<u8>::new();
+	report.print_all(&mut text, &fileserver);
+	assert!(String::from_utf8(text).unwrap().contains("first declared on the command line with `-D REV`"));
+}
+
+
+#[test]
+fn test_listing()
+{
+	let src = "
+		#ruledef
+		{
+			ld {x} => 0x55 @ x`8
+		}
+		start:
+		ld 0x12
+		VALUE = 0x42
+		#res 2
+		#if VALUE == 1
+		ld 0x34
+		#endif
+		again: ld 0x56
+		#include \"inc.asm\"";
+
+	let (result, fileserver) = drive(
+		&["-q", "main.asm", "-f", "listing", "-o", "out.lst"],
+		&[("main.asm", src), ("inc.asm", "#d8 0xab, 0xcd")]);
+
+	assert_eq!(result, Ok(()));
+
+	let listing = String::from_utf8(read_output(&fileserver, "out.lst")).unwrap();
+	let lines: Vec<&str> = listing.lines().collect();
+
+	assert!(lines.iter().any(|l| l.starts_with(" main.asm:6 ") && l.contains(" 0 | ") && l.ends_with("start:")));
+	assert!(lines.iter().any(|l| l.contains("| 55 12 ") && l.ends_with("ld 0x12")));
+	assert!(lines.iter().any(|l| l.contains("| = 0x42 ") && l.ends_with("VALUE = 0x42")));
+	assert!(lines.iter().any(|l| l.contains(" 2 | (2 words reserved) ") && l.ends_with("#res 2")));
+	assert!(lines.iter().any(|l| l.contains("| (skipped) ") && l.ends_with("ld 0x34")));
+	assert!(lines.iter().any(|l| l.contains(" 4 | 55 56 ") && l.ends_with("again: ld 0x56")));
+	assert!(lines.iter().any(|l| l.starts_with(" inc.asm:1 ") && l.contains(" 6 | ab cd ")));
+	assert!(lines.contains(&"; begin include `inc.asm`"));
+	assert!(lines.contains(&"; end include `inc.asm`"));
+	assert!(lines.iter().any(|l| l.ends_with("| label    | start")));
+}
+
+#[test]
+fn test_disassemble()
+{
+	let src = "
+		#ruledef
+		{
+			nop => 0x00
+			ld {x} => 0x55 @ x`8
+			jr {offset: s8} => 0x18 @ offset
+			mov r{a: u3}, r{b: u3} => 0b01 @ a @ b
+			jp {addr: u16} => 0xc3 @ addr[7:0] @ addr[15:8]
+			inc a => 0x3c
+			inc {x: u8} => 0x3d @ x
+			call {addr} => 0xcd @ (addr - 1)`8
+		}
+		#bankdef rom { #addr 0x100, #size 0x100, #outp 0 }";
+
+	let mut fileserver = util::FileServerMock::new();
+	fileserver.add("cpu.asm", src);
+	fileserver.add("rom.bin", vec![0x00, 0x55, 0x12, 0x18, 0xfe, 0x4b, 0xc3, 0x34, 0x12, 0x3c, 0x3d, 0x07, 0xcd, 0x55]);
+
+	let args: Vec<String> = ["customasm", "-q", "cpu.asm", "--disassemble", "rom.bin", "-o", "out.asm"]
+		.iter()
+		.map(|s| s.to_string())
+		.collect();
+
+	assert_eq!(driver::drive(&args, &mut fileserver), Ok(()));
+
+	let output = String::from_utf8(read_output(&fileserver, "out.asm")).unwrap();
+	assert_eq!(output, concat!(
+		"nop        ; 0x100: 00\n",
+		"ld 0x12    ; 0x101: 55 12\n",
+		"jr -0x2    ; 0x103: 18 fe\n",
+		"mov r1, r3 ; 0x105: 4b\n",
+		"jp 0x1234  ; 0x106: c3 34 12\n",
+		"inc a      ; 0x109: 3c\n",
+		"inc 0x7    ; 0x10a: 3d 07\n",
+		"#d8 0xcd   ; 0x10c: cd\n",
+		"mov r2, r5 ; 0x10d: 55\n"));
+}
+
+#[test]
+fn test_disassemble_bank_selection()
+{
+	let src = "
+		#ruledef
+		{
+			nop => 0x0000
+			ld {x: u8} => 0x55 @ x
+		}
+		#bankdef data { #bits 8, #addr 0x8000, #size 0x100 }
+		#bankdef code { #bits 16, #addr 0x100, #size 0x100, #outp 0 }";
+
+	let mut fileserver = util::FileServerMock::new();
+	fileserver.add("cpu.asm", src);
+	fileserver.add("rom.bin", vec![0x00, 0x00, 0x55, 0x12, 0xab]);
+
+	let drive_args = |fileserver: &mut util::FileServerMock, extra: &[&str]|
+	{
+		let mut args: Vec<String> = ["customasm", "-q", "cpu.asm", "--disassemble", "rom.bin", "-o", "out.asm"]
+			.iter()
+			.map(|s| s.to_string())
+			.collect();
+
+		args.extend(extra.iter().map(|s| s.to_string()));
+		driver::drive(&args, fileserver)
+	};
+
+	assert_eq!(drive_args(&mut fileserver, &[]), Err(()));
+	assert_eq!(drive_args(&mut fileserver, &["--bank", "stack"]), Err(()));
+
+	assert_eq!(drive_args(&mut fileserver, &["--bank", "code"]), Ok(()));
+	let output = String::from_utf8(read_output(&fileserver, "out.asm")).unwrap();
+	assert_eq!(output, concat!(
+		"nop      ; 0x100: 0000\n",
+		"ld 0x12  ; 0x101: 5512\n",
+		"#d8 0xab ; 0x102: ab (partial word)\n"));
+
+	assert_eq!(drive_args(&mut fileserver, &["--bank", "data"]), Ok(()));
+	let output = String::from_utf8(read_output(&fileserver, "out.asm")).unwrap();
+	assert_eq!(output, concat!(
+		"nop      ; 0x8000: 00 00\n",
+		"ld 0x12  ; 0x8002: 55 12\n",
+		"#d8 0xab ; 0x8004: ab\n"));
+}
+
+#[test]
+fn test_symbol_formats()
+{
+	let src = "
+		#bankdef header { #addr 0, #size 16, #outp 0 }
+		#bankdef prg { #addr 0x8000, #size 0x10, #outp 8 * 16 }
+		#bankdef ram { #addr 0x200, #size 0x10 }
+		#bank header
+		#d \"NES\", 0x1a, 0`96
+		#bank ram
+		counter: #res 1
+		#bank prg
+		#d8 0xff
+		start:
+		.loop:
+		ten = 10";
+
+	let symbols = |format: &str|
+	{
+		let (result, fileserver) = drive(
+			&["-q", "main.asm", "-o", "out.bin", "--symbol-format", format, "-s", "out.sym"],
+			&[("main.asm", src)]);
+
+		assert_eq!(result, Ok(()));
+		read_output(&fileserver, "out.sym")
+	};
+
+	let text = |format: &str| String::from_utf8(symbols(format)).unwrap();
+
+	assert_eq!(text("mesen-mlb"), "R:200:counter\nP:1:start\nP:1:start_loop\n");
+	assert_eq!(text("sym"), "00:0200 counter\n01:8001 start\n01:8001 start.loop\n");
+	assert_eq!(text("vice"), "al C:0200 .counter\nal C:8001 .start\nal C:8001 .start_loop\n");
+	assert_eq!(text("rgbds"), "; File generated by customasm\n00:0200 counter\n01:8001 start\n01:8001 start.loop\n");
+
+	let elf = symbols("elf");
+	assert_eq!(&elf[0..4], b"\x7fELF");
+	assert!(elf.windows(11).any(|w| w == b"start.loop\0"));
+}
+
+#[test]
+fn test_symbol_bank_numbers()
+{
+	let src = "
+		#bankdef romx2 { #addr 0x4000, #size 0x4000, #outp 8 * 0x8000 }
+		#bankdef rom0 { #addr 0, #size 0x4000, #outp 0 }
+		#bankdef romx1 { #addr 0x4000, #size 0x4000, #outp 8 * 0x4000 }
+		#bank romx2
+		far: #d8 3
+		#bank rom0
+		entry: #d8 1
+		#bank romx1
+		near: #d8 2";
+
+	let text = |format: &str|
+	{
+		let (result, fileserver) = drive(
+			&["-q", "main.asm", "-o", "out.bin", "--symbol-format", format, "-s", "out.sym"],
+			&[("main.asm", src)]);
+
+		assert_eq!(result, Ok(()));
+		String::from_utf8(read_output(&fileserver, "out.sym")).unwrap()
+	};
+
+	assert_eq!(text("sym"), "00:0000 entry\n02:4000 far\n01:4000 near\n");
+	assert_eq!(text("rgbds"), "; File generated by customasm\n00:0000 entry\n02:4000 far\n01:4000 near\n");
+}
+
+#[test]
+fn test_symbol_macro_scopes()
+{
+	let src = "
+		#macro spin(n)
+		{
+			.loop:
+			#d8 {n}
+		}
+		start:
+		spin 1
+		spin 2
+		end:";
+
+	let (result, fileserver) = drive(&["-q", "main.asm", "-o", "out.bin", "-s", "out.sym"], &[("main.asm", src)]);
+	assert_eq!(result, Ok(()));
+	assert_eq!(String::from_utf8(read_output(&fileserver, "out.sym")).unwrap(), "end = 0x2\nstart = 0x0\n");
+
+	let (result, fileserver) = drive(&["-q", "main.asm", "-f", "listing", "-o", "out.lst"], &[("main.asm", src)]);
+	assert_eq!(result, Ok(()));
+	let listing = String::from_utf8(read_output(&fileserver, "out.lst")).unwrap();
+	let symbol_table = listing.split("; symbol table").nth(1).unwrap();
+	assert!(symbol_table.contains("| label    | start"));
+	assert!(!symbol_table.contains('#'));
+}
+
+#[test]
+fn test_symbol_iteration_scopes()
+{
+	let src = "
+		start:
+		#repeat 2 { #d8 0 }
+		#for i in 0..2 {
+			.entry:
+			#d8 i
+		}";
+
+	let (result, fileserver) = drive(&["-q", "main.asm", "-o", "out.bin", "-s", "out.sym"], &[("main.asm", src)]);
+	assert_eq!(result, Ok(()));
+	assert_eq!(String::from_utf8(read_output(&fileserver, "out.sym")).unwrap(), "start = 0x0\n");
+}
+
+#[test]
+fn test_elf_output()
+{
+	let src = "
+		#bankdef code { #addr 0x8000, #size 0x10, #outp 0 }
+		#bankdef ram { #addr 0x200, #size 0x10 }
+		#bank ram
+		counter: #res 2
+		#bank code
+		start:
+		#d8 0xab, 0xcd";
+
+	let (result, fileserver) = drive(&["-q", "main.asm", "-f", "elf", "-o", "out.elf"], &[("main.asm", src)]);
+	assert_eq!(result, Ok(()));
+
+	let elf = read_output(&fileserver, "out.elf");
+	let read_u16 = |offset: usize| u16::from_le_bytes([elf[offset], elf[offset + 1]]) as usize;
+	let read_u32 = |offset: usize| u32::from_le_bytes([elf[offset], elf[offset + 1], elf[offset + 2], elf[offset + 3]]) as usize;
+
+	assert_eq!(&elf[0..4], b"\x7fELF");
+
+	// One loadable segment per bank
+	assert_eq!(read_u16(44), 2);
+	let phoff = read_u32(28);
+	assert_eq!(read_u32(phoff + 8), 0x8000);
+	assert_eq!(read_u32(phoff + 16), 2);
+	assert_eq!(&elf[read_u32(phoff + 4)..][..2], &[0xab, 0xcd]);
+	assert_eq!(read_u32(phoff + 32 + 8), 0x200);
+	assert_eq!(read_u32(phoff + 32 + 16), 0);
+	assert_eq!(read_u32(phoff + 32 + 20), 2);
+
+	// Null, code, ram, .symtab, .strtab, .shstrtab
+	assert_eq!(read_u16(48), 6);
+	assert!(elf.windows(6).any(|w| w == b"start\0"));
+	assert!(elf.windows(8).any(|w| w == b"counter\0"));
+}
+
+#[test]
+fn test_address_formats()
+{
+	let src = "
+		#bankdef a { #addr 0x8000, #size 0x10, #outp 0 }
+		#bankdef b { #addr 0x10000, #size 0x10, #outp 8 * 0x10 }
+		#bank a
+		#d8 0x01, 0x02
+		#bank b
+		#d8 0xab";
+
+	let output = |format: &str|
+	{
+		let (result, fileserver) = drive(&["-q", "main.asm", "-f", format, "-o", "out"], &[("main.asm", src)]);
+		assert_eq!(result, Ok(()));
+		String::from_utf8(read_output(&fileserver, "out")).unwrap()
+	};
+
+	assert_eq!(output("srec"), concat!(
+		"S0030000FC\n",
+		"S206008000010276\n",
+		"S205010000AB4E\n",
+		"S5030002FA\n",
+		"S804000000FB\n"));
+
+	assert_eq!(output("titxt"), concat!(
+		"@8000\n",
+		"01 02\n",
+		"@10000\n",
+		"AB\n",
+		"q\n"));
+}
+
+#[test]
+fn test_split_banks()
+{
+	let src = "
+		#bankdef prog { #addr 0x10000, #size 0x10, #outp 0 }
+		#bankdef ucode { #bits 16, #addr 0, #size 0x10, #outp 8 * 0x10 }
+		#bank prog
+		#d8 0x01, 0x02
+		#bank ucode
+		#d16 0xabcd, 0x1234";
+
+	let (result, fileserver) = drive(
+		&["-q", "main.asm", "-f", "mif", "--split-banks", "-o", "out/rom.mif"],
+		&[("main.asm", src)]);
+
+	assert_eq!(result, Ok(()));
+
+	let prog = String::from_utf8(read_output(&fileserver, "out/rom.prog.mif")).unwrap();
+	assert!(prog.contains("DEPTH = 65538;\nWIDTH = 8;\n"));
+	assert!(prog.contains(" 10000: 01;\n 10001: 02;\n"));
+
+	let ucode = String::from_utf8(read_output(&fileserver, "out/rom.ucode.mif")).unwrap();
+	assert!(ucode.contains("DEPTH = 2;\nWIDTH = 16;\n"));
+	assert!(ucode.contains(" 0: ABCD;\n 1: 1234;\n"));
+
+	let (result, fileserver) = drive(
+		&["-q", "main.asm", "-f", "intelhex", "--split-banks", "-o", "rom.hex"],
+		&[("main.asm", src)]);
+
+	assert_eq!(result, Ok(()));
+
+	assert_eq!(
+		String::from_utf8(read_output(&fileserver, "rom.prog.hex")).unwrap(),
+		":020000040001F9\n:020000000102FB\n:00000001FF");
+
+	assert_eq!(
+		String::from_utf8(read_output(&fileserver, "rom.ucode.hex")).unwrap(),
+		":04000000ABCD12343E\n:00000001FF");
+
+	let (result, _) = drive(
+		&["-q", "main.asm", "-f", "elf", "--split-banks"],
+		&[("main.asm", src)]);
+
+	assert_eq!(result, Err(()));
+}
+
+#[test]
+fn test_memory_init_formats()
+{
+	let src = "
+		#bankdef ucode { #bits 12, #addr 0x20, #size 0x20, #outp 0 }
+		#d12 0xabc, 0x123, 0x00f";
+
+	let output = |args: &[&str], filename: &str|
+	{
+		let (result, fileserver) = drive(args, &[("main.asm", src)]);
+		assert_eq!(result, Ok(()));
+		String::from_utf8(read_output(&fileserver, filename)).unwrap()
+	};
+
+	assert_eq!(output(&["-q", "main.asm", "-f", "coe", "-o", "out"], "out"), concat!(
+		"memory_initialization_radix=16;\n",
+		"memory_initialization_vector=\n",
+		"abc,\n",
+		"123,\n",
+		"00f;\n"));
+
+	assert_eq!(output(&["-q", "main.asm", "-f", "readmemh", "-o", "out"], "out"),
+		"@0 abc 123 00f\n");
+
+	assert_eq!(output(&["-q", "main.asm", "-f", "readmemb", "-o", "out"], "out"),
+		"@0 101010111100 000100100011 000000001111\n");
+
+	let mif = output(&["-q", "main.asm", "-f", "mif", "-o", "out"], "out");
+	assert!(mif.starts_with("DEPTH = 3;\nWIDTH = 12;\n"));
+	assert!(mif.contains(" 0: ABC;\n 1: 123;\n 2: 00F;\n"));
+
+	let mif = output(&["-q", "main.asm", "-f", "mif", "--split-banks", "-o", "out.mif"], "out.ucode.mif");
+	assert!(mif.starts_with("DEPTH = 35;\nWIDTH = 12;\n"));
+	assert!(mif.contains(" 20: ABC;\n 21: 123;\n 22: 00F;\n"));
+
+	assert_eq!(output(&["-q", "main.asm", "-f", "readmemh", "--split-banks", "-o", "out.mem"], "out.ucode.mem"),
+		"@20 abc 123 00f\n");
+}
+
+#[test]
+fn test_mif_bank_address()
+{
+	let src = "
+		#bankdef a { #addr 0x8000, #size 0x10, #outp 0 }
+		#d8 1, 2, 3";
+
+	let (result, fileserver) = drive(&["-q", "main.asm", "-f", "mif", "-o", "out.mif"], &[("main.asm", src)]);
+	assert_eq!(result, Ok(()));
+
+	let mif = String::from_utf8(read_output(&fileserver, "out.mif")).unwrap();
+	assert!(mif.starts_with("DEPTH = 3;\nWIDTH = 8;\n"));
+	assert!(mif.contains(" 0: 01;\n 1: 02;\n 2: 03;\n"));
+
+	let (result, fileserver) = drive(&["-q", "main.asm", "-f", "mif", "--split-banks", "-o", "out.mif"], &[("main.asm", src)]);
+	assert_eq!(result, Ok(()));
+
+	let mif = String::from_utf8(read_output(&fileserver, "out.a.mif")).unwrap();
+	assert!(mif.starts_with("DEPTH = 32771;\nWIDTH = 8;\n"));
+	assert!(mif.contains(" 8000: 01;\n 8001: 02;\n 8002: 03;\n"));
+}
+
+#[test]
+fn test_memory_map()
+{
+	let src = "
+		#bankdef rom { #addr 0x8000, #size 0x20, #outp 0 }
+		#bankdef ram { #addr 0x0, #size 0x100 }
+		#bank rom
+		main:
+		#d8 1, 2, 3
+		.loop:
+		#d8 4
+		#res 3
+		#align 32
+		table:
+		#d16 0xabcd
+		#bank ram
+		buffer: #res 16";
+
+	let (result, fileserver) = drive(&["-q", "main.asm", "--memory-map=map.txt"], &[("main.asm", src)]);
+	assert_eq!(result, Ok(()));
+
+	assert_eq!(String::from_utf8(read_output(&fileserver, "map.txt")).unwrap(), concat!(
+		"bank `rom`: starts at 0x8000, 8-bit words\n",
+		"  used: 10 of 32 words (31.2%)\n",
+		"  free: 22 words\n",
+		"  gaps: 4 words\n",
+		"    0x8004 - 0x8007 (4 words)\n",
+		"  largest labelled regions:\n",
+		"    main: 0x8000 (8 words)\n",
+		"    table: 0x8008 (2 words)\n",
+		"\n",
+		"bank `ram`: starts at 0x0, 8-bit words\n",
+		"  used: 16 of 256 words (6.2%)\n",
+		"  free: 240 words\n",
+		"  largest labelled regions:\n",
+		"    buffer: 0x0 (16 words)\n"));
+}
+
+#[test]
+fn test_tracked_files()
+{
+	let mut fileserver = util::FileServerMock::new();
+	fileserver.add("main.asm", "#include \"inc.asm\"\n#d incbin(\"data.bin\")");
+	fileserver.add("inc.asm", "#d8 1");
+	fileserver.add("data.bin", "\x02");
+
+	let mut tracked_fileserver = util::FileServerTracked::new(&mut fileserver);
+
+	let mut assembler = asm::Assembler::new();
+	assembler.register_file("main.asm");
+	assert!(assembler.assemble(diagn::RcReport::new(), &mut tracked_fileserver, 10).is_ok());
+
+	let read_files: Vec<String> = tracked_fileserver
+		.into_read_files()
+		.into_iter()
+		.map(|(filename, _)| filename)
+		.collect();
+
+	assert_eq!(read_files, vec!["main.asm", "inc.asm", "data.bin"]);
+}
+
+#[test]
+fn test_depfile()
+{
+	let (result, fileserver) = drive(
+		&["-q", "main.asm", "-o", "out.bin", "-s", "out.sym", "--depfile", "out.d"],
+		&[
+			("main.asm", "#include \"cpu.asm\"\nlabel:\n#d incbin(\"my tiles.bin\")"),
+			("cpu.asm", "#d8 1"),
+			("my tiles.bin", "\x02"),
+		]);
+
+	assert_eq!(result, Ok(()));
+	assert_eq!(read_output(&fileserver, "out.bin"), vec![0x01, 0x02]);
+
+	assert_eq!(String::from_utf8(read_output(&fileserver, "out.d")).unwrap(), concat!(
+		"out.bin out.sym: \\\n",
+		"  main.asm \\\n",
+		"  cpu.asm \\\n",
+		"  my\\ tiles.bin\n"));
+}
+
+#[test]
+fn test_depfile_final_pass()
+{
+	let (result, fileserver) = drive(
+		&["-q", "main.asm", "-o", "c:/out.bin", "--depfile", "out.d"],
+		&[
+			("main.asm", "#if end == 0\n#include \"a.asm\"\n#else\n#include \"lib:b.asm\"\n#endif\nend:"),
+			("a.asm", "#d8 0xaa"),
+			("lib:b.asm", "#d8 0xbb"),
+		]);
+
+	assert_eq!(result, Ok(()));
+	assert_eq!(read_output(&fileserver, "c:/out.bin"), vec![0xbb]);
+
+	assert_eq!(String::from_utf8(read_output(&fileserver, "out.d")).unwrap(), concat!(
+		"c\\:/out.bin: \\\n",
+		"  main.asm \\\n",
+		"  lib\\:b.asm\n"));
+}
+
+#[test]
+fn test_include_paths()
+{
+	let files = [
+		("src/main.asm", "#include \"cpu.asm\"\n#d incbin(\"data/tiles.bin\")"),
+		("shared/cpu.asm", "#d8 1"),
+		("assets/data/tiles.bin", "\x02"),
+	];
+
+	let (result, fileserver) = drive(
+		&["-q", "src/main.asm", "-o", "out.bin", "-I", "shared/", "-I", "assets"],
+		&files);
+
+	assert_eq!(result, Ok(()));
+	assert_eq!(read_output(&fileserver, "out.bin"), vec![0x01, 0x02]);
+
+	let (result, _) = drive(
+		&["-q", "src/main.asm", "-o", "out.bin", "-I", "shared"],
+		&files);
+
+	assert_eq!(result, Err(()));
+}
\ No newline at end of file
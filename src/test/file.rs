@@ -159,6 +159,7 @@ pub fn test_file(filepath: &str)
 
 	let mut fileserver = util::FileServerMock::new();
     populate_fileserver(&mut fileserver, &path_prefix, "");
+    let mut fileserver = util::FileServerStd::new(&mut fileserver);
 
 	let report = diagn::RcReport::new();
 
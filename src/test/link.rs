@@ -0,0 +1,281 @@
+use crate::*;
+
+
+static CPU: &str = "
+	#ruledef
+	{
+		ld {x: u8} => 0x10 @ x
+		jmp {addr: u16} => 0x20 @ addr
+		call {addr: u16} => 0x30 @ le(addr)
+		ret => 0x40
+	}
+
+	#fn double(x) => x * 2";
+
+
+fn make_object(fileserver: &util::FileServerMock, filename: &str) -> String
+{
+	let report = diagn::RcReport::new();
+
+	let mut assembler = asm::Assembler::new();
+	assembler.object_mode = true;
+	assembler.register_file(filename);
+
+	let output = assembler.assemble(report.clone(), fileserver, 10).unwrap();
+	let object = output.state.make_object(report.clone(), fileserver).unwrap();
+
+	assert!(!report.has_messages());
+	object.serialize()
+}
+
+
+fn link(files: &[(&str, &str)], object_files: &[&str]) -> (Option<Vec<u8>>, diagn::RcReport, util::FileServerMock)
+{
+	let mut fileserver = util::FileServerMock::new();
+	fileserver.add("cpu.asm", CPU);
+	for (filename, contents) in files
+	{
+		fileserver.add(*filename, *contents);
+	}
+
+	let report = diagn::RcReport::new();
+
+	let mut objects = Vec::new();
+	for filename in object_files
+	{
+		let text = make_object(&fileserver, filename);
+		let object = asm::Object::deserialize(report.clone(), filename, &text).unwrap();
+		objects.push((filename.to_string(), object));
+	}
+
+	let binary = asm::link(report.clone(), objects, &fileserver)
+		.ok()
+		.map(|output| output.binary.format_binary());
+
+	(binary, report, fileserver)
+}
+
+
+fn report_text(report: &diagn::RcReport, fileserver: &dyn util::FileServer) -> String
+{
+	let mut text = Vec::<u8>::new();
+	report.print_all(&mut text, fileserver);
+	String::from_utf8(text).unwrap()
+}
+
+
+#[test]
+fn test_link_imports()
+{
+	let main = "
+		#include \"cpu.asm\"
+		start:
+			call print
+			ld count
+		.loop:
+			jmp .loop
+			#d8 double(count)";
+
+	let lib = "
+		#include \"cpu.asm\"
+		count = 3
+		print:
+			ret";
+
+	let (binary, _, _) = link(&[("main.asm", main), ("lib.asm", lib)], &["main.asm", "lib.asm"]);
+	assert_eq!(binary, Some(vec![0x30, 0x09, 0x00, 0x10, 0x03, 0x20, 0x00, 0x05, 0x06, 0x40]));
+
+	let (binary, _, _) = link(&[("main.asm", main), ("lib.asm", lib)], &["lib.asm", "main.asm"]);
+	assert_eq!(binary, Some(vec![0x40, 0x30, 0x00, 0x00, 0x10, 0x03, 0x20, 0x00, 0x06, 0x06]));
+}
+
+
+#[test]
+fn test_link_banks()
+{
+	let header = "
+		#include \"cpu.asm\"
+		#bankdef code { #addr 0x8000, #size 0x10, #outp 0 }
+		#bankdef data { #addr 0x0200, #size 0x10 }";
+
+	let a = "
+		#include \"header.asm\"
+		#bank data
+		counter: #res 2
+		#bank code
+		ld counter`8
+		jmp other";
+
+	let b = "
+		#include \"header.asm\"
+		#bank data
+		flags: #res 1
+		#bank code
+		other:
+		ld flags`8
+		ld $`8";
+
+	let files = [("header.asm", header), ("a.asm", a), ("b.asm", b)];
+
+	let (binary, _, _) = link(&files, &["a.asm", "b.asm"]);
+	assert_eq!(binary, Some(vec![0x10, 0x00, 0x20, 0x80, 0x05, 0x10, 0x02, 0x10, 0x07]));
+}
+
+
+#[test]
+fn test_link_alignment()
+{
+	let main = "#d8 1, 2, 3";
+
+	let lib = "
+		helper:
+		#d8 0xff
+		#align 32
+		table:
+		#d8 table, helper";
+
+	let (binary, _, _) = link(&[("main.asm", main), ("lib.asm", lib)], &["main.asm", "lib.asm"]);
+	assert_eq!(binary, Some(vec![0x01, 0x02, 0x03, 0x00, 0xff, 0x00, 0x00, 0x00, 0x08, 0x04]));
+}
+
+
+#[test]
+fn test_link_bank_errors()
+{
+	let main = "
+		#include \"cpu.asm\"
+		call helper";
+
+	let banked = "#bankdef a { #addr 0x100, #size 0x10, #outp 0 }\n#d8 0x55";
+
+	let (binary, report, fileserver) = link(&[("main.asm", main), ("lb.asm", banked)], &["main.asm", "lb.asm"]);
+	assert_eq!(binary, None);
+	assert!(report_text(&report, &fileserver).contains(
+		"cannot create new bank `a` in `lb.asm` if the default bank has already been used"));
+
+	let other = "#bankdef b { #addr 0x200, #size 0x10, #outp 8 * 8 }\n#d8 0x66";
+
+	let (binary, report, fileserver) = link(&[("lb.asm", banked), ("other.asm", other)], &["lb.asm", "other.asm"]);
+	assert_eq!(binary, None);
+	assert!(report_text(&report, &fileserver).contains(
+		"output region of bank `b` in `other.asm` overlaps with bank `a`"));
+}
+
+
+#[test]
+fn test_link_errors()
+{
+	let main = "
+		#include \"cpu.asm\"
+		start:
+			call missing";
+
+	let (binary, report, fileserver) = link(&[("main.asm", main)], &["main.asm"]);
+	assert_eq!(binary, None);
+	assert!(report.has_error_at(&fileserver, "main.asm", 3, "unresolved symbol `missing` imported by `main.asm`"));
+
+	let (binary, report, fileserver) = link(&[("main.asm", main), ("lib.asm", "missing:\nstart:")], &["main.asm", "lib.asm"]);
+	assert_eq!(binary, None);
+	assert!(report.has_first_error_at(&fileserver, "lib.asm", 1, "duplicate symbol `start`"));
+
+	let main = "
+		#include \"cpu.asm\"
+		ld big";
+
+	let (binary, report, fileserver) = link(&[("main.asm", main), ("lib.asm", "big = 0x100")], &["main.asm", "lib.asm"]);
+	assert_eq!(binary, None);
+	assert!(report.has_error_at(&fileserver, "main.asm", 2, "argument out of range for type `u8`"));
+}
+
+
+#[test]
+fn test_link_import_candidates()
+{
+	let main = "
+		#ruledef
+		{
+			lda {x: u8} => 0xa5 @ x
+			lda {x: u16} => 0xad @ le(x)
+		}
+		lda 0x12
+		lda far";
+
+	let (binary, _, _) = link(&[("main.asm", main), ("lib.asm", "far = 0x1234")], &["main.asm", "lib.asm"]);
+	assert_eq!(binary, Some(vec![0xa5, 0x12, 0xad, 0x34, 0x12]));
+}
+
+
+#[test]
+fn test_link_for_locals()
+{
+	let main = "
+		#for i in 0..3 {
+			#d8 far + i
+		}";
+
+	let (binary, _, _) = link(&[("main.asm", main), ("lib.asm", "far = 0x10")], &["main.asm", "lib.asm"]);
+	assert_eq!(binary, Some(vec![0x10, 0x11, 0x12]));
+}
+
+
+#[test]
+fn test_link_enum_imports()
+{
+	let main = "
+		#enum Port { A = base, B, C }
+		#enum Flag step 2 { Off, On = base, Auto }
+		#d8 Port.A, Port.B, Port.C, Flag.Off, Flag.Auto";
+
+	let (binary, _, _) = link(&[("main.asm", main), ("lib.asm", "base = 0x10")], &["main.asm", "lib.asm"]);
+	assert_eq!(binary, Some(vec![0x10, 0x11, 0x12, 0x00, 0x12]));
+}
+
+
+#[test]
+fn test_link_conflicting_functions()
+{
+	let a = "
+		#fn offset(x) => x + 1
+		#d8 offset(1)";
+
+	let b = "
+		#fn offset(x) => x + 2
+		#d8 offset(1)";
+
+	let (binary, report, fileserver) = link(&[("a.asm", a), ("b.asm", b)], &["a.asm", "b.asm"]);
+	assert_eq!(binary, None);
+	assert!(report.has_first_error_at(&fileserver, "b.asm", 1, "conflicting definitions of function `offset`"));
+
+	let (binary, _, _) = link(&[("a.asm", a), ("b.asm", a)], &["a.asm", "b.asm"]);
+	assert_eq!(binary, Some(vec![0x02, 0x02]));
+}
+
+
+#[test]
+fn test_object_round_trip()
+{
+	let src = "
+		#include \"cpu.asm\"
+		msg = \"hi\\n\"
+		neg = -0x12
+		#fn twice(x) => { let y = x + x, y }
+		start:
+			ld (neg + 0x12)`8
+			#d msg, start`8, (1 == 1 ? 2 : 3)`8
+		#for i in -1..1 {
+			#d8 start + i
+		}";
+
+	let mut fileserver = util::FileServerMock::new();
+	fileserver.add("cpu.asm", CPU);
+	fileserver.add("main.asm", src);
+
+	let text = make_object(&fileserver, "main.asm");
+
+	let object = asm::Object::deserialize(diagn::RcReport::new(), "main.o", &text).unwrap();
+	assert_eq!(object.serialize(), text);
+
+	let report = diagn::RcReport::new();
+	assert!(asm::Object::deserialize(report.clone(), "main.o", "(customasm-object 1 (bank))").is_err());
+	assert!(report_text(&report, &fileserver).contains("invalid object file `main.o`"));
+}
\ No newline at end of file
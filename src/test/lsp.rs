@@ -0,0 +1,204 @@
+use crate::*;
+use crate::lsp::Json;
+
+
+static SRC: &str = "#ruledef
+{
+	ld {x} => 0x55 @ x`8
+}
+
+#fn add1(x) => x + 1
+
+start:
+	ld VALUE
+.loop:
+	ld .loop
+	ld add1(start.loop)
+
+VALUE = 0x42";
+
+
+fn open(server: &mut lsp::Server, uri: &str, text: &str) -> Vec<Json>
+{
+	server.handle_message(&Json::parse(&format!(
+		"{{\"jsonrpc\":\"2.0\",\"method\":\"textDocument/didOpen\",\"params\":{{\"textDocument\":{{\"uri\":\"{}\",\"languageId\":\"customasm\",\"version\":1,\"text\":{}}}}}}}",
+		uri,
+		Json::from(text).to_string())).unwrap())
+}
+
+
+fn request(server: &mut lsp::Server, method: &str, uri: &str, line: usize, character: usize) -> Json
+{
+	let responses = server.handle_message(&Json::parse(&format!(
+		"{{\"jsonrpc\":\"2.0\",\"id\":7,\"method\":\"{}\",\"params\":{{\"textDocument\":{{\"uri\":\"{}\"}},\"position\":{{\"line\":{},\"character\":{}}}}}}}",
+		method,
+		uri,
+		line,
+		character)).unwrap());
+
+	assert_eq!(responses.len(), 1);
+	assert_eq!(responses[0].get("id").as_usize(), Some(7));
+	responses[0].get("result").clone()
+}
+
+
+fn get_range(location: &Json) -> (usize, usize, usize, usize)
+{
+	let range = location.get("range");
+	(
+		range.get("start").get("line").as_usize().unwrap(),
+		range.get("start").get("character").as_usize().unwrap(),
+		range.get("end").get("line").as_usize().unwrap(),
+		range.get("end").get("character").as_usize().unwrap(),
+	)
+}
+
+
+#[test]
+fn test_json()
+{
+	let src = "{\"a\": [1, -2.5, true, false, null], \"b\": \"x\\\"\\n\\u00e9\\ud83d\\ude00\", \"c\": {}}";
+	let json = Json::parse(src).unwrap();
+
+	assert_eq!(json.get("a").as_array().unwrap().len(), 5);
+	assert_eq!(json.get("a").as_array().unwrap()[0].as_usize(), Some(1));
+	assert_eq!(json.get("b").as_str(), Some("x\"\n\u{e9}\u{1f600}"));
+	assert!(json.get("d").is_null());
+	assert_eq!(Json::parse(&json.to_string()).unwrap(), json);
+
+	assert!(Json::parse("{\"a\": }").is_err());
+	assert!(Json::parse("[1, 2").is_err());
+	assert!(Json::parse("1 2").is_err());
+}
+
+
+#[test]
+fn test_uri()
+{
+	assert_eq!(lsp::uri_to_filename("file:///home/user/my%20src/main.asm"), "home/user/my src/main.asm");
+	assert_eq!(lsp::uri_to_filename("file:///c%3A/src/main.asm"), "c:/src/main.asm");
+	assert_eq!(lsp::filename_to_uri("home/user/my src/main.asm"), "file:///home/user/my%20src/main.asm");
+	assert_eq!(lsp::filename_to_uri("c:/src/main.asm"), "file:///c%3A/src/main.asm");
+}
+
+
+#[test]
+fn test_initialize()
+{
+	let mut server = lsp::Server::new();
+
+	let responses = server.handle_message(&Json::parse(
+		"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"initialize\",\"params\":{}}").unwrap());
+
+	assert_eq!(responses.len(), 1);
+	let capabilities = responses[0].get("result").get("capabilities");
+	assert_eq!(capabilities.get("definitionProvider"), &Json::Bool(true));
+	assert_eq!(capabilities.get("hoverProvider"), &Json::Bool(true));
+
+	let responses = server.handle_message(&Json::parse(
+		"{\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"unknown/method\"}").unwrap());
+
+	assert_eq!(responses[0].get("error").get("message").as_str(), Some("method not found"));
+}
+
+
+#[test]
+fn test_diagnostics()
+{
+	let mut server = lsp::Server::new();
+
+	let notifications = open(&mut server, "file:///src/main.asm", SRC);
+	assert_eq!(notifications.len(), 1);
+	assert_eq!(notifications[0].get("method").as_str(), Some("textDocument/publishDiagnostics"));
+	assert_eq!(notifications[0].get("params").get("uri").as_str(), Some("file:///src/main.asm"));
+	assert_eq!(notifications[0].get("params").get("diagnostics").as_array().unwrap().len(), 0);
+
+	let notifications = open(&mut server, "file:///src/main.asm", "x:\nx:");
+	let diagnostics = notifications[0].get("params").get("diagnostics").as_array().unwrap();
+	assert_eq!(diagnostics.len(), 1);
+	assert_eq!(diagnostics[0].get("message").as_str(), Some("duplicate symbol"));
+	assert_eq!(diagnostics[0].get("severity").as_usize(), Some(1));
+	assert_eq!(get_range(&diagnostics[0]), (1, 0, 1, 2));
+
+	let related = diagnostics[0].get("relatedInformation").as_array().unwrap();
+	assert_eq!(related.len(), 1);
+	assert_eq!(related[0].get("message").as_str(), Some("first declared here"));
+	assert_eq!(get_range(related[0].get("location")), (0, 0, 0, 2));
+}
+
+
+#[test]
+fn test_definition()
+{
+	let mut server = lsp::Server::new();
+	open(&mut server, "file:///src/main.asm", SRC);
+
+	let uri = "file:///src/main.asm";
+
+	let location = request(&mut server, "textDocument/definition", uri, 8, 6);
+	assert_eq!(location.get("uri").as_str(), Some(uri));
+	assert_eq!(get_range(&location), (13, 0, 13, 5));
+
+	let location = request(&mut server, "textDocument/definition", uri, 10, 6);
+	assert_eq!(get_range(&location), (9, 0, 9, 6));
+
+	let location = request(&mut server, "textDocument/definition", uri, 11, 16);
+	assert_eq!(get_range(&location), (9, 0, 9, 6));
+
+	let location = request(&mut server, "textDocument/definition", uri, 11, 10);
+	assert_eq!(get_range(&location), (7, 0, 7, 6));
+
+	let location = request(&mut server, "textDocument/definition", uri, 11, 5);
+	assert_eq!(get_range(&location).0, 5);
+
+	assert!(request(&mut server, "textDocument/definition", uri, 4, 0).is_null());
+}
+
+
+#[test]
+fn test_hover()
+{
+	let mut server = lsp::Server::new();
+	open(&mut server, "file:///src/main.asm", SRC);
+
+	let uri = "file:///src/main.asm";
+
+	let hover = request(&mut server, "textDocument/hover", uri, 8, 6);
+	assert_eq!(hover.get("contents").get("value").as_str(), Some("```\n(constant) VALUE = 0x42\n```"));
+
+	let hover = request(&mut server, "textDocument/hover", uri, 11, 15);
+	assert_eq!(hover.get("contents").get("value").as_str(), Some("```\n(label) start.loop = 0x2\n```"));
+
+	let hover = request(&mut server, "textDocument/hover", uri, 11, 5);
+	assert_eq!(hover.get("contents").get("value").as_str(), Some("```\n#fn add1(x)\n```"));
+
+	// Keeps the last successful result while the document has errors.
+	open(&mut server, uri, &format!("{}\nld", SRC));
+	let hover = request(&mut server, "textDocument/hover", uri, 8, 6);
+	assert_eq!(hover.get("contents").get("value").as_str(), Some("```\n(constant) VALUE = 0x42\n```"));
+}
+
+
+#[test]
+fn test_run()
+{
+	let input = [
+		"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"initialize\",\"params\":{}}",
+		"{\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"shutdown\"}",
+		"{\"jsonrpc\":\"2.0\",\"method\":\"exit\"}",
+	];
+
+	let mut stream = Vec::new();
+	for msg in &input
+	{
+		stream.extend(format!("Content-Length: {}\r\n\r\n{}", msg.len(), msg).bytes());
+	}
+
+	let mut output = Vec::new();
+	let exit_code = lsp::run(&mut std::io::Cursor::new(stream), &mut output);
+	assert_eq!(exit_code, 0);
+
+	let output = String::from_utf8(output).unwrap();
+	assert!(output.starts_with("Content-Length: "));
+	assert!(output.ends_with("{\"jsonrpc\":\"2.0\",\"id\":2,\"result\":null}"));
+}
\ No newline at end of file
@@ -1,11 +1,15 @@
 use crate::*;
 
 
+mod driver;
 mod examples;
 mod excerpt;
 mod expr;
 mod file;
 mod lib;
+mod link;
+mod lsp;
+mod report;
 
 
 pub enum ExpectedResult<T>
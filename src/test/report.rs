@@ -0,0 +1,39 @@
+use crate::*;
+use std::rc::Rc;
+
+
+#[test]
+fn test_json_format()
+{
+	let mut fileserver = util::FileServerMock::new();
+	fileserver.add("main.asm", "ld x\n\tld \"y\"");
+
+	let filename = Rc::new("main.asm".to_string());
+
+	let report = diagn::RcReport::new();
+	report.error("no \"input\" files");
+
+	{
+		let _guard = report.push_parent("failed to resolve instruction", &diagn::Span::new(filename.clone(), 5, 12));
+		report.error_span("unknown symbol", &diagn::Span::new(filename.clone(), 9, 12));
+		report.note_span("first declared here", &diagn::Span::new(filename.clone(), 0, 4));
+	}
+
+	let mut output = Vec::<u8>::new();
+	report.print_all_json(&mut output, &fileserver);
+
+	assert_eq!(
+		String::from_utf8(output).unwrap(),
+		concat!(
+			"[",
+			"{\"kind\":\"error\",\"descr\":\"no \\\"input\\\" files\",\"file\":null,\"range\":null,\"inner\":[]},",
+			"{\"kind\":\"error\",\"descr\":\"failed to resolve instruction\",\"file\":\"main.asm\",",
+				"\"range\":{\"start\":{\"line\":2,\"column\":1},\"end\":{\"line\":2,\"column\":8}},\"inner\":[",
+				"{\"kind\":\"error\",\"descr\":\"unknown symbol\",\"file\":\"main.asm\",",
+					"\"range\":{\"start\":{\"line\":2,\"column\":5},\"end\":{\"line\":2,\"column\":8}},\"inner\":[]}]},",
+			"{\"kind\":\"error\",\"descr\":\"failed to resolve instruction\",\"file\":\"main.asm\",",
+				"\"range\":{\"start\":{\"line\":2,\"column\":1},\"end\":{\"line\":2,\"column\":8}},\"inner\":[",
+				"{\"kind\":\"note\",\"descr\":\"first declared here\",\"file\":\"main.asm\",",
+					"\"range\":{\"start\":{\"line\":1,\"column\":1},\"end\":{\"line\":1,\"column\":5}},\"inner\":[]}]}",
+			"]\n"));
+}
\ No newline at end of file
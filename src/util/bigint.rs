@@ -28,6 +28,12 @@ impl BigInt
     }
 
 
+    pub fn to_str_radix(&self, radix: u32) -> String
+    {
+        self.bigint.to_str_radix(radix)
+    }
+
+
     pub fn from_bytes_be(bytes: &[u8]) -> BigInt
     {
         let bigint = num_bigint::BigInt::from_signed_bytes_be(&bytes);
@@ -207,6 +213,48 @@ impl BigInt
     }
 
 
+    /// Interprets the lowest `from` bits as a two's complement
+    /// number, and gives the result a size of `to` bits.
+    pub fn sign_extend(&self, from: usize, to: usize) -> BigInt
+    {
+        let mut result = self.slice(from, 0);
+        if from > 0 && result.get_bit(from - 1)
+        {
+            result = &result - &BigInt::from(1).shl(from);
+        }
+
+        result.size = Some(to);
+        result
+    }
+
+
+    pub fn reverse_bits(&self, size: usize) -> BigInt
+    {
+        use num_traits::Zero;
+        use num_traits::One;
+
+        let mut result = num_bigint::BigInt::zero();
+        for i in 0..size
+        {
+            result = result << 1;
+            if self.get_bit(i)
+                { result = result + num_bigint::BigInt::one(); }
+        }
+
+        BigInt::new(result, Some(size))
+    }
+
+
+    pub fn count_ones(&self) -> usize
+    {
+        self.bigint
+            .to_bytes_le().1
+            .iter()
+            .map(|byte| byte.count_ones() as usize)
+            .sum()
+    }
+
+
     pub fn convert_le(&self) -> BigInt
     {
         let mut be_bytes = self.bigint.to_bytes_le().1;
@@ -110,6 +110,32 @@ impl BitVec
     }
 	
 	
+	/// Returns a copy of the given range of bits, along
+	/// with the spans that start inside of it.
+	pub fn get_slice(&self, start: usize, size: usize) -> BitVec
+	{
+		let mut result = BitVec::new();
+
+		for i in 0..size
+			{ result.write(i, self.read(start + i)); }
+
+		for span in &self.spans
+		{
+			if let Some(offset) = span.offset
+			{
+				if offset >= start && offset < start + size
+				{
+					let mut new_span = span.clone();
+					new_span.offset = Some(offset - start);
+					result.spans.push(new_span);
+				}
+			}
+		}
+
+		result
+	}
+	
+	
 	pub fn truncate(&mut self, new_len: usize)
 	{
 		while self.bits.len() > new_len
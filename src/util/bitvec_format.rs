@@ -170,36 +170,34 @@ impl util::BitVec
     }
     
 	
-	pub fn format_mif(&self) -> String
+	/// Formats the output as an Altera memory initialization
+	/// file, with addresses counted in words from the given
+	/// base address.
+	pub fn format_mif(&self, wordsize: usize, base_addr: usize) -> String
 	{
 		let mut result = String::new();
 		
-		let byte_num = self.len() / 8 + if self.len() % 8 != 0 { 1 } else { 0 };
+		let word_num = self.len() / wordsize + if self.len() % wordsize != 0 { 1 } else { 0 };
+		let depth = base_addr + word_num;
 		
-		result.push_str(&format!("DEPTH = {};\n", byte_num));
-		result.push_str("WIDTH = 8;\n");
+		result.push_str(&format!("DEPTH = {};\n", depth));
+		result.push_str(&format!("WIDTH = {};\n", wordsize));
 		result.push_str("ADDRESS_RADIX = HEX;\n");
 		result.push_str("DATA_RADIX = HEX;\n");
 		result.push_str("\n");
 		result.push_str("CONTENT\n");
 		result.push_str("BEGIN\n");
 		
-		let addr_max_width = format!("{:x}", byte_num - 1).len();
+		let addr_max_width = format!("{:x}", depth.saturating_sub(1)).len();
 		
 		let mut index = 0;
 		while index < self.len()
 		{
-			result.push_str(&format!(" {:1$X}: ", index / 8, addr_max_width));
-			
-			let mut byte: u8 = 0;
-			for _ in 0..8
-			{
-				byte <<= 1;
-				byte |= if self.read(index) { 1 } else { 0 };
-				index += 1;
-			}
+			result.push_str(&format!(" {:1$X}: ", base_addr + index / wordsize, addr_max_width));
 			
-			result.push_str(&format!("{:02X};\n", byte));
+			let word = self.format_word(index, wordsize, 16).to_uppercase();
+			result.push_str(&format!("{};\n", word));
+			index += wordsize;
 		}
 		
 		result.push_str("END;");
@@ -207,27 +205,32 @@ impl util::BitVec
 	}
 	
 	
-	pub fn format_intelhex(&self) -> String
+	/// Formats the output as Intel HEX, starting at the given
+	/// byte address, and emitting extended linear address
+	/// records to reach past the first 64 KiB.
+	pub fn format_intelhex(&self, base_addr: usize) -> String
 	{
 		let mut result = String::new();
 		
 		let mut bytes_left = self.len() / 8 + if self.len() % 8 != 0 { 1 } else { 0 };
+		let mut cur_upper_addr = 0;
 		
 		let mut index = 0;
 		while index < self.len()
 		{
-			let bytes_in_row = if bytes_left > 32 { 32 } else { bytes_left };
+			let addr = base_addr + index / 8;
 			
-			result.push(':');
-			result.push_str(&format!("{:02X}", bytes_in_row));
-			result.push_str(&format!("{:04X}", index / 8));
-			result.push_str("00");
+			if addr >> 16 != cur_upper_addr
+			{
+				cur_upper_addr = addr >> 16;
+				push_intelhex_record(&mut result, 0, 4, &[(cur_upper_addr >> 8) as u8, cur_upper_addr as u8]);
+			}
 			
-			let mut checksum = 0_u8;
-			checksum = checksum.wrapping_add(bytes_in_row as u8);
-			checksum = checksum.wrapping_add(((index / 8) >> 8) as u8);
-			checksum = checksum.wrapping_add((index / 8) as u8);
+			// Rows don't cross a 64 KiB boundary
+			let bytes_to_boundary = 0x10000 - (addr & 0xffff);
+			let bytes_in_row = std::cmp::min(std::cmp::min(bytes_left, 32), bytes_to_boundary);
 			
+			let mut bytes = Vec::new();
 			for _ in 0..bytes_in_row
 			{
 				let mut byte: u8 = 0;
@@ -238,13 +241,11 @@ impl util::BitVec
 					index += 1;
 				}
 				
-				result.push_str(&format!("{:02X}", byte));
-				checksum = checksum.wrapping_add(byte);
+				bytes.push(byte);
 			}
 			
+			push_intelhex_record(&mut result, addr & 0xffff, 0, &bytes);
 			bytes_left -= bytes_in_row;
-			result.push_str(&format!("{:02X}", (!checksum).wrapping_add(1)));
-			result.push('\n');
 		}
 		
 		result.push_str(":00000001FF");
@@ -252,6 +253,81 @@ impl util::BitVec
 	}
 	
 	
+	/// Formats the output as a Xilinx coefficients file,
+	/// with one word per entry.
+	pub fn format_coe(&self, wordsize: usize) -> String
+	{
+		let mut result = String::new();
+		
+		result.push_str("memory_initialization_radix=16;\n");
+		result.push_str("memory_initialization_vector=\n");
+		
+		let mut index = 0;
+		while index < self.len()
+		{
+			result.push_str(&self.format_word(index, wordsize, 16));
+			index += wordsize;
+			
+			if index < self.len()
+				{ result.push_str(",\n"); }
+		}
+		
+		result.push_str(";\n");
+		result
+	}
+	
+	
+	/// Formats the output for Verilog's `$readmemh` (radix 16)
+	/// or `$readmemb` (radix 2), with an `@addr` marker
+	/// at the start of every row, counted in words from
+	/// the given base address.
+	pub fn format_readmem(&self, wordsize: usize, radix: usize, base_addr: usize) -> String
+	{
+		let mut result = String::new();
+		
+		let words_per_row = if radix == 2 { 4 } else { 16 };
+		
+		let mut index = 0;
+		while index < self.len()
+		{
+			let word_index = index / wordsize;
+			if word_index % words_per_row == 0
+				{ result.push_str(&format!("@{:x}", base_addr + word_index)); }
+			
+			result.push(' ');
+			result.push_str(&self.format_word(index, wordsize, radix));
+			index += wordsize;
+			
+			if (index / wordsize) % words_per_row == 0 || index >= self.len()
+				{ result.push('\n'); }
+		}
+		
+		result
+	}
+	
+	
+	fn format_word(&self, index: usize, wordsize: usize, radix: usize) -> String
+	{
+		match radix
+		{
+			2 => (0..wordsize)
+				.map(|i| if self.read(index + i) { '1' } else { '0' })
+				.collect(),
+			
+			16 =>
+			{
+				let mut word = util::BigInt::from(0);
+				for i in 0..wordsize
+					{ word = word.set_bit(wordsize - 1 - i, self.read(index + i)); }
+				
+				format!("{:0>1$}", format!("{:x}", word), (wordsize + 3) / 4)
+			}
+			
+			_ => panic!("invalid radix")
+		}
+	}
+	
+	
 	pub fn format_comma(&self, radix: usize) -> String
 	{
 		let mut result = String::new();
@@ -527,4 +603,28 @@ impl util::BitVec
         
         result
     }
+}
+
+
+fn push_intelhex_record(result: &mut String, addr: usize, kind: u8, data: &[u8])
+{
+	result.push(':');
+	result.push_str(&format!("{:02X}", data.len()));
+	result.push_str(&format!("{:04X}", addr));
+	result.push_str(&format!("{:02X}", kind));
+	
+	let mut checksum = 0_u8;
+	checksum = checksum.wrapping_add(data.len() as u8);
+	checksum = checksum.wrapping_add((addr >> 8) as u8);
+	checksum = checksum.wrapping_add(addr as u8);
+	checksum = checksum.wrapping_add(kind);
+	
+	for byte in data
+	{
+		result.push_str(&format!("{:02X}", byte));
+		checksum = checksum.wrapping_add(*byte);
+	}
+	
+	result.push_str(&format!("{:02X}", (!checksum).wrapping_add(1)));
+	result.push('\n');
 }
\ No newline at end of file
@@ -0,0 +1,290 @@
+/// A minimal writer for 32-bit little-endian ELF files,
+/// holding sections of assembled data and a symbol table.
+/// Every section is also given a loadable segment, so that
+/// tools reading program headers see the same layout.
+pub struct ElfWriter
+{
+	pub sections: Vec<ElfSection>,
+	pub symbols: Vec<ElfSymbol>,
+}
+
+
+pub struct ElfSection
+{
+	pub name: String,
+	pub addr: u32,
+	pub flags: u32,
+	pub kind: ElfSectionKind,
+}
+
+
+pub enum ElfSectionKind
+{
+	ProgBits(Vec<u8>),
+	NoBits(u32),
+}
+
+
+pub struct ElfSymbol
+{
+	pub name: String,
+	pub value: u32,
+	pub is_local: bool,
+	pub is_object: bool,
+
+	/// Index into `ElfWriter::sections`,
+	/// or `None` for an absolute symbol.
+	pub section: Option<usize>,
+}
+
+
+pub const SHF_WRITE: u32 = 0x1;
+pub const SHF_ALLOC: u32 = 0x2;
+pub const SHF_EXECINSTR: u32 = 0x4;
+
+const SHT_PROGBITS: u32 = 1;
+const SHT_SYMTAB: u32 = 2;
+const SHT_STRTAB: u32 = 3;
+const SHT_NOBITS: u32 = 8;
+
+const SHN_ABS: u16 = 0xfff1;
+
+const PT_LOAD: u32 = 1;
+
+const PF_X: u32 = 0x1;
+const PF_W: u32 = 0x2;
+const PF_R: u32 = 0x4;
+
+const EHDR_SIZE: usize = 52;
+const PHDR_SIZE: usize = 32;
+const SHDR_SIZE: usize = 40;
+const SYM_SIZE: usize = 16;
+
+
+struct StringTable
+{
+	data: Vec<u8>,
+}
+
+
+impl StringTable
+{
+	fn new() -> StringTable
+	{
+		StringTable { data: vec![0] }
+	}
+
+
+	fn add(&mut self, s: &str) -> u32
+	{
+		let index = self.data.len() as u32;
+		self.data.extend_from_slice(s.as_bytes());
+		self.data.push(0);
+		index
+	}
+}
+
+
+impl ElfWriter
+{
+	pub fn new() -> ElfWriter
+	{
+		ElfWriter
+		{
+			sections: Vec::new(),
+			symbols: Vec::new(),
+		}
+	}
+
+
+	pub fn build(&self) -> Vec<u8>
+	{
+		let mut shstrtab = StringTable::new();
+		let mut strtab = StringTable::new();
+
+		// Local symbols must come before global ones,
+		// with `sh_info` pointing at the first global.
+		let mut symbols: Vec<&ElfSymbol> = self.symbols.iter().collect();
+		symbols.sort_by_key(|s| !s.is_local);
+		let first_global = 1 + symbols.iter().filter(|s| s.is_local).count();
+
+		let mut symtab = vec![0; SYM_SIZE];
+		for symbol in &symbols
+		{
+			let bind = if symbol.is_local { 0 } else { 1 };
+			let kind = if symbol.is_object { 1 } else { 0 };
+			let shndx = match symbol.section
+			{
+				Some(index) => (index + 1) as u16,
+				None => SHN_ABS,
+			};
+
+			write_u32(&mut symtab, strtab.add(&symbol.name));
+			write_u32(&mut symtab, symbol.value);
+			write_u32(&mut symtab, 0);
+			symtab.push((bind << 4) | kind);
+			symtab.push(0);
+			write_u16(&mut symtab, shndx);
+		}
+
+		// Lay out the section contents after
+		// the file and program headers.
+		let contents_offset = EHDR_SIZE + self.sections.len() * PHDR_SIZE;
+		let mut contents = Vec::new();
+		let mut headers = vec![0; SHDR_SIZE];
+		let mut program_headers = Vec::new();
+
+		for section in &self.sections
+		{
+			align(&mut contents, 4);
+			let offset = contents_offset + contents.len();
+
+			let (kind, size, file_size) = match section.kind
+			{
+				ElfSectionKind::ProgBits(ref data) =>
+				{
+					contents.extend_from_slice(data);
+					(SHT_PROGBITS, data.len() as u32, data.len() as u32)
+				}
+
+				ElfSectionKind::NoBits(size) => (SHT_NOBITS, size, 0),
+			};
+
+			let mut flags = PF_R;
+			if section.flags & SHF_WRITE != 0
+				{ flags |= PF_W; }
+			if section.flags & SHF_EXECINSTR != 0
+				{ flags |= PF_X; }
+
+			for value in &[PT_LOAD, offset as u32, section.addr, section.addr, file_size, size, flags, 1]
+			{
+				write_u32(&mut program_headers, *value);
+			}
+
+			write_section_header(
+				&mut headers,
+				shstrtab.add(&section.name),
+				kind,
+				section.flags,
+				section.addr,
+				offset as u32,
+				size,
+				0,
+				0,
+				1,
+				0);
+		}
+
+		let symtab_index = self.sections.len() + 1;
+
+		let tables = [
+			(".symtab", SHT_SYMTAB, &symtab, (symtab_index + 1) as u32, first_global as u32, 4, SYM_SIZE as u32),
+			(".strtab", SHT_STRTAB, &strtab.data, 0, 0, 1, 0),
+		];
+
+		for (name, kind, data, link, info, addralign, entsize) in tables.iter()
+		{
+			align(&mut contents, 4);
+			let offset = contents_offset + contents.len();
+			contents.extend_from_slice(data);
+
+			write_section_header(
+				&mut headers,
+				shstrtab.add(name),
+				*kind,
+				0,
+				0,
+				offset as u32,
+				data.len() as u32,
+				*link,
+				*info,
+				*addralign,
+				*entsize);
+		}
+
+		let shstrtab_name = shstrtab.add(".shstrtab");
+		align(&mut contents, 4);
+		let shstrtab_offset = contents_offset + contents.len();
+		contents.extend_from_slice(&shstrtab.data);
+
+		write_section_header(
+			&mut headers,
+			shstrtab_name,
+			SHT_STRTAB,
+			0,
+			0,
+			shstrtab_offset as u32,
+			shstrtab.data.len() as u32,
+			0,
+			0,
+			1,
+			0);
+
+		align(&mut contents, 4);
+		let shoff = contents_offset + contents.len();
+		let shnum = headers.len() / SHDR_SIZE;
+
+		let mut result = Vec::new();
+		result.extend_from_slice(&[0x7f, b'E', b'L', b'F', 1, 1, 1, 0]);
+		result.extend_from_slice(&[0; 8]);
+		write_u16(&mut result, 2); // e_type: executable
+		write_u16(&mut result, 0); // e_machine: none
+		write_u32(&mut result, 1); // e_version
+		write_u32(&mut result, 0); // e_entry
+		write_u32(&mut result, if self.sections.len() > 0 { EHDR_SIZE as u32 } else { 0 });
+		write_u32(&mut result, shoff as u32);
+		write_u32(&mut result, 0); // e_flags
+		write_u16(&mut result, EHDR_SIZE as u16);
+		write_u16(&mut result, PHDR_SIZE as u16);
+		write_u16(&mut result, self.sections.len() as u16);
+		write_u16(&mut result, SHDR_SIZE as u16);
+		write_u16(&mut result, shnum as u16);
+		write_u16(&mut result, (shnum - 1) as u16);
+
+		result.extend_from_slice(&program_headers);
+		result.extend_from_slice(&contents);
+		result.extend_from_slice(&headers);
+		result
+	}
+}
+
+
+fn write_section_header(
+	headers: &mut Vec<u8>,
+	name: u32,
+	kind: u32,
+	flags: u32,
+	addr: u32,
+	offset: u32,
+	size: u32,
+	link: u32,
+	info: u32,
+	addralign: u32,
+	entsize: u32)
+{
+	for value in &[name, kind, flags, addr, offset, size, link, info, addralign, entsize]
+	{
+		write_u32(headers, *value);
+	}
+}
+
+
+fn write_u16(result: &mut Vec<u8>, value: u16)
+{
+	result.extend_from_slice(&value.to_le_bytes());
+}
+
+
+fn write_u32(result: &mut Vec<u8>, value: u32)
+{
+	result.extend_from_slice(&value.to_le_bytes());
+}
+
+
+fn align(result: &mut Vec<u8>, alignment: usize)
+{
+	while result.len() % alignment != 0
+	{
+		result.push(0);
+	}
+}
\ No newline at end of file
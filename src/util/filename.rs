@@ -1,4 +1,5 @@
-use crate::diagn::{Span, RcReport};
+use crate::diagn::{Span, RcReport, Message};
+use crate::util::FileServer;
 use std::path::{PathBuf, Component};
 
 
@@ -91,4 +92,48 @@ pub fn filename_navigate(report: RcReport, current: &str, nav: &str, span: &Span
 	}
 
 	Ok(new_filename)
+}
+
+
+/// Resolves a filename relative to the current file, like
+/// `filename_navigate`, but falls back to looking under each
+/// of the include paths in order when that file doesn't exist.
+pub fn filename_search(
+	report: RcReport,
+	fileserver: &dyn FileServer,
+	include_paths: &[String],
+	current: &str,
+	nav: &str,
+	span: &Span)
+	-> Result<String, ()>
+{
+	// Standard library files are served by name
+	if crate::util::is_std_filename(nav)
+		{ return Ok(nav.to_string()); }
+	
+	let new_filename = filename_navigate(report.clone(), current, nav, span)?;
+	
+	let nav = nav.replace("\\", "/");
+	if include_paths.len() == 0 || nav.starts_with("/") || fileserver.exists(&new_filename)
+		{ return Ok(new_filename); }
+	
+	let mut tried = vec![new_filename];
+	for include_path in include_paths
+	{
+		let include_path = include_path.replace("\\", "/");
+		let candidate = format!("{}/{}", include_path.trim_end_matches('/'), nav);
+		
+		if fileserver.exists(&candidate)
+			{ return Ok(candidate); }
+		
+		tried.push(candidate);
+	}
+	
+	let _guard = report.push_parent(format!("file not found: `{}`", nav), span);
+	report.push_multiple(tried
+		.iter()
+		.map(|candidate| Message::note(format!("tried `{}`", candidate)))
+		.collect());
+	
+	Err(())
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use crate::diagn::{Span, RcReport};
 use crate::util::CharCounter;
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{Read, Write};
@@ -25,6 +26,12 @@ pub trait FileServer
 	fn write_bytes(&mut self, report: RcReport, filename: &str, data: &Vec<u8>, span: Option<&Span>) -> Result<(), ()>;
 	
 	
+	fn get_modified_time(&self, _filename: &str) -> Option<std::time::SystemTime>
+	{
+		None
+	}
+	
+	
 	fn get_excerpt(&self, span: &Span) -> String
 	{
 		if let Ok(chars) = self.get_chars(RcReport::new(), &*span.file, None)
@@ -50,6 +57,25 @@ pub struct FileServerMock
 pub struct FileServerReal;
 
 
+/// Wraps another `FileServer`, recording every file that
+/// is read through it along with its modification time
+/// at the moment it was read.
+pub struct FileServerTracked<'a>
+{
+	inner: &'a mut dyn FileServer,
+	read_files: RefCell<Vec<(String, Option<std::time::SystemTime>)>>,
+}
+
+
+/// Serves the standard library embedded in the binary,
+/// under names like `<std/6502.asm>`, and forwards every
+/// other file to another `FileServer`.
+pub struct FileServerStd<'a>
+{
+	inner: &'a mut dyn FileServer,
+}
+
+
 impl FileServerMock
 {
 	pub fn new() -> FileServerMock
@@ -78,6 +104,47 @@ impl FileServerReal
 }
 
 
+impl<'a> FileServerTracked<'a>
+{
+	pub fn new(inner: &'a mut dyn FileServer) -> FileServerTracked<'a>
+	{
+		FileServerTracked
+		{
+			inner,
+			read_files: RefCell::new(Vec::new()),
+		}
+	}
+	
+	
+	pub fn into_read_files(self) -> Vec<(String, Option<std::time::SystemTime>)>
+	{
+		self.read_files.into_inner()
+	}
+	
+	
+	fn track(&self, filename: &str)
+	{
+		let mut read_files = self.read_files.borrow_mut();
+		if read_files.iter().any(|(f, _)| f == filename)
+			{ return; }
+		
+		read_files.push((filename.to_string(), self.inner.get_modified_time(filename)));
+	}
+}
+
+
+impl<'a> FileServerStd<'a>
+{
+	pub fn new(inner: &'a mut dyn FileServer) -> FileServerStd<'a>
+	{
+		FileServerStd
+		{
+			inner,
+		}
+	}
+}
+
+
 impl FileServer for FileServerMock
 {
 	fn exists(&self, filename: &str) -> bool
@@ -106,9 +173,17 @@ impl FileServer for FileServerMock
 
 impl FileServer for FileServerReal
 {
-	fn exists(&self, _filename: &str) -> bool
+	fn exists(&self, filename: &str) -> bool
 	{
-		unimplemented!()
+		Path::new(filename).exists()
+	}
+	
+	
+	fn get_modified_time(&self, filename: &str) -> Option<std::time::SystemTime>
+	{
+		std::fs::metadata(filename)
+			.and_then(|m| m.modified())
+			.ok()
 	}
 
 
@@ -153,6 +228,91 @@ impl FileServer for FileServerReal
 }
 
 
+
+impl<'a> FileServer for FileServerTracked<'a>
+{
+	fn exists(&self, filename: &str) -> bool
+	{
+		self.inner.exists(filename)
+	}
+	
+	
+	fn get_bytes(&self, report: RcReport, filename: &str, span: Option<&Span>) -> Result<Vec<u8>, ()>
+	{
+		self.track(filename);
+		self.inner.get_bytes(report, filename, span)
+	}
+	
+	
+	fn get_chars(&self, report: RcReport, filename: &str, span: Option<&Span>) -> Result<Vec<char>, ()>
+	{
+		self.track(filename);
+		self.inner.get_chars(report, filename, span)
+	}
+	
+	
+	fn write_bytes(&mut self, report: RcReport, filename: &str, data: &Vec<u8>, span: Option<&Span>) -> Result<(), ()>
+	{
+		self.inner.write_bytes(report, filename, data, span)
+	}
+	
+	
+	fn get_modified_time(&self, filename: &str) -> Option<std::time::SystemTime>
+	{
+		self.inner.get_modified_time(filename)
+	}
+}
+
+
+impl<'a> FileServer for FileServerStd<'a>
+{
+	fn exists(&self, filename: &str) -> bool
+	{
+		crate::util::get_std_file(filename).is_some() ||
+			self.inner.exists(filename)
+	}
+	
+	
+	fn get_bytes(&self, report: RcReport, filename: &str, span: Option<&Span>) -> Result<Vec<u8>, ()>
+	{
+		if crate::util::is_std_filename(filename)
+		{
+			return match crate::util::get_std_file(filename)
+			{
+				Some(contents) => Ok(contents.bytes().collect()),
+				None => Err(error(report, format!("unknown standard library file: `{}`", filename), span)),
+			};
+		}
+		
+		self.inner.get_bytes(report, filename, span)
+	}
+	
+	
+	fn get_chars(&self, report: RcReport, filename: &str, span: Option<&Span>) -> Result<Vec<char>, ()>
+	{
+		if crate::util::is_std_filename(filename)
+		{
+			let bytes = self.get_bytes(report, filename, span)?;
+			return Ok(String::from_utf8_lossy(&bytes).chars().collect());
+		}
+		
+		self.inner.get_chars(report, filename, span)
+	}
+	
+	
+	fn write_bytes(&mut self, report: RcReport, filename: &str, data: &Vec<u8>, span: Option<&Span>) -> Result<(), ()>
+	{
+		self.inner.write_bytes(report, filename, data, span)
+	}
+	
+	
+	fn get_modified_time(&self, filename: &str) -> Option<std::time::SystemTime>
+	{
+		self.inner.get_modified_time(filename)
+	}
+}
+
+
 fn error<S>(report: RcReport, descr: S, span: Option<&Span>)
 where S: Into<String>
 {
@@ -5,6 +5,8 @@ mod bitvec_format;
 mod fileserver;
 mod filename;
 mod windows_console;
+mod elf;
+mod stdlib;
 
 
 pub use self::char_counter::CharCounter;
@@ -14,6 +16,18 @@ pub use self::bitvec::BitVecSpan;
 pub use self::fileserver::FileServer;
 pub use self::fileserver::FileServerMock;
 pub use self::fileserver::FileServerReal;
+pub use self::fileserver::FileServerTracked;
+pub use self::fileserver::FileServerStd;
 pub use self::filename::filename_validate;
 pub use self::filename::filename_navigate;
-pub use self::windows_console::enable_windows_ansi_support;
\ No newline at end of file
+pub use self::filename::filename_search;
+pub use self::stdlib::is_std_filename;
+pub use self::stdlib::get_std_file;
+pub use self::windows_console::enable_windows_ansi_support;
+pub use self::elf::ElfWriter;
+pub use self::elf::ElfSection;
+pub use self::elf::ElfSectionKind;
+pub use self::elf::ElfSymbol;
+pub use self::elf::SHF_WRITE;
+pub use self::elf::SHF_ALLOC;
+pub use self::elf::SHF_EXECINSTR;
\ No newline at end of file
@@ -0,0 +1,25 @@
+/// The files of the standard library, embedded in the binary
+/// and included with names like `<std/6502.asm>`.
+static STD_FILES: &[(&str, &str)] = &[
+	("<std/6502.asm>",  include_str!("../../std/6502.asm")),
+	("<std/8080.asm>",  include_str!("../../std/8080.asm")),
+	("<std/avr.asm>",   include_str!("../../std/avr.asm")),
+	("<std/chip8.asm>", include_str!("../../std/chip8.asm")),
+	("<std/rv32i.asm>", include_str!("../../std/rv32i.asm")),
+	("<std/z80.asm>",   include_str!("../../std/z80.asm")),
+];
+
+
+pub fn is_std_filename(filename: &str) -> bool
+{
+	filename.starts_with("<") && filename.ends_with(">")
+}
+
+
+pub fn get_std_file(filename: &str) -> Option<&'static str>
+{
+	STD_FILES
+		.iter()
+		.find(|(name, _)| *name == filename)
+		.map(|(_, contents)| *contents)
+}
\ No newline at end of file
@@ -11,13 +11,16 @@ pub unsafe extern fn wasm_assemble(format: u32, src: *mut String) -> *mut String
 	let mut fileserver = util::FileServerMock::new();
 	fileserver.add("asm", src.clone());
 	
-	let assemble = |report: diagn::RcReport, fileserver: &util::FileServerMock, filename: &str| -> Result<String, ()>
+	let fileserver = util::FileServerStd::new(&mut fileserver);
+	
+	let assemble = |report: diagn::RcReport, fileserver: &dyn util::FileServer, filename: &str| -> Result<String, ()>
 	{
 		let mut asm = asm::Assembler::new();
 		asm.register_file(filename);
 		let output = asm.assemble(report.clone(), fileserver, 10)?;
 		
 		let binary = output.binary;
+		let state = output.state;
 		match format
 		{
 			 0 => Ok(binary.format_annotated_hex(fileserver)),
@@ -27,14 +30,19 @@ pub unsafe extern fn wasm_assemble(format: u32, src: *mut String) -> *mut String
 			 3 => Ok(binary.format_bindump ()),
 			 4 => Ok(binary.format_hexstr  ()),
 			 5 => Ok(binary.format_binstr  ()),
-			 6 => Ok(binary.format_mif     ()),
-			 7 => Ok(binary.format_intelhex()),
+			 6 => Ok(binary.format_mif     (8, 0)),
+			 7 => Ok(binary.format_intelhex(0)),
 			 8 => Ok(binary.format_comma   (10)),
 			 9 => Ok(binary.format_comma   (16)),
 			10 => Ok(binary.format_c_array (10)),
 			11 => Ok(binary.format_c_array (16)),
 			12 => Ok(binary.format_logisim (8)),
 			13 => Ok(binary.format_logisim (16)),
+			14 => Ok(state.format_srec     (&binary, None)),
+			15 => Ok(state.format_titxt    (&binary, None)),
+			16 => Ok(binary.format_coe     (state.get_output_wordsize())),
+			17 => Ok(binary.format_readmem (state.get_output_wordsize(), 16, 0)),
+			18 => Ok(binary.format_readmem (state.get_output_wordsize(), 2, 0)),
 			_ => unreachable!()
 		}
 	};
@@ -0,0 +1,141 @@
+; Intel 8080
+;
+; The full instruction set, with Intel mnemonics. The memory
+; operand addressed by HL is written `m`.
+
+
+#subruledef i8080_reg
+{
+	b => 0b000
+	c => 0b001
+	d => 0b010
+	e => 0b011
+	h => 0b100
+	l => 0b101
+	m => 0b110
+	a => 0b111
+}
+
+
+#subruledef i8080_pair
+{
+	b  => 0b00
+	d  => 0b01
+	h  => 0b10
+	sp => 0b11
+}
+
+
+#subruledef i8080_pair_psw
+{
+	b   => 0b00
+	d   => 0b01
+	h   => 0b10
+	psw => 0b11
+}
+
+
+#subruledef i8080_pair_bd
+{
+	b => 0b0
+	d => 0b1
+}
+
+
+#ruledef i8080
+{
+	mov {dst: i8080_reg}, {src: i8080_reg} =>
+	{
+		assert(dst != 0b110 || src != 0b110)
+		0b01 @ dst @ src
+	}
+
+	mvi  {dst: i8080_reg}, {imm: i8} => 0b00 @ dst @ 0b110 @ imm
+	lxi  {rp: i8080_pair}, {imm: i16} => 0b00 @ rp @ 0b0001 @ le(imm)
+	lda  {addr: u16} => 0x3a @ le(addr)
+	sta  {addr: u16} => 0x32 @ le(addr)
+	lhld {addr: u16} => 0x2a @ le(addr)
+	shld {addr: u16} => 0x22 @ le(addr)
+	ldax {rp: i8080_pair_bd} => 0b000 @ rp @ 0b1010
+	stax {rp: i8080_pair_bd} => 0b000 @ rp @ 0b0010
+	xchg => 0xeb
+
+	add {src: i8080_reg} => 0b10000 @ src
+	adc {src: i8080_reg} => 0b10001 @ src
+	sub {src: i8080_reg} => 0b10010 @ src
+	sbb {src: i8080_reg} => 0b10011 @ src
+	ana {src: i8080_reg} => 0b10100 @ src
+	xra {src: i8080_reg} => 0b10101 @ src
+	ora {src: i8080_reg} => 0b10110 @ src
+	cmp {src: i8080_reg} => 0b10111 @ src
+
+	adi {imm: i8} => 0xc6 @ imm
+	aci {imm: i8} => 0xce @ imm
+	sui {imm: i8} => 0xd6 @ imm
+	sbi {imm: i8} => 0xde @ imm
+	ani {imm: i8} => 0xe6 @ imm
+	xri {imm: i8} => 0xee @ imm
+	ori {imm: i8} => 0xf6 @ imm
+	cpi {imm: i8} => 0xfe @ imm
+
+	inr {dst: i8080_reg} => 0b00 @ dst @ 0b100
+	dcr {dst: i8080_reg} => 0b00 @ dst @ 0b101
+	inx {rp: i8080_pair} => 0b00 @ rp @ 0b0011
+	dcx {rp: i8080_pair} => 0b00 @ rp @ 0b1011
+	dad {rp: i8080_pair} => 0b00 @ rp @ 0b1001
+
+	daa => 0x27
+	rlc => 0x07
+	rrc => 0x0f
+	ral => 0x17
+	rar => 0x1f
+	cma => 0x2f
+	cmc => 0x3f
+	stc => 0x37
+
+	jmp {addr: u16} => 0xc3 @ le(addr)
+	jnz {addr: u16} => 0xc2 @ le(addr)
+	jz  {addr: u16} => 0xca @ le(addr)
+	jnc {addr: u16} => 0xd2 @ le(addr)
+	jc  {addr: u16} => 0xda @ le(addr)
+	jpo {addr: u16} => 0xe2 @ le(addr)
+	jpe {addr: u16} => 0xea @ le(addr)
+	jp  {addr: u16} => 0xf2 @ le(addr)
+	jm  {addr: u16} => 0xfa @ le(addr)
+
+	call {addr: u16} => 0xcd @ le(addr)
+	cnz  {addr: u16} => 0xc4 @ le(addr)
+	cz   {addr: u16} => 0xcc @ le(addr)
+	cnc  {addr: u16} => 0xd4 @ le(addr)
+	cc   {addr: u16} => 0xdc @ le(addr)
+	cpo  {addr: u16} => 0xe4 @ le(addr)
+	cpe  {addr: u16} => 0xec @ le(addr)
+	cp   {addr: u16} => 0xf4 @ le(addr)
+	cm   {addr: u16} => 0xfc @ le(addr)
+
+	ret => 0xc9
+	rnz => 0xc0
+	rz  => 0xc8
+	rnc => 0xd0
+	rc  => 0xd8
+	rpo => 0xe0
+	rpe => 0xe8
+	rp  => 0xf0
+	rm  => 0xf8
+
+	rst {n: u3} => 0b11 @ n @ 0b111
+
+	pchl => 0xe9
+	sphl => 0xf9
+	xthl => 0xe3
+	push {rp: i8080_pair_psw} => 0b11 @ rp @ 0b0101
+	pop  {rp: i8080_pair_psw} => 0b11 @ rp @ 0b0001
+
+	in  {port: u8} => 0xdb @ port
+	out {port: u8} => 0xd3 @ port
+
+	ei  => 0xfb
+	di  => 0xf3
+	hlt => 0x76
+	nop => 0x00
+}
\ No newline at end of file
@@ -0,0 +1,338 @@
+; Atmel AVR
+;
+; The classic 8-bit instruction set. Addresses are in bytes, as
+; with the GNU tools, so program memory should be declared with
+; 8-bit words; jump and call targets are converted to word
+; addresses when encoding. Instructions that only accept some of
+; the registers, like `ldi` or `adiw`, check their operands.
+
+
+#subruledef avr_reg
+{
+	r0  => 0`5
+	r1  => 1`5
+	r2  => 2`5
+	r3  => 3`5
+	r4  => 4`5
+	r5  => 5`5
+	r6  => 6`5
+	r7  => 7`5
+	r8  => 8`5
+	r9  => 9`5
+	r10 => 10`5
+	r11 => 11`5
+	r12 => 12`5
+	r13 => 13`5
+	r14 => 14`5
+	r15 => 15`5
+	r16 => 16`5
+	r17 => 17`5
+	r18 => 18`5
+	r19 => 19`5
+	r20 => 20`5
+	r21 => 21`5
+	r22 => 22`5
+	r23 => 23`5
+	r24 => 24`5
+	r25 => 25`5
+	r26 => 26`5
+	r27 => 27`5
+	r28 => 28`5
+	r29 => 29`5
+	r30 => 30`5
+	r31 => 31`5
+}
+
+
+#subruledef avr_reg_pair
+{
+	x => 26`5
+	y => 28`5
+	z => 30`5
+	{r: avr_reg} => r
+}
+
+
+#subruledef avr_branch
+{
+	{addr: u32} =>
+	{
+		offset = addr - $ - 2
+		assert(offset % 2 == 0)
+		assert(offset <=  126)
+		assert(offset >= -128)
+		(offset >> 1)`7
+	}
+}
+
+
+#subruledef avr_reljump
+{
+	{addr: u32} =>
+	{
+		offset = addr - $ - 2
+		assert(offset % 2 == 0)
+		assert(offset <=  0xffe)
+		assert(offset >= -0x1000)
+		(offset >> 1)`12
+	}
+}
+
+
+#subruledef avr_absjump
+{
+	{addr: u32} =>
+	{
+		assert(addr % 2 == 0)
+		assert(addr < 0x800000)
+		(addr >> 1)`22
+	}
+}
+
+
+#ruledef avr
+{
+	add  {rd: avr_reg}, {rr: avr_reg} => le(0b000011 @ rr[4:4] @ rd @ rr[3:0])
+	adc  {rd: avr_reg}, {rr: avr_reg} => le(0b000111 @ rr[4:4] @ rd @ rr[3:0])
+	sub  {rd: avr_reg}, {rr: avr_reg} => le(0b000110 @ rr[4:4] @ rd @ rr[3:0])
+	sbc  {rd: avr_reg}, {rr: avr_reg} => le(0b000010 @ rr[4:4] @ rd @ rr[3:0])
+	and  {rd: avr_reg}, {rr: avr_reg} => le(0b001000 @ rr[4:4] @ rd @ rr[3:0])
+	or   {rd: avr_reg}, {rr: avr_reg} => le(0b001010 @ rr[4:4] @ rd @ rr[3:0])
+	eor  {rd: avr_reg}, {rr: avr_reg} => le(0b001001 @ rr[4:4] @ rd @ rr[3:0])
+	cp   {rd: avr_reg}, {rr: avr_reg} => le(0b000101 @ rr[4:4] @ rd @ rr[3:0])
+	cpc  {rd: avr_reg}, {rr: avr_reg} => le(0b000001 @ rr[4:4] @ rd @ rr[3:0])
+	cpse {rd: avr_reg}, {rr: avr_reg} => le(0b000100 @ rr[4:4] @ rd @ rr[3:0])
+	mov  {rd: avr_reg}, {rr: avr_reg} => le(0b001011 @ rr[4:4] @ rd @ rr[3:0])
+	mul  {rd: avr_reg}, {rr: avr_reg} => le(0b100111 @ rr[4:4] @ rd @ rr[3:0])
+
+	lsl {rd: avr_reg} => le(0b000011 @ rd[4:4] @ rd @ rd[3:0])
+	rol {rd: avr_reg} => le(0b000111 @ rd[4:4] @ rd @ rd[3:0])
+	tst {rd: avr_reg} => le(0b001000 @ rd[4:4] @ rd @ rd[3:0])
+	clr {rd: avr_reg} => le(0b001001 @ rd[4:4] @ rd @ rd[3:0])
+
+	movw {rd: avr_reg}, {rr: avr_reg} =>
+	{
+		assert(rd % 2 == 0)
+		assert(rr % 2 == 0)
+		le(0x01 @ rd[4:1] @ rr[4:1])
+	}
+
+	muls {rd: avr_reg}, {rr: avr_reg} =>
+	{
+		assert(rd >= 16)
+		assert(rr >= 16)
+		le(0x02 @ rd[3:0] @ rr[3:0])
+	}
+
+	mulsu {rd: avr_reg}, {rr: avr_reg} =>
+	{
+		assert(rd >= 16 && rd <= 23)
+		assert(rr >= 16 && rr <= 23)
+		le(0x03 @ 0b0 @ rd[2:0] @ 0b0 @ rr[2:0])
+	}
+
+	fmul {rd: avr_reg}, {rr: avr_reg} =>
+	{
+		assert(rd >= 16 && rd <= 23)
+		assert(rr >= 16 && rr <= 23)
+		le(0x03 @ 0b0 @ rd[2:0] @ 0b1 @ rr[2:0])
+	}
+
+	fmuls {rd: avr_reg}, {rr: avr_reg} =>
+	{
+		assert(rd >= 16 && rd <= 23)
+		assert(rr >= 16 && rr <= 23)
+		le(0x03 @ 0b1 @ rd[2:0] @ 0b0 @ rr[2:0])
+	}
+
+	fmulsu {rd: avr_reg}, {rr: avr_reg} =>
+	{
+		assert(rd >= 16 && rd <= 23)
+		assert(rr >= 16 && rr <= 23)
+		le(0x03 @ 0b1 @ rd[2:0] @ 0b1 @ rr[2:0])
+	}
+
+	subi {rd: avr_reg}, {k: i8} =>
+	{
+		assert(rd >= 16)
+		le(0x5 @ k[7:4] @ rd[3:0] @ k[3:0])
+	}
+
+	sbci {rd: avr_reg}, {k: i8} =>
+	{
+		assert(rd >= 16)
+		le(0x4 @ k[7:4] @ rd[3:0] @ k[3:0])
+	}
+
+	andi {rd: avr_reg}, {k: i8} =>
+	{
+		assert(rd >= 16)
+		le(0x7 @ k[7:4] @ rd[3:0] @ k[3:0])
+	}
+
+	cbr {rd: avr_reg}, {k: i8} =>
+	{
+		assert(rd >= 16)
+		mask = (!k)`8
+		le(0x7 @ mask[7:4] @ rd[3:0] @ mask[3:0])
+	}
+
+	ori {rd: avr_reg}, {k: i8} =>
+	{
+		assert(rd >= 16)
+		le(0x6 @ k[7:4] @ rd[3:0] @ k[3:0])
+	}
+
+	sbr {rd: avr_reg}, {k: i8} =>
+	{
+		assert(rd >= 16)
+		le(0x6 @ k[7:4] @ rd[3:0] @ k[3:0])
+	}
+
+	cpi {rd: avr_reg}, {k: i8} =>
+	{
+		assert(rd >= 16)
+		le(0x3 @ k[7:4] @ rd[3:0] @ k[3:0])
+	}
+
+	ldi {rd: avr_reg}, {k: i8} =>
+	{
+		assert(rd >= 16)
+		le(0xe @ k[7:4] @ rd[3:0] @ k[3:0])
+	}
+
+	ser {rd: avr_reg} =>
+	{
+		assert(rd >= 16)
+		le(0xef @ rd[3:0] @ 0xf)
+	}
+
+	adiw {rd: avr_reg_pair}, {k: u6} =>
+	{
+		assert(rd >= 24 && rd % 2 == 0)
+		le(0x96 @ k[5:4] @ rd[2:1] @ k[3:0])
+	}
+
+	sbiw {rd: avr_reg_pair}, {k: u6} =>
+	{
+		assert(rd >= 24 && rd % 2 == 0)
+		le(0x97 @ k[5:4] @ rd[2:1] @ k[3:0])
+	}
+
+	com  {rd: avr_reg} => le(0b1001010 @ rd @ 0x0)
+	neg  {rd: avr_reg} => le(0b1001010 @ rd @ 0x1)
+	swap {rd: avr_reg} => le(0b1001010 @ rd @ 0x2)
+	inc  {rd: avr_reg} => le(0b1001010 @ rd @ 0x3)
+	asr  {rd: avr_reg} => le(0b1001010 @ rd @ 0x5)
+	lsr  {rd: avr_reg} => le(0b1001010 @ rd @ 0x6)
+	ror  {rd: avr_reg} => le(0b1001010 @ rd @ 0x7)
+	dec  {rd: avr_reg} => le(0b1001010 @ rd @ 0xa)
+	push {rr: avr_reg} => le(0b1001001 @ rr @ 0xf)
+	pop  {rd: avr_reg} => le(0b1001000 @ rd @ 0xf)
+
+	rjmp  {k: avr_reljump} => le(0xc @ k)
+	rcall {k: avr_reljump} => le(0xd @ k)
+	jmp   {k: avr_absjump} => le(0b1001010 @ k[21:17] @ 0b110 @ k[16:16]) @ le(k[15:0])
+	call  {k: avr_absjump} => le(0b1001010 @ k[21:17] @ 0b111 @ k[16:16]) @ le(k[15:0])
+	ijmp   => le(0x9409)
+	icall  => le(0x9509)
+	eijmp  => le(0x9419)
+	eicall => le(0x9519)
+	ret    => le(0x9508)
+	reti   => le(0x9518)
+
+	brbs {s: u3}, {k: avr_branch} => le(0b111100 @ k @ s)
+	brbc {s: u3}, {k: avr_branch} => le(0b111101 @ k @ s)
+	brcs {k: avr_branch} => le(0b111100 @ k @ 0b000)
+	brlo {k: avr_branch} => le(0b111100 @ k @ 0b000)
+	breq {k: avr_branch} => le(0b111100 @ k @ 0b001)
+	brmi {k: avr_branch} => le(0b111100 @ k @ 0b010)
+	brvs {k: avr_branch} => le(0b111100 @ k @ 0b011)
+	brlt {k: avr_branch} => le(0b111100 @ k @ 0b100)
+	brhs {k: avr_branch} => le(0b111100 @ k @ 0b101)
+	brts {k: avr_branch} => le(0b111100 @ k @ 0b110)
+	brie {k: avr_branch} => le(0b111100 @ k @ 0b111)
+	brcc {k: avr_branch} => le(0b111101 @ k @ 0b000)
+	brsh {k: avr_branch} => le(0b111101 @ k @ 0b000)
+	brne {k: avr_branch} => le(0b111101 @ k @ 0b001)
+	brpl {k: avr_branch} => le(0b111101 @ k @ 0b010)
+	brvc {k: avr_branch} => le(0b111101 @ k @ 0b011)
+	brge {k: avr_branch} => le(0b111101 @ k @ 0b100)
+	brhc {k: avr_branch} => le(0b111101 @ k @ 0b101)
+	brtc {k: avr_branch} => le(0b111101 @ k @ 0b110)
+	brid {k: avr_branch} => le(0b111101 @ k @ 0b111)
+
+	sbrc {rr: avr_reg}, {b: u3} => le(0b1111110 @ rr @ 0b0 @ b)
+	sbrs {rr: avr_reg}, {b: u3} => le(0b1111111 @ rr @ 0b0 @ b)
+	sbic {a: u5}, {b: u3} => le(0x99 @ a @ b)
+	sbis {a: u5}, {b: u3} => le(0x9b @ a @ b)
+	sbi  {a: u5}, {b: u3} => le(0x9a @ a @ b)
+	cbi  {a: u5}, {b: u3} => le(0x98 @ a @ b)
+
+	in  {rd: avr_reg}, {a: u6} => le(0b10110 @ a[5:4] @ rd @ a[3:0])
+	out {a: u6}, {rr: avr_reg} => le(0b10111 @ a[5:4] @ rr @ a[3:0])
+
+	bset {s: u3} => le(0x94 @ 0b0 @ s @ 0x8)
+	bclr {s: u3} => le(0x94 @ 0b1 @ s @ 0x8)
+	bst {rd: avr_reg}, {b: u3} => le(0b1111101 @ rd @ 0b0 @ b)
+	bld {rd: avr_reg}, {b: u3} => le(0b1111100 @ rd @ 0b0 @ b)
+
+	sec => le(0x9408)
+	sez => le(0x9418)
+	sen => le(0x9428)
+	sev => le(0x9438)
+	ses => le(0x9448)
+	seh => le(0x9458)
+	set => le(0x9468)
+	sei => le(0x9478)
+	clc => le(0x9488)
+	clz => le(0x9498)
+	cln => le(0x94a8)
+	clv => le(0x94b8)
+	cls => le(0x94c8)
+	clh => le(0x94d8)
+	clt => le(0x94e8)
+	cli => le(0x94f8)
+
+	lds {rd: avr_reg}, {k: u16} => le(0b1001000 @ rd @ 0x0) @ le(k)
+	sts {k: u16}, {rr: avr_reg} => le(0b1001001 @ rr @ 0x0) @ le(k)
+
+	ld {rd: avr_reg}, x  => le(0b1001000 @ rd @ 0xc)
+	ld {rd: avr_reg}, x+ => le(0b1001000 @ rd @ 0xd)
+	ld {rd: avr_reg}, -x => le(0b1001000 @ rd @ 0xe)
+	ld {rd: avr_reg}, y  => le(0b1000000 @ rd @ 0x8)
+	ld {rd: avr_reg}, y+ => le(0b1001000 @ rd @ 0x9)
+	ld {rd: avr_reg}, -y => le(0b1001000 @ rd @ 0xa)
+	ld {rd: avr_reg}, z  => le(0b1000000 @ rd @ 0x0)
+	ld {rd: avr_reg}, z+ => le(0b1001000 @ rd @ 0x1)
+	ld {rd: avr_reg}, -z => le(0b1001000 @ rd @ 0x2)
+
+	ldd {rd: avr_reg}, y+{q: u6} => le(0b10 @ q[5:5] @ 0b0 @ q[4:3] @ 0b0 @ rd @ 0b1 @ q[2:0])
+	ldd {rd: avr_reg}, z+{q: u6} => le(0b10 @ q[5:5] @ 0b0 @ q[4:3] @ 0b0 @ rd @ 0b0 @ q[2:0])
+
+	st x,  {rr: avr_reg} => le(0b1001001 @ rr @ 0xc)
+	st x+, {rr: avr_reg} => le(0b1001001 @ rr @ 0xd)
+	st -x, {rr: avr_reg} => le(0b1001001 @ rr @ 0xe)
+	st y,  {rr: avr_reg} => le(0b1000001 @ rr @ 0x8)
+	st y+, {rr: avr_reg} => le(0b1001001 @ rr @ 0x9)
+	st -y, {rr: avr_reg} => le(0b1001001 @ rr @ 0xa)
+	st z,  {rr: avr_reg} => le(0b1000001 @ rr @ 0x0)
+	st z+, {rr: avr_reg} => le(0b1001001 @ rr @ 0x1)
+	st -z, {rr: avr_reg} => le(0b1001001 @ rr @ 0x2)
+
+	std y+{q: u6}, {rr: avr_reg} => le(0b10 @ q[5:5] @ 0b0 @ q[4:3] @ 0b1 @ rr @ 0b1 @ q[2:0])
+	std z+{q: u6}, {rr: avr_reg} => le(0b10 @ q[5:5] @ 0b0 @ q[4:3] @ 0b1 @ rr @ 0b0 @ q[2:0])
+
+	lpm  => le(0x95c8)
+	lpm  {rd: avr_reg}, z  => le(0b1001000 @ rd @ 0x4)
+	lpm  {rd: avr_reg}, z+ => le(0b1001000 @ rd @ 0x5)
+	elpm => le(0x95d8)
+	elpm {rd: avr_reg}, z  => le(0b1001000 @ rd @ 0x6)
+	elpm {rd: avr_reg}, z+ => le(0b1001000 @ rd @ 0x7)
+	spm  => le(0x95e8)
+
+	nop   => le(0x0000)
+	sleep => le(0x9588)
+	wdr   => le(0x95a8)
+	break => le(0x9598)
+}
\ No newline at end of file
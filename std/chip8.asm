@@ -0,0 +1,74 @@
+; CHIP-8
+;
+; The original instruction set, with the mnemonics from Cowgod's
+; technical reference. Programs are usually loaded at 0x200.
+
+
+#subruledef chip8_reg
+{
+	v0 => 0x0
+	v1 => 0x1
+	v2 => 0x2
+	v3 => 0x3
+	v4 => 0x4
+	v5 => 0x5
+	v6 => 0x6
+	v7 => 0x7
+	v8 => 0x8
+	v9 => 0x9
+	va => 0xa
+	vb => 0xb
+	vc => 0xc
+	vd => 0xd
+	ve => 0xe
+	vf => 0xf
+}
+
+
+#ruledef chip8
+{
+	cls => 0x00e0
+	ret => 0x00ee
+	sys {addr: u12} => 0x0 @ addr
+
+	jp   {addr: u12}     => 0x1 @ addr
+	jp   v0, {addr: u12} => 0xb @ addr
+	call {addr: u12}     => 0x2 @ addr
+
+	se  {x: chip8_reg}, {y: chip8_reg} => 0x5 @ x @ y @ 0x0
+	se  {x: chip8_reg}, {kk: i8}       => 0x3 @ x @ kk
+	sne {x: chip8_reg}, {y: chip8_reg} => 0x9 @ x @ y @ 0x0
+	sne {x: chip8_reg}, {kk: i8}       => 0x4 @ x @ kk
+
+	ld {x: chip8_reg}, {y: chip8_reg} => 0x8 @ x @ y @ 0x0
+	ld {x: chip8_reg}, dt             => 0xf @ x @ 0x07
+	ld {x: chip8_reg}, k              => 0xf @ x @ 0x0a
+	ld {x: chip8_reg}, [i]            => 0xf @ x @ 0x65
+	ld {x: chip8_reg}, {kk: i8}       => 0x6 @ x @ kk
+	ld i, {addr: u12}                 => 0xa @ addr
+	ld dt, {x: chip8_reg}             => 0xf @ x @ 0x15
+	ld st, {x: chip8_reg}             => 0xf @ x @ 0x18
+	ld f, {x: chip8_reg}              => 0xf @ x @ 0x29
+	ld b, {x: chip8_reg}              => 0xf @ x @ 0x33
+	ld [i], {x: chip8_reg}            => 0xf @ x @ 0x55
+
+	add {x: chip8_reg}, {y: chip8_reg} => 0x8 @ x @ y @ 0x4
+	add {x: chip8_reg}, {kk: i8}       => 0x7 @ x @ kk
+	add i, {x: chip8_reg}              => 0xf @ x @ 0x1e
+
+	or   {x: chip8_reg}, {y: chip8_reg} => 0x8 @ x @ y @ 0x1
+	and  {x: chip8_reg}, {y: chip8_reg} => 0x8 @ x @ y @ 0x2
+	xor  {x: chip8_reg}, {y: chip8_reg} => 0x8 @ x @ y @ 0x3
+	sub  {x: chip8_reg}, {y: chip8_reg} => 0x8 @ x @ y @ 0x5
+	shr  {x: chip8_reg}, {y: chip8_reg} => 0x8 @ x @ y @ 0x6
+	shr  {x: chip8_reg}                 => 0x8 @ x @ 0x06
+	subn {x: chip8_reg}, {y: chip8_reg} => 0x8 @ x @ y @ 0x7
+	shl  {x: chip8_reg}, {y: chip8_reg} => 0x8 @ x @ y @ 0xe
+	shl  {x: chip8_reg}                 => 0x8 @ x @ 0x0e
+
+	rnd {x: chip8_reg}, {kk: i8} => 0xc @ x @ kk
+	drw {x: chip8_reg}, {y: chip8_reg}, {n: u4} => 0xd @ x @ y @ n
+
+	skp  {x: chip8_reg} => 0xe @ x @ 0x9e
+	sknp {x: chip8_reg} => 0xe @ x @ 0xa1
+}
\ No newline at end of file
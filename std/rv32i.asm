@@ -0,0 +1,249 @@
+; RISC-V RV32I
+;
+; The base integer instruction set, with registers written either
+; as `x0` to `x31` or by their ABI names. Branch and jump targets
+; are addresses, and instructions are encoded little-endian.
+; Common pseudo-instructions like `li`, `mv`, `j` and `ret`
+; are also provided.
+
+
+#subruledef rv32i_reg
+{
+	x0    => 0`5
+	x1    => 1`5
+	x2    => 2`5
+	x3    => 3`5
+	x4    => 4`5
+	x5    => 5`5
+	x6    => 6`5
+	x7    => 7`5
+	x8    => 8`5
+	x9    => 9`5
+	x10   => 10`5
+	x11   => 11`5
+	x12   => 12`5
+	x13   => 13`5
+	x14   => 14`5
+	x15   => 15`5
+	x16   => 16`5
+	x17   => 17`5
+	x18   => 18`5
+	x19   => 19`5
+	x20   => 20`5
+	x21   => 21`5
+	x22   => 22`5
+	x23   => 23`5
+	x24   => 24`5
+	x25   => 25`5
+	x26   => 26`5
+	x27   => 27`5
+	x28   => 28`5
+	x29   => 29`5
+	x30   => 30`5
+	x31   => 31`5
+
+	zero => 0`5
+	ra   => 1`5
+	sp   => 2`5
+	gp   => 3`5
+	tp   => 4`5
+	t0   => 5`5
+	t1   => 6`5
+	t2   => 7`5
+	s0   => 8`5
+	s1   => 9`5
+	a0   => 10`5
+	a1   => 11`5
+	a2   => 12`5
+	a3   => 13`5
+	a4   => 14`5
+	a5   => 15`5
+	a6   => 16`5
+	a7   => 17`5
+	s2   => 18`5
+	s3   => 19`5
+	s4   => 20`5
+	s5   => 21`5
+	s6   => 22`5
+	s7   => 23`5
+	s8   => 24`5
+	s9   => 25`5
+	s10  => 26`5
+	s11  => 27`5
+	t3   => 28`5
+	t4   => 29`5
+	t5   => 30`5
+	t6   => 31`5
+	fp   => 8`5
+}
+
+
+#subruledef rv32i_branch_offset
+{
+	{addr: u32} =>
+	{
+		offset = addr - $
+		assert(offset % 2 == 0)
+		assert(offset <=  0xffe)
+		assert(offset >= -0x1000)
+		offset`13
+	}
+}
+
+
+#subruledef rv32i_jump_offset
+{
+	{addr: u32} =>
+	{
+		offset = addr - $
+		assert(offset % 2 == 0)
+		assert(offset <=  0xffffe)
+		assert(offset >= -0x100000)
+		offset`21
+	}
+}
+
+
+#ruledef rv32i
+{
+	lui   {rd: rv32i_reg}, {imm: u20} => le(imm @ rd @ 0b0110111`7)
+	auipc {rd: rv32i_reg}, {imm: u20} => le(imm @ rd @ 0b0010111`7)
+
+	jal {rd: rv32i_reg}, {off: rv32i_jump_offset} =>
+		le(off[20:20] @ off[10:1] @ off[11:11] @ off[19:12] @ rd @ 0b1101111`7)
+
+	jalr {rd: rv32i_reg}, {imm: s12}({rs1: rv32i_reg}) => le(imm @ rs1 @ 0b000 @ rd @ 0b1100111`7)
+	jalr {rd: rv32i_reg}, ({rs1: rv32i_reg}) => le(0`12 @ rs1 @ 0b000 @ rd @ 0b1100111`7)
+	jalr {rd: rv32i_reg}, {rs1: rv32i_reg}, {imm: s12} => le(imm @ rs1 @ 0b000 @ rd @ 0b1100111`7)
+
+	beq  {rs1: rv32i_reg}, {rs2: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ rs2 @ rs1 @ 0b000 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	bne  {rs1: rv32i_reg}, {rs2: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ rs2 @ rs1 @ 0b001 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	blt  {rs1: rv32i_reg}, {rs2: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ rs2 @ rs1 @ 0b100 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	bge  {rs1: rv32i_reg}, {rs2: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ rs2 @ rs1 @ 0b101 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	bltu {rs1: rv32i_reg}, {rs2: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ rs2 @ rs1 @ 0b110 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	bgeu {rs1: rv32i_reg}, {rs2: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ rs2 @ rs1 @ 0b111 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+
+	lb  {rd: rv32i_reg}, {imm: s12}({rs1: rv32i_reg}) => le(imm @ rs1 @ 0b000 @ rd @ 0b0000011`7)
+	lh  {rd: rv32i_reg}, {imm: s12}({rs1: rv32i_reg}) => le(imm @ rs1 @ 0b001 @ rd @ 0b0000011`7)
+	lw  {rd: rv32i_reg}, {imm: s12}({rs1: rv32i_reg}) => le(imm @ rs1 @ 0b010 @ rd @ 0b0000011`7)
+	lbu {rd: rv32i_reg}, {imm: s12}({rs1: rv32i_reg}) => le(imm @ rs1 @ 0b100 @ rd @ 0b0000011`7)
+	lhu {rd: rv32i_reg}, {imm: s12}({rs1: rv32i_reg}) => le(imm @ rs1 @ 0b101 @ rd @ 0b0000011`7)
+
+	lb  {rd: rv32i_reg}, ({rs1: rv32i_reg}) => le(0`12 @ rs1 @ 0b000 @ rd @ 0b0000011`7)
+	lh  {rd: rv32i_reg}, ({rs1: rv32i_reg}) => le(0`12 @ rs1 @ 0b001 @ rd @ 0b0000011`7)
+	lw  {rd: rv32i_reg}, ({rs1: rv32i_reg}) => le(0`12 @ rs1 @ 0b010 @ rd @ 0b0000011`7)
+	lbu {rd: rv32i_reg}, ({rs1: rv32i_reg}) => le(0`12 @ rs1 @ 0b100 @ rd @ 0b0000011`7)
+	lhu {rd: rv32i_reg}, ({rs1: rv32i_reg}) => le(0`12 @ rs1 @ 0b101 @ rd @ 0b0000011`7)
+
+	sb {rs2: rv32i_reg}, {imm: s12}({rs1: rv32i_reg}) => le(imm[11:5] @ rs2 @ rs1 @ 0b000 @ imm[4:0] @ 0b0100011`7)
+	sh {rs2: rv32i_reg}, {imm: s12}({rs1: rv32i_reg}) => le(imm[11:5] @ rs2 @ rs1 @ 0b001 @ imm[4:0] @ 0b0100011`7)
+	sw {rs2: rv32i_reg}, {imm: s12}({rs1: rv32i_reg}) => le(imm[11:5] @ rs2 @ rs1 @ 0b010 @ imm[4:0] @ 0b0100011`7)
+
+	sb {rs2: rv32i_reg}, ({rs1: rv32i_reg}) => le(0`7 @ rs2 @ rs1 @ 0b000 @ 0`5 @ 0b0100011`7)
+	sh {rs2: rv32i_reg}, ({rs1: rv32i_reg}) => le(0`7 @ rs2 @ rs1 @ 0b001 @ 0`5 @ 0b0100011`7)
+	sw {rs2: rv32i_reg}, ({rs1: rv32i_reg}) => le(0`7 @ rs2 @ rs1 @ 0b010 @ 0`5 @ 0b0100011`7)
+
+	addi  {rd: rv32i_reg}, {rs1: rv32i_reg}, {imm: s12} => le(imm @ rs1 @ 0b000 @ rd @ 0b0010011`7)
+	slti  {rd: rv32i_reg}, {rs1: rv32i_reg}, {imm: s12} => le(imm @ rs1 @ 0b010 @ rd @ 0b0010011`7)
+	sltiu {rd: rv32i_reg}, {rs1: rv32i_reg}, {imm: s12} => le(imm @ rs1 @ 0b011 @ rd @ 0b0010011`7)
+	xori  {rd: rv32i_reg}, {rs1: rv32i_reg}, {imm: s12} => le(imm @ rs1 @ 0b100 @ rd @ 0b0010011`7)
+	ori   {rd: rv32i_reg}, {rs1: rv32i_reg}, {imm: s12} => le(imm @ rs1 @ 0b110 @ rd @ 0b0010011`7)
+	andi  {rd: rv32i_reg}, {rs1: rv32i_reg}, {imm: s12} => le(imm @ rs1 @ 0b111 @ rd @ 0b0010011`7)
+
+	slli {rd: rv32i_reg}, {rs1: rv32i_reg}, {shamt: u5} => le(0b0000000 @ shamt @ rs1 @ 0b001 @ rd @ 0b0010011`7)
+	srli {rd: rv32i_reg}, {rs1: rv32i_reg}, {shamt: u5} => le(0b0000000 @ shamt @ rs1 @ 0b101 @ rd @ 0b0010011`7)
+	srai {rd: rv32i_reg}, {rs1: rv32i_reg}, {shamt: u5} => le(0b0100000 @ shamt @ rs1 @ 0b101 @ rd @ 0b0010011`7)
+
+	add  {rd: rv32i_reg}, {rs1: rv32i_reg}, {rs2: rv32i_reg} => le(0b0000000 @ rs2 @ rs1 @ 0b000 @ rd @ 0b0110011`7)
+	sub  {rd: rv32i_reg}, {rs1: rv32i_reg}, {rs2: rv32i_reg} => le(0b0100000 @ rs2 @ rs1 @ 0b000 @ rd @ 0b0110011`7)
+	sll  {rd: rv32i_reg}, {rs1: rv32i_reg}, {rs2: rv32i_reg} => le(0b0000000 @ rs2 @ rs1 @ 0b001 @ rd @ 0b0110011`7)
+	slt  {rd: rv32i_reg}, {rs1: rv32i_reg}, {rs2: rv32i_reg} => le(0b0000000 @ rs2 @ rs1 @ 0b010 @ rd @ 0b0110011`7)
+	sltu {rd: rv32i_reg}, {rs1: rv32i_reg}, {rs2: rv32i_reg} => le(0b0000000 @ rs2 @ rs1 @ 0b011 @ rd @ 0b0110011`7)
+	xor  {rd: rv32i_reg}, {rs1: rv32i_reg}, {rs2: rv32i_reg} => le(0b0000000 @ rs2 @ rs1 @ 0b100 @ rd @ 0b0110011`7)
+	srl  {rd: rv32i_reg}, {rs1: rv32i_reg}, {rs2: rv32i_reg} => le(0b0000000 @ rs2 @ rs1 @ 0b101 @ rd @ 0b0110011`7)
+	sra  {rd: rv32i_reg}, {rs1: rv32i_reg}, {rs2: rv32i_reg} => le(0b0100000 @ rs2 @ rs1 @ 0b101 @ rd @ 0b0110011`7)
+	or   {rd: rv32i_reg}, {rs1: rv32i_reg}, {rs2: rv32i_reg} => le(0b0000000 @ rs2 @ rs1 @ 0b110 @ rd @ 0b0110011`7)
+	and  {rd: rv32i_reg}, {rs1: rv32i_reg}, {rs2: rv32i_reg} => le(0b0000000 @ rs2 @ rs1 @ 0b111 @ rd @ 0b0110011`7)
+
+	fence  => le(0x0ff0000f`32)
+	ecall  => le(0x00000073`32)
+	ebreak => le(0x00100073`32)
+
+	; Pseudo-instructions
+
+	nop => le(0x00000013`32)
+
+	li {rd: rv32i_reg}, {imm: s12} => le(imm @ 0`5 @ 0b000 @ rd @ 0b0010011`7)
+	li {rd: rv32i_reg}, {imm: i32} =>
+	{
+		hi = ((imm + 0x800) >> 12)`20
+		lo = imm`12
+		le(hi @ rd @ 0b0110111`7) @ le(lo @ rd @ 0b000 @ rd @ 0b0010011`7)
+	}
+
+	la {rd: rv32i_reg}, {addr: u32} =>
+	{
+		offset = addr - $
+		hi = ((offset + 0x800) >> 12)`20
+		lo = offset`12
+		le(hi @ rd @ 0b0010111`7) @ le(lo @ rd @ 0b000 @ rd @ 0b0010011`7)
+	}
+
+	mv   {rd: rv32i_reg}, {rs: rv32i_reg} => le(0`12 @ rs @ 0b000 @ rd @ 0b0010011`7)
+	not  {rd: rv32i_reg}, {rs: rv32i_reg} => le(0xfff @ rs @ 0b100 @ rd @ 0b0010011`7)
+	neg  {rd: rv32i_reg}, {rs: rv32i_reg} => le(0b0100000 @ rs @ 0`5 @ 0b000 @ rd @ 0b0110011`7)
+	seqz {rd: rv32i_reg}, {rs: rv32i_reg} => le(1`12 @ rs @ 0b011 @ rd @ 0b0010011`7)
+	snez {rd: rv32i_reg}, {rs: rv32i_reg} => le(0b0000000 @ rs @ 0`5 @ 0b011 @ rd @ 0b0110011`7)
+	sltz {rd: rv32i_reg}, {rs: rv32i_reg} => le(0b0000000 @ 0`5 @ rs @ 0b010 @ rd @ 0b0110011`7)
+	sgtz {rd: rv32i_reg}, {rs: rv32i_reg} => le(0b0000000 @ rs @ 0`5 @ 0b010 @ rd @ 0b0110011`7)
+
+	beqz {rs: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ 0`5 @ rs @ 0b000 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	bnez {rs: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ 0`5 @ rs @ 0b001 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	blez {rs: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ rs @ 0`5 @ 0b101 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	bgez {rs: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ 0`5 @ rs @ 0b101 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	bltz {rs: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ 0`5 @ rs @ 0b100 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	bgtz {rs: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ rs @ 0`5 @ 0b100 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+
+	bgt  {rs1: rv32i_reg}, {rs2: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ rs1 @ rs2 @ 0b100 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	ble  {rs1: rv32i_reg}, {rs2: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ rs1 @ rs2 @ 0b101 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	bgtu {rs1: rv32i_reg}, {rs2: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ rs1 @ rs2 @ 0b110 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+	bleu {rs1: rv32i_reg}, {rs2: rv32i_reg}, {off: rv32i_branch_offset} =>
+		le(off[12:12] @ off[10:5] @ rs1 @ rs2 @ 0b111 @ off[4:1] @ off[11:11] @ 0b1100011`7)
+
+	j   {off: rv32i_jump_offset} => le(off[20:20] @ off[10:1] @ off[11:11] @ off[19:12] @ 0`5 @ 0b1101111`7)
+	jal {off: rv32i_jump_offset} => le(off[20:20] @ off[10:1] @ off[11:11] @ off[19:12] @ 1`5 @ 0b1101111`7)
+	jr   {rs: rv32i_reg} => le(0`12 @ rs @ 0b000 @ 0`5 @ 0b1100111`7)
+	jalr {rs: rv32i_reg} => le(0`12 @ rs @ 0b000 @ 1`5 @ 0b1100111`7)
+	ret => le(0x00008067`32)
+
+	call {addr: u32} =>
+	{
+		offset = addr - $
+		hi = ((offset + 0x800) >> 12)`20
+		lo = offset`12
+		le(hi @ 1`5 @ 0b0010111`7) @ le(lo @ 1`5 @ 0b000 @ 1`5 @ 0b1100111`7)
+	}
+
+	tail {addr: u32} =>
+	{
+		offset = addr - $
+		hi = ((offset + 0x800) >> 12)`20
+		lo = offset`12
+		le(hi @ 6`5 @ 0b0010111`7) @ le(lo @ 6`5 @ 0b000 @ 0`5 @ 0b1100111`7)
+	}
+}
\ No newline at end of file
@@ -0,0 +1,315 @@
+; Zilog Z80
+;
+; The documented instruction set, with Zilog mnemonics. Indexed
+; operands are written `(ix + d)`, `(ix - d)` or `(ix)`, and the
+; alternate register swap is written `ex af, af` since the
+; assembler has no `'` token.
+;
+; Spaces inside a mnemonic are not significant, so `rl a`, `rr a`,
+; `rlc a` and `rrc a` assemble to the shorter `rla`, `rra`, `rlca`
+; and `rrca`, and `rl d` and `rr d` are taken as `rld` and `rrd`.
+
+
+#subruledef z80_reg
+{
+	b    => 0b000
+	c    => 0b001
+	d    => 0b010
+	e    => 0b011
+	h    => 0b100
+	l    => 0b101
+	(hl) => 0b110
+	a    => 0b111
+}
+
+
+#subruledef z80_pair
+{
+	bc => 0b00
+	de => 0b01
+	hl => 0b10
+	sp => 0b11
+}
+
+
+#subruledef z80_pair_af
+{
+	bc => 0b00
+	de => 0b01
+	hl => 0b10
+	af => 0b11
+}
+
+
+#subruledef z80_pair_ix
+{
+	bc => 0b00
+	de => 0b01
+	ix => 0b10
+	sp => 0b11
+}
+
+
+#subruledef z80_pair_iy
+{
+	bc => 0b00
+	de => 0b01
+	iy => 0b10
+	sp => 0b11
+}
+
+
+#subruledef z80_index
+{
+	ix => 0xdd
+	iy => 0xfd
+}
+
+
+; Yields the prefix in the high byte
+; and the displacement in the low byte
+#subruledef z80_indexed
+{
+	(ix + {d: s8}) => 0xdd @ d
+	(iy + {d: s8}) => 0xfd @ d
+	(ix - {d: u8}) =>
+	{
+		assert(d <= 0x80)
+		0xdd @ (-d)`8
+	}
+	(iy - {d: u8}) =>
+	{
+		assert(d <= 0x80)
+		0xfd @ (-d)`8
+	}
+	(ix) => 0xdd00
+	(iy) => 0xfd00
+}
+
+
+#subruledef z80_cond
+{
+	nz => 0b000
+	z  => 0b001
+	nc => 0b010
+	c  => 0b011
+	po => 0b100
+	pe => 0b101
+	p  => 0b110
+	m  => 0b111
+}
+
+
+#subruledef z80_cond_jr
+{
+	nz => 0b00
+	z  => 0b01
+	nc => 0b10
+	c  => 0b11
+}
+
+
+#subruledef z80_reladdr
+{
+	{addr: u16} =>
+	{
+		reladdr = addr - $ - 2
+		assert(reladdr <=  0x7f)
+		assert(reladdr >= !0x7f)
+		reladdr`8
+	}
+}
+
+
+#ruledef z80
+{
+	ld {dst: z80_reg}, {src: z80_reg} =>
+	{
+		assert(dst != 0b110 || src != 0b110)
+		0b01 @ dst @ src
+	}
+
+	ld {dst: z80_reg}, {m: z80_indexed} => m[15:8] @ 0b01 @ dst @ 0b110 @ m[7:0]
+	ld {m: z80_indexed}, {src: z80_reg} => m[15:8] @ 0b01110 @ src @ m[7:0]
+	ld {m: z80_indexed}, {imm: i8} => m[15:8] @ 0x36 @ m[7:0] @ imm
+	ld {dst: z80_reg}, {imm: i8} => 0b00 @ dst @ 0b110 @ imm
+
+	ld a, (bc) => 0x0a
+	ld a, (de) => 0x1a
+	ld a, ({addr: u16}) => 0x3a @ le(addr)
+	ld (bc), a => 0x02
+	ld (de), a => 0x12
+	ld ({addr: u16}), a => 0x32 @ le(addr)
+	ld a, i => 0xed57
+	ld a, r => 0xed5f
+	ld i, a => 0xed47
+	ld r, a => 0xed4f
+
+	ld sp, hl => 0xf9
+	ld sp, {x: z80_index} => x @ 0xf9
+	ld hl, ({addr: u16}) => 0x2a @ le(addr)
+	ld ({addr: u16}), hl => 0x22 @ le(addr)
+	ld {x: z80_index}, ({addr: u16}) => x @ 0x2a @ le(addr)
+	ld ({addr: u16}), {x: z80_index} => x @ 0x22 @ le(addr)
+	ld {rp: z80_pair}, ({addr: u16}) => 0xed @ 0b01 @ rp @ 0b1011 @ le(addr)
+	ld ({addr: u16}), {rp: z80_pair} => 0xed @ 0b01 @ rp @ 0b0011 @ le(addr)
+	ld {x: z80_index}, {imm: i16} => x @ 0x21 @ le(imm)
+	ld {rp: z80_pair}, {imm: i16} => 0b00 @ rp @ 0b0001 @ le(imm)
+
+	push {rp: z80_pair_af} => 0b11 @ rp @ 0b0101
+	push {x: z80_index} => x @ 0xe5
+	pop  {rp: z80_pair_af} => 0b11 @ rp @ 0b0001
+	pop  {x: z80_index} => x @ 0xe1
+
+	ex de, hl => 0xeb
+	ex af, af => 0x08
+	ex (sp), hl => 0xe3
+	ex (sp), {x: z80_index} => x @ 0xe3
+	exx => 0xd9
+
+	ldi  => 0xeda0
+	ldir => 0xedb0
+	ldd  => 0xeda8
+	lddr => 0xedb8
+	cpi  => 0xeda1
+	cpir => 0xedb1
+	cpd  => 0xeda9
+	cpdr => 0xedb9
+
+	add a, {src: z80_reg} => 0b10000 @ src
+	adc a, {src: z80_reg} => 0b10001 @ src
+	sub    {src: z80_reg} => 0b10010 @ src
+	sbc a, {src: z80_reg} => 0b10011 @ src
+	and    {src: z80_reg} => 0b10100 @ src
+	xor    {src: z80_reg} => 0b10101 @ src
+	or     {src: z80_reg} => 0b10110 @ src
+	cp     {src: z80_reg} => 0b10111 @ src
+
+	add a, {m: z80_indexed} => m[15:8] @ 0x86 @ m[7:0]
+	adc a, {m: z80_indexed} => m[15:8] @ 0x8e @ m[7:0]
+	sub    {m: z80_indexed} => m[15:8] @ 0x96 @ m[7:0]
+	sbc a, {m: z80_indexed} => m[15:8] @ 0x9e @ m[7:0]
+	and    {m: z80_indexed} => m[15:8] @ 0xa6 @ m[7:0]
+	xor    {m: z80_indexed} => m[15:8] @ 0xae @ m[7:0]
+	or     {m: z80_indexed} => m[15:8] @ 0xb6 @ m[7:0]
+	cp     {m: z80_indexed} => m[15:8] @ 0xbe @ m[7:0]
+
+	add a, {imm: i8} => 0xc6 @ imm
+	adc a, {imm: i8} => 0xce @ imm
+	sub    {imm: i8} => 0xd6 @ imm
+	sbc a, {imm: i8} => 0xde @ imm
+	and    {imm: i8} => 0xe6 @ imm
+	xor    {imm: i8} => 0xee @ imm
+	or     {imm: i8} => 0xf6 @ imm
+	cp     {imm: i8} => 0xfe @ imm
+
+	inc {dst: z80_reg} => 0b00 @ dst @ 0b100
+	dec {dst: z80_reg} => 0b00 @ dst @ 0b101
+	inc {m: z80_indexed} => m[15:8] @ 0x34 @ m[7:0]
+	dec {m: z80_indexed} => m[15:8] @ 0x35 @ m[7:0]
+
+	daa => 0x27
+	cpl => 0x2f
+	neg => 0xed44
+	ccf => 0x3f
+	scf => 0x37
+	nop => 0x00
+	halt => 0x76
+	di => 0xf3
+	ei => 0xfb
+	im 0 => 0xed46
+	im 1 => 0xed56
+	im 2 => 0xed5e
+
+	add hl, {rp: z80_pair} => 0b00 @ rp @ 0b1001
+	adc hl, {rp: z80_pair} => 0xed @ 0b01 @ rp @ 0b1010
+	sbc hl, {rp: z80_pair} => 0xed @ 0b01 @ rp @ 0b0010
+	add ix, {rp: z80_pair_ix} => 0xdd @ 0b00 @ rp @ 0b1001
+	add iy, {rp: z80_pair_iy} => 0xfd @ 0b00 @ rp @ 0b1001
+	inc {rp: z80_pair} => 0b00 @ rp @ 0b0011
+	dec {rp: z80_pair} => 0b00 @ rp @ 0b1011
+	inc {x: z80_index} => x @ 0x23
+	dec {x: z80_index} => x @ 0x2b
+
+	rlca => 0x07
+	rla  => 0x17
+	rrca => 0x0f
+	rra  => 0x1f
+	rld  => 0xed6f
+	rrd  => 0xed67
+
+	rlc {src: z80_reg} => 0xcb @ 0b00000 @ src
+	rrc {src: z80_reg} => 0xcb @ 0b00001 @ src
+	rl  {src: z80_reg} =>
+	{
+		assert(src != 0b010)
+		0xcb @ 0b00010 @ src
+	}
+	rr  {src: z80_reg} =>
+	{
+		assert(src != 0b010)
+		0xcb @ 0b00011 @ src
+	}
+	sla {src: z80_reg} => 0xcb @ 0b00100 @ src
+	sra {src: z80_reg} => 0xcb @ 0b00101 @ src
+	srl {src: z80_reg} => 0xcb @ 0b00111 @ src
+
+	rlc {m: z80_indexed} => m[15:8] @ 0xcb @ m[7:0] @ 0x06
+	rrc {m: z80_indexed} => m[15:8] @ 0xcb @ m[7:0] @ 0x0e
+	rl  {m: z80_indexed} => m[15:8] @ 0xcb @ m[7:0] @ 0x16
+	rr  {m: z80_indexed} => m[15:8] @ 0xcb @ m[7:0] @ 0x1e
+	sla {m: z80_indexed} => m[15:8] @ 0xcb @ m[7:0] @ 0x26
+	sra {m: z80_indexed} => m[15:8] @ 0xcb @ m[7:0] @ 0x2e
+	srl {m: z80_indexed} => m[15:8] @ 0xcb @ m[7:0] @ 0x3e
+
+	bit {n: u3}, {src: z80_reg} => 0xcb @ 0b01 @ n @ src
+	res {n: u3}, {src: z80_reg} => 0xcb @ 0b10 @ n @ src
+	set {n: u3}, {src: z80_reg} => 0xcb @ 0b11 @ n @ src
+
+	bit {n: u3}, {m: z80_indexed} => m[15:8] @ 0xcb @ m[7:0] @ 0b01 @ n @ 0b110
+	res {n: u3}, {m: z80_indexed} => m[15:8] @ 0xcb @ m[7:0] @ 0b10 @ n @ 0b110
+	set {n: u3}, {m: z80_indexed} => m[15:8] @ 0xcb @ m[7:0] @ 0b11 @ n @ 0b110
+
+	jp (hl) => 0xe9
+	jp ({x: z80_index}) => x @ 0xe9
+	jp {cc: z80_cond}, {addr: u16} => 0b11 @ cc @ 0b010 @ le(addr)
+	jp {addr: u16} => 0xc3 @ le(addr)
+	jr {cc: z80_cond_jr}, {addr: z80_reladdr} => 0b001 @ cc @ 0b000 @ addr
+	jr {addr: z80_reladdr} => 0x18 @ addr
+	djnz {addr: z80_reladdr} => 0x10 @ addr
+
+	call {cc: z80_cond}, {addr: u16} => 0b11 @ cc @ 0b100 @ le(addr)
+	call {addr: u16} => 0xcd @ le(addr)
+	ret {cc: z80_cond} => 0b11 @ cc @ 0b000
+	ret  => 0xc9
+	reti => 0xed4d
+	retn => 0xed45
+
+	rst {addr: u8} =>
+	{
+		assert(addr & 0x38 == addr)
+		0b11 @ addr[5:3] @ 0b111
+	}
+
+	in a, ({port: u8}) => 0xdb @ port
+	in {dst: z80_reg}, (c) =>
+	{
+		assert(dst != 0b110)
+		0xed @ 0b01 @ dst @ 0b000
+	}
+	out ({port: u8}), a => 0xd3 @ port
+	out (c), {src: z80_reg} =>
+	{
+		assert(src != 0b110)
+		0xed @ 0b01 @ src @ 0b001
+	}
+
+	ini  => 0xeda2
+	inir => 0xedb2
+	ind  => 0xedaa
+	indr => 0xedba
+	outi => 0xeda3
+	otir => 0xedb3
+	outd => 0xedab
+	otdr => 0xedbb
+}
\ No newline at end of file
@@ -0,0 +1 @@
+#checksum md5 ; error: unknown checksum algorithm
\ No newline at end of file
@@ -0,0 +1 @@
+#checksum sum8, nope ; error: unknown bank
\ No newline at end of file
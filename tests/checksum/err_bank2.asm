@@ -0,0 +1,4 @@
+#bankdef ram { #addr 0, #size 4 }
+#bankdef rom { #addr 0, #size 4, #outp 0 }
+#bank rom
+#checksum sum8, ram ; error: failed / error: non-writable
\ No newline at end of file
@@ -0,0 +1 @@
+#checksum sum16 be ; error: expected `le`
\ No newline at end of file
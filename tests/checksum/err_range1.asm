@@ -0,0 +1 @@
+#checksum sum8, 4, 2 ; error: failed / error: ends before it starts
\ No newline at end of file
@@ -0,0 +1,2 @@
+#bankdef a { #addr 0x10, #size 4, #outp 0 }
+#checksum sum8, 0, 4 ; error: failed / error: outside of the bank
\ No newline at end of file
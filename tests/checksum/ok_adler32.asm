@@ -0,0 +1,4 @@
+start:
+#d "123456789" ; = 0x313233343536373839
+end:
+#checksum adler32, start, end ; = 0x091e01de
\ No newline at end of file
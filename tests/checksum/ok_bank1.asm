@@ -0,0 +1,3 @@
+#d8 1, 2, 3    ; = 0x010203
+#checksum sum8 ; = 0x0a
+#d8 4          ; = 0x04
\ No newline at end of file
@@ -0,0 +1,8 @@
+#bankdef header { #addr 0, #size 2, #outp 0 }
+#bankdef rom { #addr 0x100, #size 4, #outp 8 * 2 }
+
+#bank header
+#checksum sum16, rom ; = 0x000a
+
+#bank rom
+#d8 1, 2, 3, 4 ; = 0x01020304
\ No newline at end of file
@@ -0,0 +1,4 @@
+start:
+#d8 1, 2 ; = 0x0102
+#checksum sum8, start, $ ; = 0x03
+#checksum sum8, start, $ ; = 0x06
\ No newline at end of file
@@ -0,0 +1,4 @@
+start:
+#d "123456789" ; = 0x313233343536373839
+end:
+#checksum crc16-ccitt, start, end ; = 0x29b1
\ No newline at end of file
@@ -0,0 +1,4 @@
+start:
+#d "123456789" ; = 0x313233343536373839
+end:
+#checksum crc32, start, end ; = 0xcbf43926
\ No newline at end of file
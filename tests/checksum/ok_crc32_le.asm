@@ -0,0 +1,4 @@
+start:
+#d "123456789" ; = 0x313233343536373839
+end:
+#checksum crc32 le, start, end ; = 0x2639f4cb
\ No newline at end of file
@@ -0,0 +1,4 @@
+#checksum xor, data_start, data_end ; = 0xff
+data_start:
+#d8 0x0f, 0xf0 ; = 0x0ff0
+data_end:
\ No newline at end of file
@@ -0,0 +1,4 @@
+start:
+#d "123456789" ; = 0x313233343536373839
+end:
+#checksum sum8, start, end ; = 0xdd
\ No newline at end of file
@@ -0,0 +1,4 @@
+#bankdef rom { #bits 16, #addr 0, #size 3, #outp 0 }
+
+#d16 0x0102, 0x0304 ; = 0x01020304
+#checksum sum16, 0, 2 ; = 0x000a
\ No newline at end of file
@@ -0,0 +1 @@
+#enum State unique { Idle, Run = 0, Stop } ; error: duplicate enum value / note:_:1: first used here
\ No newline at end of file
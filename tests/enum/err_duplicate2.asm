@@ -0,0 +1 @@
+#enum State { Idle, Run, Idle } ; error: duplicate symbol / note:_:1: first declared here
\ No newline at end of file
@@ -0,0 +1 @@
+#enum State sorted { Idle, Run } ; error: unknown enum option
\ No newline at end of file
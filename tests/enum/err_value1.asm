@@ -0,0 +1 @@
+#enum State { Idle = 1 == 1 } ; error: expected integer value
\ No newline at end of file
@@ -0,0 +1,3 @@
+#enum Color { Red, Green = 5, Blue }
+
+#d8 Color.Red, Color.Green, Color.Blue ; = 0x000506
\ No newline at end of file
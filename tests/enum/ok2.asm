@@ -0,0 +1,9 @@
+#enum Vector step 2
+{
+    Reset
+    Nmi
+    Irq = 0x10
+    Brk
+}
+
+#d8 Vector.Reset, Vector.Nmi, Vector.Irq, Vector.Brk ; = 0x00021012
\ No newline at end of file
@@ -0,0 +1,5 @@
+BASE = 0x40
+
+#enum Opcode unique step -1 { Halt = BASE, Load, Store }
+
+#d8 Opcode.Halt, Opcode.Load, Opcode.Store ; = 0x403f3e
\ No newline at end of file
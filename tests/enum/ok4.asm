@@ -0,0 +1,3 @@
+#enum State { Idle, Run = 0, Stop }
+
+#d8 State.Idle, State.Run, State.Stop ; = 0x000001
\ No newline at end of file
@@ -0,0 +1,10 @@
+#ruledef test
+{
+    ld {x} => 0x55 @ x`8
+}
+
+start:
+#enum Mode { Off, On }
+.local:
+ld start.local ; = 0x5500
+ld Mode.On     ; = 0x5501
\ No newline at end of file
@@ -0,0 +1,7 @@
+#fn f(value) =>
+{
+    while value { value = 0 }
+    value
+}
+
+#d8 f(1) ; error: failed / error:_:3: invalid condition type
\ No newline at end of file
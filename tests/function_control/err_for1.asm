@@ -0,0 +1,7 @@
+#fn last(n) =>
+{
+    for j in 0..n { }
+    j
+}
+
+#d8 last(3) ; error: failed / error:_:4: unknown variable
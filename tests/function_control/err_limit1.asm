@@ -0,0 +1,7 @@
+#fn forever(value) =>
+{
+    while 1 == 1 { value = value + 1 }
+    value
+}
+
+#d8 forever(0) ; error: failed / error:_:3: loop exceeded the limit of 100000 iterations
\ No newline at end of file
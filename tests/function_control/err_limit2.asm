@@ -0,0 +1,7 @@
+#fn f(value) =>
+{
+    for i in 0..value { }
+    0
+}
+
+#d8 f(1000000) ; error: failed / error:_:3: loop exceeded the limit of 100000 iterations
\ No newline at end of file
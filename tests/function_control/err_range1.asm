@@ -0,0 +1,7 @@
+#fn f(value) =>
+{
+    for i in 0..value { }
+    0
+}
+
+#d8 f(1 == 1) ; error: failed / error:_:3: invalid range bound
\ No newline at end of file
@@ -0,0 +1,5 @@
+#fn f(value) =>
+{
+    for i of 0..value { } ; error: expected `in`
+    0
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+#fn crc8(byte) =>
+{
+    let crc = byte
+    for i in 0..8
+    {
+        if crc & 0x80 != 0
+        {
+            crc = ((crc << 1) ^ 0x07) & 0xff
+        }
+        else
+        {
+            crc = (crc << 1) & 0xff
+        }
+    }
+    crc
+}
+
+#d8 crc8(0x00) ; = 0x00
+#d8 crc8(0x01) ; = 0x07
+#d8 crc8(0x80) ; = 0x89
+#d8 crc8(0xff) ; = 0xf3
+#d8 crc8(0x31) ; = 0x97
\ No newline at end of file
@@ -0,0 +1,14 @@
+first = 2
+last = 5
+
+#fn sum(start, end) =>
+{
+    let total = 0
+    for i in start..end { total = total + i }
+    total
+}
+
+#d8 sum(first, last) ; = 0x09
+#d8 sum(0, 0)        ; = 0x00
+#d8 sum(3, 1)        ; = 0x00
+#d8 sum(-2, 1)       ; = 0xfd
\ No newline at end of file
@@ -0,0 +1,10 @@
+i = 0x55
+
+#fn count(n) =>
+{
+    let total = 0
+    for i in 0..n { total = total + 1 }
+    i
+}
+
+#d8 count(3) ; = 0x55
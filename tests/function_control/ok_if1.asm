@@ -0,0 +1,8 @@
+#fn sign(value) =>
+{
+    if value < 0 { 0xff } else if value == 0 { 0x00 } else { 0x01 }
+}
+
+#d8 sign(-5) ; = 0xff
+#d8 sign(0)  ; = 0x00
+#d8 sign(7)  ; = 0x01
\ No newline at end of file
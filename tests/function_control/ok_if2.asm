@@ -0,0 +1,16 @@
+#fn clamp(value) =>
+{
+    if value > 0x7f
+    {
+        value = 0x7f
+    }
+    else
+    {
+        if value < 0 { value = 0 }
+    }
+    value
+}
+
+#d8 clamp(0x100) ; = 0x7f
+#d8 clamp(-3)    ; = 0x00
+#d8 clamp(0x12)  ; = 0x12
\ No newline at end of file
@@ -0,0 +1,10 @@
+x = 0x10
+
+#fn shadow(value) =>
+{
+    let x = value * 2
+    x + 1
+}
+
+#d8 shadow(3) ; = 0x07
+#d8 x         ; = 0x10
\ No newline at end of file
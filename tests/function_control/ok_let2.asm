@@ -0,0 +1,11 @@
+#fn shadow_arg(value) =>
+{
+    if value > 0
+    {
+        let value = 0xff
+        value
+    }
+    value
+}
+
+#d8 shadow_arg(3) ; = 0x03
@@ -0,0 +1,12 @@
+#fn nested(value) =>
+{
+    let x = value
+    let sum = 0
+    {
+        let x = x * 0x10
+        sum = sum + x
+    }
+    sum + x
+}
+
+#d8 nested(2) ; = 0x22
@@ -0,0 +1,15 @@
+#fn popcount(value) =>
+{
+    let count = 0
+    while value != 0
+    {
+        count = count + (value & 1)
+        value = value >> 1
+    }
+    count
+}
+
+#d8 popcount(0)    ; = 0x00
+#d8 popcount(1)    ; = 0x01
+#d8 popcount(0xff) ; = 0x08
+#d8 popcount(0xa5) ; = 0x04
\ No newline at end of file
@@ -0,0 +1,2 @@
+#if 1 == 1 ; error: unterminated
+    #d8 0x11
\ No newline at end of file
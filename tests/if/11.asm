@@ -0,0 +1,2 @@
+#d8 0x11
+#endif ; error: without matching
\ No newline at end of file
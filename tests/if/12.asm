@@ -0,0 +1,4 @@
+#if 1 == 1
+#else
+#else ; error: duplicate `#else`
+#endif
\ No newline at end of file
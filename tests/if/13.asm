@@ -0,0 +1,4 @@
+#if 1 == 0
+#else
+#elif 1 == 1 ; error: after `#else`
+#endif
\ No newline at end of file
@@ -0,0 +1,13 @@
+#ruledef test
+{
+    ld {x} => 0x55 @ x`8
+}
+
+#if ld_target == 4
+    ld $
+    ld $
+#else
+    ld $ ; = 0x5500
+#endif
+ld_target:
+ld ld_target ; = 0x5502
\ No newline at end of file
@@ -0,0 +1,5 @@
+#if 1 == 1
+    #d8 0x11 ; = 0x11
+#elif unknown_symbol
+    #d8 0x22
+#endif
\ No newline at end of file
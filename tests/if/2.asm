@@ -0,0 +1,4 @@
+#if 1 == 2
+    #d8 0x11
+#endif
+#d8 0x22 ; = 0x22
\ No newline at end of file
@@ -0,0 +1,11 @@
+REV = 2
+
+#if REV == 1
+    #d8 0x11
+#elif REV == 2
+    #d8 0x22 ; = 0x22
+#elif REV == 3
+    #d8 0x33
+#else
+    #d8 0x44
+#endif
\ No newline at end of file
@@ -0,0 +1,9 @@
+REV = 5
+
+#if REV == 1
+    #d8 0x11
+#elif REV == 2
+    #d8 0x22
+#else
+    #d8 0x44 ; = 0x44
+#endif
\ No newline at end of file
@@ -0,0 +1,14 @@
+#if 1 == 1
+    #if 1 == 0
+        #d8 0x11
+    #elif 1 == 1
+        #d8 0x22 ; = 0x22
+    #endif
+#else
+    #if 1 == 1
+        #d8 0x33
+    #else
+        #d8 0x44
+    #endif
+#endif
+#d8 0x55 ; = 0x55
\ No newline at end of file
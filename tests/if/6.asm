@@ -0,0 +1,6 @@
+#if 1 == 0
+    label:
+    x = 1
+    this is not a valid instruction
+#endif
+#d8 label ; error: unknown
\ No newline at end of file
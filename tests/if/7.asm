@@ -0,0 +1,6 @@
+#d8 0x11 ; = 0x11
+#if end > 2
+    #d8 0x22 ; = 0x22
+#endif
+#d8 0x33, 0x44 ; = 0x3344
+end:
\ No newline at end of file
@@ -0,0 +1,4 @@
+#if end < 2 ; error: did not converge
+    #d8 0x11, 0x22
+#endif
+end:
\ No newline at end of file
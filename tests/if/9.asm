@@ -0,0 +1,2 @@
+#if 1 ; error: expected boolean
+#endif
\ No newline at end of file
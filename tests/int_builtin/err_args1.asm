@@ -0,0 +1 @@
+#d8 min() ; error: wrong number of arguments
\ No newline at end of file
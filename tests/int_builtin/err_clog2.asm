@@ -0,0 +1 @@
+#d8 clog2(-4) ; error: negative argument
\ No newline at end of file
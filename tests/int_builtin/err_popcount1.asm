@@ -0,0 +1 @@
+#d8 popcount(-1) ; error: unsized negative argument
\ No newline at end of file
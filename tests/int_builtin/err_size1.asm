@@ -0,0 +1 @@
+#d bswap(0x123, 12) ; error: not a multiple of 8
\ No newline at end of file
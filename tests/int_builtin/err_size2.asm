@@ -0,0 +1 @@
+#d sext(0x1234`16, 8) ; error: larger than the extended size
\ No newline at end of file
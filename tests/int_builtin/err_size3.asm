@@ -0,0 +1 @@
+#d sext(1, 1 << 40) ; error: size (= 1099511627776) is outside the range of 1 to 65536 bits
@@ -0,0 +1 @@
+#d bitrev(1, 0x10001) ; error: size (= 65537) is outside the range of 1 to 65536 bits
@@ -0,0 +1 @@
+#d sext(1, 0) ; error: size (= 0) is outside the range of 1 to 65536 bits
@@ -0,0 +1 @@
+#d bitrev(5) ; error: unsized integer argument
\ No newline at end of file
@@ -0,0 +1,3 @@
+#d bitrev(0x01`8)      ; = 0x80
+#d bitrev(0x0001, 16)  ; = 0x8000
+#d bitrev(0b0011, 4) @ 0x0 ; = 0xc0
\ No newline at end of file
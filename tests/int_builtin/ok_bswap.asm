@@ -0,0 +1,3 @@
+#d bswap(0x1234`16)       ; = 0x3412
+#d bswap(0x123456, 24)    ; = 0x563412
+#d bswap(0x12345678, 16) ; = 0x7856
\ No newline at end of file
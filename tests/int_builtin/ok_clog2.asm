@@ -0,0 +1 @@
+#d8 clog2(0), clog2(1), clog2(2), clog2(3), clog2(256), clog2(257) ; = 0x000001020809
\ No newline at end of file
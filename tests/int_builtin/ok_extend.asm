@@ -0,0 +1,8 @@
+#d sext(0xff, 8)           ; = 0xff
+#d sext(0x80`8, 16)        ; = 0xff80
+#d sext(0x7f`8, 16)        ; = 0x007f
+#d sext(0xfe`8, 12) @ 0x0  ; = 0xffe0
+#d8 sext(0xf, 4) == -1 ? 1 : 0 ; = 0x01
+#d zext(0x80`8, 16)        ; = 0x0080
+#d zext(-1, 8)             ; = 0xff
+#d zext(0xa`4, 8) @ 0x5`4  ; = 0x0a5
\ No newline at end of file
@@ -0,0 +1,3 @@
+#fn field_size(count) => clog2(max(count, 2))
+
+#d8 field_size(1), field_size(5), field_size(16) ; = 0x010304
\ No newline at end of file
@@ -0,0 +1,2 @@
+#d8 min(3, -1, 7), max(3, -1, 7), min(5) ; = 0xff0705
+#d8 abs(-5), abs(5)                      ; = 0x0505
\ No newline at end of file
@@ -0,0 +1,2 @@
+#d8 popcount(0xff), popcount(0), popcount(0xa5) ; = 0x080004
+#d8 popcount(-1`16)                            ; = 0x10
\ No newline at end of file
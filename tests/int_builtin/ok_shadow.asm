@@ -0,0 +1,8 @@
+max = 5
+#fn min(a, b) => a + b
+
+#d8 max          ; = 0x05
+#d8 min(1, 2)    ; = 0x03
+#d8 abs(-2)      ; = 0x02
+#d8 popcount     ; = 0x07
+popcount = 7
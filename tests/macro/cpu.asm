@@ -0,0 +1,6 @@
+#ruledef
+{
+    ld {x: u8} => 0x10 @ x
+    jmp {addr: u8} => 0x20 @ addr
+    halt => 0xff
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+#macro bad(a)
+{
+    #d8 {a}
+    #unknown ; error: unknown directive
+}
+
+bad 1 ; error: failed to expand macro `bad`
\ No newline at end of file
@@ -0,0 +1,9 @@
+#macro pair(a, b) ; note: first declared here
+{
+    #d8 {a}, {b}
+}
+
+#macro pair(a, b) ; error: duplicate macro
+{
+    #d8 {a}, {b}
+}
\ No newline at end of file
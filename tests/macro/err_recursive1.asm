@@ -0,0 +1,6 @@
+#macro forever(a)
+{
+    forever {a} ; error: failed to expand / error: recursive
+}
+
+forever 1 ; error: failed to expand
\ No newline at end of file
@@ -0,0 +1,6 @@
+#macro pair(a, b) ; note: takes 2 arguments
+{
+    #d8 {a}, {b}
+}
+
+pair 1, 2, 3 ; error: failed to expand / error: wrong number
\ No newline at end of file
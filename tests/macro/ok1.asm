@@ -0,0 +1,11 @@
+#include "cpu.asm"
+
+#macro load_pair(a, b)
+{
+    ld {a}
+    ld {b}
+    #d8 {a} + {b}
+}
+
+load_pair 1, 2 ; = 0x1001100203
+load_pair 3, 4 ; = 0x1003100407
\ No newline at end of file
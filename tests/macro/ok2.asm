@@ -0,0 +1,15 @@
+#include "cpu.asm"
+
+#macro spin()
+{
+.loop:
+    jmp .loop
+    jmp .end
+.end:
+}
+
+start:
+    spin       ; = 0x20002004
+    spin       ; = 0x20042008
+.end:
+    jmp .end   ; = 0x2008
\ No newline at end of file
@@ -0,0 +1,18 @@
+#include "cpu.asm"
+
+#fn add(a, b) => a + b
+
+#macro inner(x)
+{
+    ld {x}
+}
+
+#macro outer(x, y)
+{
+    inner {x}
+    inner {y}
+    halt
+}
+
+outer add(1, 2), (3 + 4) ; = 0x10031007ff
+outer 5, 6               ; = 0x10051006ff
\ No newline at end of file
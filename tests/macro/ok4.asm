@@ -0,0 +1,11 @@
+#include "cpu.asm"
+
+#macro jump_to(target)
+{
+    jmp {target}
+}
+
+jump_to later ; = 0x2004
+jump_to later ; = 0x2004
+later:
+    halt      ; = 0xff
\ No newline at end of file
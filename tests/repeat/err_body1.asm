@@ -0,0 +1,3 @@
+#repeat 2 { ; error: failed to expand `#repeat` iteration 0
+    #unknown ; error: unknown directive
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+#for i in 254..257 { ; error: failed to expand `#for` with `i = 256`
+    #d8 i ; error: larger than the directive size
+}
\ No newline at end of file
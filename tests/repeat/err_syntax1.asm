@@ -0,0 +1,2 @@
+#for i 0..2 { ; error: expected `in`
+}
\ No newline at end of file
@@ -0,0 +1,2 @@
+#for i in 0 2 { ; error: expected `..`
+}
\ No newline at end of file
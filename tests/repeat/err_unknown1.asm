@@ -0,0 +1,2 @@
+#for i in 0..n { ; error: unknown
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+#repeat 3
+{
+    #d8 0xaa
+}
+; = 0xaaaaaa
\ No newline at end of file
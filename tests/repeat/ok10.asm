@@ -0,0 +1,8 @@
+; The loop variable hides other symbols of the same name,
+; and stays bound after a global label in the body
+i = 0x55
+#for i in 3..4 {
+entry:
+    #d8 i, entry
+}
+#d8 i ; = 0x030055
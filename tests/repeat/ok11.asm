@@ -0,0 +1,9 @@
+#macro pair(a)
+{
+    #d8 {a}, {a} + 0x10
+}
+
+#for i in 0..2 {
+    pair i
+}
+; = 0x00100111
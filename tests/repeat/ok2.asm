@@ -0,0 +1,4 @@
+#repeat 3 {
+    #d8 0xaa, 0xbb
+}
+#d8 0xcc ; = 0xaabbaabbaabbcc
\ No newline at end of file
@@ -0,0 +1,4 @@
+#for i in 0..4 {
+    #d8 i * i
+}
+; = 0x00010409
\ No newline at end of file
@@ -0,0 +1,5 @@
+count = 3
+#for i in 1..count + 1 {
+    #d8 i, -i
+}
+; = 0x01ff02fe03fd
\ No newline at end of file
@@ -0,0 +1,6 @@
+#for row in 0..2 {
+    #for col in 0..3 {
+        #d8 row * 0x10 + col
+    }
+}
+; = 0x000102101112
\ No newline at end of file
@@ -0,0 +1,12 @@
+#ruledef test
+{
+    ld {x: u8} => 0x11 @ x
+    jmp {x: u8} => 0x22 @ x
+}
+
+#for i in 5..7 {
+.loop:
+    ld i
+    jmp .loop
+}
+; = 0x1105220011062204
\ No newline at end of file
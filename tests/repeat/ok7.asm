@@ -0,0 +1,7 @@
+#for i in 4..0 {
+    #d8 0xff
+}
+#repeat 0 {
+    #d8 0xff
+}
+#d8 0x12 ; = 0x12
\ No newline at end of file
@@ -0,0 +1,7 @@
+; Names after a `.` are not the loop variable
+x = 0x10
+.i = 0x20
+#for i in 0..2 {
+    #d8 i, x.i
+}
+; = 0x00200120
\ No newline at end of file
@@ -0,0 +1,11 @@
+#ruledef test
+{
+    jmp {x: u8} => 0x22 @ x
+    jmp {x: u16} => 0x33 @ x
+}
+
+#for i in 0..2 {
+    jmp end + i
+}
+end:
+; = 0x22042205
\ No newline at end of file
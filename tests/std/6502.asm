@@ -0,0 +1,28 @@
+#include "<std/6502.asm>"
+
+start:
+lda #0x12       ; = 0xa912
+lda #-1         ; = 0xa9ff
+lda 0x34        ; = 0xa534
+lda <0x34, x    ; = 0xb534
+lda 0x1234      ; = 0xad3412
+lda 0x1234, x   ; = 0xbd3412
+lda 0x1234, y   ; = 0xb93412
+lda (0x20, x)   ; = 0xa120
+lda (0x20), y   ; = 0xb120
+sta 0x0200      ; = 0x8d0002
+ldx 0x10, y     ; = 0xb610
+asl a           ; = 0x0a
+rol 0x10        ; = 0x2610
+inc 0x1234, x   ; = 0xfe3412
+jmp (0x1234)    ; = 0x6c3412
+jsr start       ; = 0x200000
+bne start       ; = 0xd0d8
+beq forward     ; = 0xf002
+nop             ; = 0xea
+brk             ; = 0x00
+forward:
+rts             ; = 0x60
+bit 0x10        ; = 0x2410
+bit <0x20       ; = 0x2420
+bit 0x1234      ; = 0x2c3412
@@ -0,0 +1,34 @@
+#include "<std/8080.asm>"
+
+start:
+mov a, b        ; = 0x78
+mov m, a        ; = 0x77
+mov e, m        ; = 0x5e
+mvi c, 0x42     ; = 0x0e42
+mvi m, 0xff     ; = 0x36ff
+lxi h, 0x1234   ; = 0x213412
+lxi sp, 0xf000  ; = 0x3100f0
+lda 0x2000      ; = 0x3a0020
+shld 0x2002     ; = 0x220220
+ldax d          ; = 0x1a
+stax b          ; = 0x02
+add m           ; = 0x86
+sbb c           ; = 0x99
+cmp a           ; = 0xbf
+sui 1           ; = 0xd601
+cpi 0x0d        ; = 0xfe0d
+inr a           ; = 0x3c
+dcr m           ; = 0x35
+inx h           ; = 0x23
+dcx sp          ; = 0x3b
+dad d           ; = 0x19
+jnz start       ; = 0xc20000
+cm start        ; = 0xfc0000
+rz              ; = 0xc8
+rst 7           ; = 0xff
+push psw        ; = 0xf5
+pop b           ; = 0xc1
+in 0x10         ; = 0xdb10
+out 0x11        ; = 0xd311
+xchg            ; = 0xeb
+hlt             ; = 0x76
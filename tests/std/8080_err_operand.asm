@@ -0,0 +1,3 @@
+#include "<std/8080.asm>"
+
+mvi a, 0x100 ; error: failed / error: out of range
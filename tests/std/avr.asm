@@ -0,0 +1,129 @@
+#include "<std/avr.asm>"
+
+add r1, r2       ; = 0x120c
+adc r31, r16     ; = 0xf01f
+sub r0, r31      ; = 0x0f1a
+sbc r5, r6       ; = 0x5608
+and r7, r8       ; = 0x7820
+or r9, r10       ; = 0x9a28
+eor r11, r12     ; = 0xbc24
+cp r13, r14      ; = 0xde14
+cpc r15, r16     ; = 0xf006
+cpse r17, r18    ; = 0x1213
+mov r19, r20     ; = 0x342f
+mul r21, r22     ; = 0x569f
+lsl r23          ; = 0x770f
+rol r24          ; = 0x881f
+tst r25          ; = 0x9923
+clr r26          ; = 0xaa27
+movw r30, r28    ; = 0xfe01
+muls r16, r31    ; = 0x0f02
+mulsu r17, r23   ; = 0x1703
+fmul r18, r19    ; = 0x2b03
+fmuls r20, r21   ; = 0xc503
+fmulsu r22, r16  ; = 0xe803
+subi r16, 0x12   ; = 0x0251
+sbci r17, 0xff   ; = 0x1f4f
+andi r18, 0x0f   ; = 0x2f70
+cbr r19, 0x0f    ; = 0x307f
+ori r20, 0x80    ; = 0x4068
+sbr r21, 1       ; = 0x5160
+cpi r22, 100     ; = 0x6436
+ldi r31, 0xab    ; = 0xfbea
+ser r23          ; = 0x7fef
+adiw r24, 63     ; = 0xcf96
+sbiw r30, 1      ; = 0x3197
+com r0           ; = 0x0094
+neg r1           ; = 0x1194
+swap r2          ; = 0x2294
+inc r3           ; = 0x3394
+asr r4           ; = 0x4594
+lsr r5           ; = 0x5694
+ror r6           ; = 0x6794
+dec r7           ; = 0x7a94
+push r8          ; = 0x8f92
+pop r9           ; = 0x9f90
+jmp 0x3fffe      ; = 0x0d94ffff
+call 0x1234      ; = 0x0e941a09
+ijmp             ; = 0x0994
+icall            ; = 0x0995
+eijmp            ; = 0x1994
+eicall           ; = 0x1995
+ret              ; = 0x0895
+reti             ; = 0x1895
+sbrc r0, 7       ; = 0x07fc
+sbrs r31, 0      ; = 0xf0ff
+sbic 0x1f, 7     ; = 0xff99
+sbis 1, 2        ; = 0x0a9b
+sbi 3, 4         ; = 0x1c9a
+cbi 5, 6         ; = 0x2e98
+in r0, 0x3f      ; = 0x0fb6
+out 0x3e, r29    ; = 0xdebf
+bset 3           ; = 0x3894
+bclr 5           ; = 0xd894
+bst r1, 2        ; = 0x12fa
+bld r3, 4        ; = 0x34f8
+sec              ; = 0x0894
+sez              ; = 0x1894
+sen              ; = 0x2894
+sev              ; = 0x3894
+ses              ; = 0x4894
+seh              ; = 0x5894
+set              ; = 0x6894
+sei              ; = 0x7894
+clc              ; = 0x8894
+clz              ; = 0x9894
+cln              ; = 0xa894
+clv              ; = 0xb894
+cls              ; = 0xc894
+clh              ; = 0xd894
+clt              ; = 0xe894
+cli              ; = 0xf894
+lds r0, 0x1234   ; = 0x00903412
+sts 0xfedc, r31  ; = 0xf093dcfe
+ld r0, X         ; = 0x0c90
+ld r1, X+        ; = 0x1d90
+ld r2, -X        ; = 0x2e90
+ld r3, Y         ; = 0x3880
+ld r4, Y+        ; = 0x4990
+ld r5, -Y        ; = 0x5a90
+ld r6, Z         ; = 0x6080
+ld r7, Z+        ; = 0x7190
+ld r8, -Z        ; = 0x8290
+ldd r9, Y+63     ; = 0x9fac
+ldd r10, Z+1     ; = 0xa180
+st X, r0         ; = 0x0c92
+st X+, r1        ; = 0x1d92
+st -X, r2        ; = 0x2e92
+st Y, r3         ; = 0x3882
+st Y+, r4        ; = 0x4992
+st -Y, r5        ; = 0x5a92
+st Z, r6         ; = 0x6082
+st Z+, r7        ; = 0x7192
+st -Z, r8        ; = 0x8292
+std Y+33, r9     ; = 0x99a2
+std Z+8, r10     ; = 0xa086
+lpm              ; = 0xc895
+lpm r0, Z        ; = 0x0490
+lpm r1, Z+       ; = 0x1590
+elpm             ; = 0xd895
+elpm r2, Z       ; = 0x2690
+elpm r3, Z+      ; = 0x3790
+spm              ; = 0xe895
+nop              ; = 0x0000
+sleep            ; = 0x8895
+wdr              ; = 0xa895
+break            ; = 0x9895
+loop:
+rjmp loop         ; = 0xffcf
+rjmp $ + 4        ; = 0x01c0
+rjmp $ - 2        ; = 0xfecf
+rcall $ + 100     ; = 0x31d0
+breq $ - 126      ; = 0x01f2
+brne $ + 128      ; = 0xf9f5
+brbs 3, $ + 10    ; = 0x23f0
+brbc 6, $ - 4     ; = 0xeef7
+brlo $ + 2        ; = 0x00f0
+brsh $ + 2        ; = 0x00f4
+brge $ + 2        ; = 0x04f4
+brid $ + 2        ; = 0x07f4
\ No newline at end of file
@@ -0,0 +1,3 @@
+#include "<std/avr.asm>"
+
+ldi r16, 0x100 ; error: failed / error: out of range
\ No newline at end of file
@@ -0,0 +1,39 @@
+#include "<std/chip8.asm>"
+
+#bankdef prog { #addr 0x200, #outp 0 }
+
+start:
+cls             ; = 0x00e0
+ret             ; = 0x00ee
+jp start        ; = 0x1200
+jp v0, 0x300    ; = 0xb300
+call 0x456      ; = 0x2456
+se v1, 0x20     ; = 0x3120
+se v1, v2       ; = 0x5120
+sne va, 0xff    ; = 0x4aff
+sne va, vb      ; = 0x9ab0
+ld v3, 0x7f     ; = 0x637f
+ld v3, v4       ; = 0x8340
+ld i, 0xabc     ; = 0xaabc
+ld v5, dt       ; = 0xf507
+ld v5, k        ; = 0xf50a
+ld dt, v6       ; = 0xf615
+ld st, v6       ; = 0xf618
+ld f, v7        ; = 0xf729
+ld b, v7        ; = 0xf733
+ld [i], vf      ; = 0xff55
+ld vf, [i]      ; = 0xff65
+add v0, 1       ; = 0x7001
+add v0, v1      ; = 0x8014
+add i, v2       ; = 0xf21e
+or v1, v2       ; = 0x8121
+and v1, v2      ; = 0x8122
+xor v1, v2      ; = 0x8123
+sub v1, v2      ; = 0x8125
+shr v1          ; = 0x8106
+subn v1, v2     ; = 0x8127
+shl v1, v2      ; = 0x812e
+rnd vc, 0x0f    ; = 0xcc0f
+drw v1, v2, 5   ; = 0xd125
+skp v9          ; = 0xe99e
+sknp v9         ; = 0xe9a1
\ No newline at end of file
@@ -0,0 +1,76 @@
+#include "<std/rv32i.asm>"
+
+#bankdef text { #addr 0x10000, #outp 0 }
+
+lui a0, 0x12345       ; = 0x37553412
+auipc t0, 1           ; = 0x97120000
+jalr ra, 4(a1)        ; = 0xe7804500
+jalr ra, 0(a1)        ; = 0xe7800500
+lb a0, -4(sp)         ; = 0x0305c1ff
+lh a1, 2(s0)          ; = 0x83152400
+lw s1, 2044(t6)       ; = 0x83a4cf7f
+lbu a0, 0(a0)         ; = 0x03450500
+lhu x5, -2048(x6)     ; = 0x83520380
+sb a0, -1(sp)         ; = 0xa30fa1fe
+sh a1, 6(sp)          ; = 0x2313b100
+sw ra, 12(sp)         ; = 0x23261100
+addi sp, sp, -16      ; = 0x130101ff
+slti a0, a1, 5        ; = 0x13a55500
+sltiu a0, a1, 1       ; = 0x13b51500
+xori a0, a1, -1       ; = 0x13c5f5ff
+ori a0, a1, 0x7ff     ; = 0x13e5f57f
+andi a0, a1, 0xff     ; = 0x13f5f50f
+slli a0, a1, 31       ; = 0x1395f501
+srli a0, a1, 3        ; = 0x13d53500
+srai a0, a1, 3        ; = 0x13d53540
+add a0, a1, a2        ; = 0x3385c500
+sub s10, s11, t3      ; = 0x338dcd41
+sll t4, t5, t6        ; = 0xb31eff01
+slt a0, a1, a2        ; = 0x33a5c500
+sltu a0, a1, a2       ; = 0x33b5c500
+xor a0, a1, a2        ; = 0x33c5c500
+srl a0, a1, a2        ; = 0x33d5c500
+sra a0, a1, a2        ; = 0x33d5c540
+or a0, a1, a2         ; = 0x33e5c500
+and a0, a1, a2        ; = 0x33f5c500
+fence                 ; = 0x0f00f00f
+ecall                 ; = 0x73000000
+ebreak                ; = 0x73001000
+nop                   ; = 0x13000000
+li a0, -5             ; = 0x1305b0ff
+mv a0, a1             ; = 0x13850500
+not a0, a1            ; = 0x13c5f5ff
+neg a0, a1            ; = 0x3305b040
+seqz a0, a1           ; = 0x13b51500
+snez a0, a1           ; = 0x3335b000
+sltz a0, a1           ; = 0x33a50500
+sgtz a0, a1           ; = 0x3325b000
+jr t0                 ; = 0x67800200
+jalr t0               ; = 0xe7800200
+ret                   ; = 0x67800000
+beq a0, a1, $ - 8     ; = 0xe30cb5fe
+bne a0, a1, $ + 8     ; = 0x6314b500
+blt a0, a1, $ + 4094  ; = 0xe34fb57e
+bge a0, a1, $ - 4096  ; = 0x6350b580
+bltu t0, t1, $ + 16   ; = 0x63e86200
+bgeu t0, t1, $ + 16   ; = 0x63f86200
+jal ra, $ + 2048      ; = 0xef001000
+jal zero, $ - 4       ; = 0x6ff0dfff
+beqz a0, $ + 12       ; = 0x63060500
+bnez a0, $ + 12       ; = 0x63160500
+blez a0, $ + 12       ; = 0x6356a000
+bgez a0, $ + 12       ; = 0x63560500
+bltz a0, $ + 12       ; = 0x63460500
+bgtz a0, $ + 12       ; = 0x6346a000
+bgt a0, a1, $ + 12    ; = 0x63c6a500
+ble a0, a1, $ + 12    ; = 0x63d6a500
+bgtu a0, a1, $ + 12   ; = 0x63e6a500
+bleu a0, a1, $ + 12   ; = 0x63f6a500
+j $ - 16              ; = 0x6ff01fff
+jal $ - 16            ; = 0xeff01fff
+li a0, 0x12345678     ; = 0x3755341213058567
+li a1, -0x801         ; = 0xb7f5ffff9385f57f
+li a2, 0x7ff          ; = 0x1306f07f
+li a3, 0x800          ; = 0xb716000093860680
+call $ + 8            ; = 0x97000000e7808000
+la a0, $ + 0x1000     ; = 0x1715000013050500
\ No newline at end of file
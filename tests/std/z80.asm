@@ -0,0 +1,91 @@
+#include "<std/z80.asm>"
+
+start:
+ld a, b             ; = 0x78
+ld (hl), e          ; = 0x73
+ld c, (hl)          ; = 0x4e
+ld d, 0x42          ; = 0x1642
+ld (hl), 0x42       ; = 0x3642
+ld a, (ix + 5)      ; = 0xdd7e05
+ld e, (iy - 2)      ; = 0xfd5efe
+ld (ix), h          ; = 0xdd7400
+ld (iy + 1), 0xff   ; = 0xfd3601ff
+ld a, (bc)          ; = 0x0a
+ld (de), a          ; = 0x12
+ld a, (0x1234)      ; = 0x3a3412
+ld (0x1234), a      ; = 0x323412
+ld a, i             ; = 0xed57
+ld r, a             ; = 0xed4f
+ld bc, 0x1234       ; = 0x013412
+ld sp, 0xf000       ; = 0x3100f0
+ld ix, 0x1234       ; = 0xdd213412
+ld hl, (0x1234)     ; = 0x2a3412
+ld (0x1234), hl     ; = 0x223412
+ld de, (0x1234)     ; = 0xed5b3412
+ld (0x1234), sp     ; = 0xed733412
+ld iy, (0x1234)     ; = 0xfd2a3412
+ld sp, hl           ; = 0xf9
+ld sp, ix           ; = 0xddf9
+push af             ; = 0xf5
+pop bc              ; = 0xc1
+push iy             ; = 0xfde5
+ex de, hl           ; = 0xeb
+ex af, af           ; = 0x08
+ex (sp), ix         ; = 0xdde3
+exx                 ; = 0xd9
+ldir                ; = 0xedb0
+cpdr                ; = 0xedb9
+add a, c            ; = 0x81
+adc a, (hl)         ; = 0x8e
+sub 0x10            ; = 0xd610
+sbc a, 1            ; = 0xde01
+and (ix + 3)        ; = 0xdda603
+xor a               ; = 0xaf
+or 0x80             ; = 0xf680
+cp (iy)             ; = 0xfdbe00
+inc a               ; = 0x3c
+dec (hl)            ; = 0x35
+inc (ix + 7)        ; = 0xdd3407
+inc hl              ; = 0x23
+dec sp              ; = 0x3b
+inc ix              ; = 0xdd23
+add hl, de          ; = 0x19
+adc hl, sp          ; = 0xed7a
+sbc hl, bc          ; = 0xed42
+add ix, ix          ; = 0xdd29
+add iy, bc          ; = 0xfd09
+daa                 ; = 0x27
+neg                 ; = 0xed44
+halt                ; = 0x76
+im 2                ; = 0xed5e
+rlca                ; = 0x07
+rld                 ; = 0xed6f
+rlc b               ; = 0xcb00
+srl (hl)            ; = 0xcb3e
+rr (ix + 2)         ; = 0xddcb021e
+bit 7, a            ; = 0xcb7f
+res 0, (hl)         ; = 0xcb86
+set 3, (iy - 1)     ; = 0xfdcbffde
+jp 0x1234           ; = 0xc33412
+jp nz, 0x1234       ; = 0xc23412
+jp m, 0x1234        ; = 0xfa3412
+jp (hl)             ; = 0xe9
+jp (ix)             ; = 0xdde9
+call 0x1234         ; = 0xcd3412
+call pe, 0x1234     ; = 0xec3412
+ret                 ; = 0xc9
+ret c               ; = 0xd8
+reti                ; = 0xed4d
+rst 0x38            ; = 0xff
+in a, (0x10)        ; = 0xdb10
+in b, (c)           ; = 0xed40
+out (0x10), a       ; = 0xd310
+out (c), e          ; = 0xed59
+otir                ; = 0xedb3
+
+loop:
+jr loop             ; = 0x18fe
+jr nc, loop         ; = 0x30fc
+djnz loop           ; = 0x10fa
+jr z, after         ; = 0x2800
+after:
\ No newline at end of file
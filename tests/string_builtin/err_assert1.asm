@@ -0,0 +1,3 @@
+#fn check(x) => assert(x < 4, fmt("value {} is too large", x))
+
+#d8 check(5) ; error: failed / error:_:1: assertion failed: value 5 is too large
\ No newline at end of file
@@ -0,0 +1 @@
+#d chr(0xd800) ; error: invalid character code
\ No newline at end of file
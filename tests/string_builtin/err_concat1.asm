@@ -0,0 +1 @@
+#d ascii("a") + utf16le("b") ; error: concatenation of strings with different encodings
\ No newline at end of file
@@ -0,0 +1 @@
+#d fmt("{} {}", 1) ; error: missing argument for format string
\ No newline at end of file
@@ -0,0 +1 @@
+#d fmt("{}", 1, 2) ; error: too many arguments for format string
\ No newline at end of file
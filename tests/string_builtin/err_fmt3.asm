@@ -0,0 +1 @@
+#d fmt("{:q}", 1) ; error: invalid format placeholder
\ No newline at end of file
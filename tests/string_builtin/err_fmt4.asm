@@ -0,0 +1 @@
+#d fmt("{", 1) ; error: invalid format string
\ No newline at end of file
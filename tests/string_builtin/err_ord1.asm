@@ -0,0 +1 @@
+#d8 ord("ab") ; error: expected a string of one character
\ No newline at end of file
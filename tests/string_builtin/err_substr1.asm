@@ -0,0 +1 @@
+#d substr("hello", 3, 3) ; error: substring out of range
\ No newline at end of file
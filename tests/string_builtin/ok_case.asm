@@ -0,0 +1,3 @@
+#d upper("abc1") ; = 0x41424331
+#d lower("ABC1") ; = 0x61626331
+#d upper(ascii("a")) ; = 0x41
\ No newline at end of file
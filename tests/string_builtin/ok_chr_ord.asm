@@ -0,0 +1,3 @@
+#d chr(0x41)         ; = 0x41
+#d8 ord("a")         ; = 0x61
+#d chr(ord("a") + 1) ; = 0x62
\ No newline at end of file
@@ -0,0 +1,3 @@
+#d "ab" + "cd"   ; = 0x61626364
+#d8 "a" + 1      ; = 0x62
+#d "" + "x" + "" ; = 0x78
\ No newline at end of file
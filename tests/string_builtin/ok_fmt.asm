@@ -0,0 +1,9 @@
+#d fmt("{:04x}", 0xab)  ; = 0x30306162
+#d fmt("x{}y", 12)      ; = 0x78313279
+#d fmt("{:3}", 5)       ; = 0x202035
+#d fmt("{:X}", 255)     ; = 0x4646
+#d fmt("{:08b}", 5)     ; = 0x3030303030313031
+#d fmt("{:o}", 8)       ; = 0x3130
+#d fmt("{:03}", -5)     ; = 0x2d3035
+#d fmt("{{}}")          ; = 0x7b7d
+#d fmt("{}{}", "a", 1)  ; = 0x6131
\ No newline at end of file
@@ -0,0 +1,4 @@
+#fn name(i) => "item" + fmt("{:02}", i)
+
+#d name(7)  ; = 0x6974656d3037
+#d name(12) ; = 0x6974656d3132
\ No newline at end of file
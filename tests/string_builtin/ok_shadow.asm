@@ -0,0 +1,7 @@
+#fn upper(s) => s
+
+#d upper("a")    ; = 0x61
+#d lower("B")    ; = 0x62
+fmt:
+#d8 fmt, strlen  ; = 0x0203
+strlen = 3
@@ -0,0 +1,3 @@
+#d8 strlen("hello") ; = 0x05
+#d8 strlen("")      ; = 0x00
+#d8 strlen("héllo") ; = 0x05
\ No newline at end of file
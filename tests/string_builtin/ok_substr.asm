@@ -0,0 +1,3 @@
+#d substr("hello", 1, 3) ; = 0x656c6c
+#d substr("hello", 0, 5) ; = 0x68656c6c6f
+#d substr("hello", 5, 0) ; = 0x
\ No newline at end of file
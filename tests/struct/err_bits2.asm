@@ -0,0 +1,3 @@
+#struct Point { x: 8, y: 8 }
+#bits 16
+#struct Actor { hp: 16, pos: Point } ; error: struct `Point` was declared with `#bits 8`, but the current `#bits` is 16 / note:_:1: declared here
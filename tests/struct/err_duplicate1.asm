@@ -0,0 +1,2 @@
+#struct Point { x: 8 }
+#struct Point { y: 8 } ; error: duplicate struct / note:_:1: first declared here
\ No newline at end of file
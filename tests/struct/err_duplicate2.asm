@@ -0,0 +1 @@
+#struct Point { x: 8, x: 8 } ; error: duplicate symbol / note:_:1: first declared here
\ No newline at end of file
@@ -0,0 +1 @@
+#struct Point { x: 8, y: 4 } ; error: not a multiple of the word size
\ No newline at end of file
@@ -0,0 +1 @@
+#struct Header { size: 8, kind: 8 } ; error: field cannot be named `size`
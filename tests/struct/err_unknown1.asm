@@ -0,0 +1 @@
+#res player: Point ; error: unknown struct
\ No newline at end of file
@@ -0,0 +1 @@
+#struct Actor { pos: Point } ; error: unknown
\ No newline at end of file
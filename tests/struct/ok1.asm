@@ -0,0 +1,3 @@
+#struct Point { x: 16, y: 16, flags: 8 }
+
+#d8 Point.x, Point.y, Point.flags, Point.size ; = 0x00020405
\ No newline at end of file
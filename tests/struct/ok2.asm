@@ -0,0 +1,15 @@
+#struct Point
+{
+    x: 16
+    y: 16
+}
+
+#struct Sprite
+{
+    tile: 8
+    pos: Point
+    attrs: 8 * 2
+}
+
+#d8 Sprite.tile, Sprite.pos, Sprite.pos.x, Sprite.pos.y ; = 0x00010103
+#d8 Sprite.attrs, Sprite.size                          ; = 0x0507
\ No newline at end of file
@@ -0,0 +1,5 @@
+#bits 16
+
+#struct Regs { ctrl: 16, status: 16, data: 32 }
+
+#d16 Regs.ctrl, Regs.status, Regs.data, Regs.size ; = 0x0000000100020004
\ No newline at end of file
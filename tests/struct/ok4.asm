@@ -0,0 +1,14 @@
+#ruledef test
+{
+    ld {x} => 0x55 @ x`8
+}
+
+#struct Point { x: 8, y: 8 }
+
+ld $                ; = 0x5500
+#res player: Point  ; = 0x0000
+#res enemy: Point   ; = 0x0000
+ld player           ; = 0x5502
+ld player.y         ; = 0x5503
+ld enemy.x          ; = 0x5504
+ld enemy.y          ; = 0x5505
\ No newline at end of file
@@ -0,0 +1,17 @@
+#ruledef test
+{
+    ld {x} => 0x55 @ x`8
+}
+
+#struct Point { x: 8, y: 8 }
+#struct Actor { hp: 8, pos: Point }
+
+#bankdef ram { #addr 0x200, #size 0x100 }
+#bankdef rom { #addr 0x8000, #size 0x10, #outp 0 }
+
+#bank ram
+#res hero: Actor
+
+#bank rom
+ld hero.pos.y ; = 0x5502
+ld hero.hp    ; = 0x5500
\ No newline at end of file
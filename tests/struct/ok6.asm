@@ -0,0 +1,11 @@
+#ruledef test
+{
+    ld {x} => 0x55 @ x`8
+}
+
+#struct Point { x: 8, y: 8 }
+
+start:
+#res point: Point ; = 0x0000
+.local:
+ld start.local ; = 0x5502
\ No newline at end of file
@@ -0,0 +1,8 @@
+#bits 16
+#struct Pair { a: 16, b: 32 }
+#struct Table { first: Pair, second: Pair }
+
+#d16 Pair.b, Pair.size          ; = 0x00010003
+#d16 Table.second.b, Table.size ; = 0x00040006
+#res entry: Table               ; = 0x000000000000000000000000
+#d16 entry.second               ; = 0x0007